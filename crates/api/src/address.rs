@@ -1,5 +1,17 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    sync::Arc,
+};
 
+use andromeda_common::Network;
+use bitcoin::{
+    absolute::LockTime,
+    hashes::{hex::FromHex, sha256, Hash},
+    transaction::Version,
+    Address, Amount, OutPoint, Script, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid, Witness,
+};
+use futures::{stream, StreamExt};
 use serde::{Deserialize, Serialize};
 
 use super::BASE_WALLET_API_V1;
@@ -25,6 +37,23 @@ pub struct AddressBalance {
     pub MempoolSpentBitcoin: u64,
 }
 
+/// Net balance of a Bitcoin address (sats). Pending balance can be negative
+/// when mempool spends exceed mempool funding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressNetBalance {
+    pub confirmed: i64,
+    pub pending: i64,
+}
+
+impl From<&AddressBalance> for AddressNetBalance {
+    fn from(balance: &AddressBalance) -> Self {
+        AddressNetBalance {
+            confirmed: balance.ChainFundedBitcoin as i64 - balance.ChainSpentBitcoin as i64,
+            pending: balance.MempoolFundedBitcoin as i64 - balance.MempoolSpentBitcoin as i64,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[allow(non_snake_case)]
 pub struct GetAddressBalanceResponseBody {
@@ -71,16 +100,74 @@ pub struct ApiTx {
     pub TransactionStatus: ApiTransactionStatus,
 }
 
-#[derive(Debug, Deserialize)]
-#[allow(non_snake_case)]
-pub struct GetScriptHashTransactionsResponseBody {
-    pub Code: u16,
-    pub Transactions: Vec<ApiTx>,
+impl TryFrom<ApiVin> for TxIn {
+    type Error = Error;
+
+    fn try_from(vin: ApiVin) -> Result<Self, Self::Error> {
+        // Coinbase inputs don't spend any previous output
+        let previous_output = if vin.IsCoinbase != 0 {
+            OutPoint::null()
+        } else {
+            OutPoint::new(Txid::from_str(&vin.TransactionID)?, vin.Vout)
+        };
+
+        let witness = vin
+            .Witness
+            .unwrap_or_default()
+            .iter()
+            .map(Vec::<u8>::from_hex)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(TxIn {
+            previous_output,
+            script_sig: ScriptBuf::from_hex(&vin.ScriptSig)?,
+            sequence: Sequence(vin.Sequence),
+            witness: Witness::from_slice(&witness),
+        })
+    }
+}
+
+impl TryFrom<ApiVout> for TxOut {
+    type Error = Error;
+
+    fn try_from(vout: ApiVout) -> Result<Self, Self::Error> {
+        Ok(TxOut {
+            value: Amount::from_sat(vout.Value),
+            script_pubkey: ScriptBuf::from_hex(&vout.ScriptPubKey)?,
+        })
+    }
+}
+
+impl TryFrom<ApiTx> for Transaction {
+    type Error = Error;
+
+    fn try_from(tx: ApiTx) -> Result<Self, Self::Error> {
+        let input = tx
+            .Vin
+            .unwrap_or_default()
+            .into_iter()
+            .map(TxIn::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let output = tx
+            .Vout
+            .unwrap_or_default()
+            .into_iter()
+            .map(TxOut::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Transaction {
+            version: Version(tx.Version),
+            lock_time: LockTime::from_consensus(tx.Locktime),
+            input,
+            output,
+        })
+    }
 }
 
 #[derive(Debug, Deserialize)]
 #[allow(non_snake_case)]
-pub struct GetScriptHashTransactionsAtTransactionIDResponseBody {
+pub struct GetScriptHashTransactionsResponseBody {
     pub Code: u16,
     pub Transactions: Vec<ApiTx>,
 }
@@ -107,6 +194,28 @@ pub struct GetScriptHashesTransactionsResponseBody {
     pub Transactions: TransactionsByScriptHash,
 }
 
+/// Computes the scripthash of a script pubkey, as expected by scripthash
+/// endpoints: the sha256 of the script bytes, hex-encoded in natural byte
+/// order (this is what the esplora client uses for syncing too).
+pub fn scripthash_for_script(script_pubkey: &Script) -> String {
+    sha256::Hash::hash(script_pubkey.as_bytes()).to_string()
+}
+
+/// Parses a Bitcoin address, checks it belongs to the provided network and
+/// computes the scripthash of its script pubkey.
+pub fn scripthash_for_address(address: &str, network: Network) -> Result<String, Error> {
+    let address = Address::from_str(address)?.require_network(network.into())?;
+
+    Ok(scripthash_for_script(&address.script_pubkey()))
+}
+
+/// Maximum number of confirmed transactions returned by a single scripthash
+/// transactions page
+pub const SCRIPTHASH_TRANSACTIONS_PER_PAGE: usize = 25;
+
+/// Maximum number of address balance requests sent at the same time
+const PARALLEL_ADDRESS_BALANCE_REQUESTS: usize = 5;
+
 impl ApiClient for AddressClient {
     fn new(api_client: Arc<ProtonWalletApiClient>) -> Self {
         Self { api_client }
@@ -131,6 +240,29 @@ impl AddressClient {
         Ok(parsed.Balance)
     }
 
+    /// Get balances of several Bitcoin addresses, sending a bounded number of
+    /// requests concurrently. Each address comes with its own result, so a
+    /// failed lookup doesn't fail the whole batch. Results are returned in
+    /// the same order as the provided addresses.
+    pub async fn get_address_balances(&self, addresses: Vec<String>) -> Vec<(String, Result<AddressBalance, Error>)> {
+        stream::iter(addresses)
+            .map(|address| async move {
+                let balance = self.get_address_balance(address.clone()).await;
+                (address, balance)
+            })
+            .buffered(PARALLEL_ADDRESS_BALANCE_REQUESTS)
+            .collect()
+            .await
+    }
+
+    /// Get confirmed and pending net balances of a Bitcoin address, computed
+    /// from funded and spent amounts on chain and in mempool.
+    pub async fn get_address_net_balance(&self, address: String) -> Result<AddressNetBalance, Error> {
+        let balance = self.get_address_balance(address).await?;
+
+        Ok(AddressNetBalance::from(&balance))
+    }
+
     /// Get transaction history for the specified scripthash, sorted by newest
     /// first. Returns up to 50 mempool transactions plus the first 25
     /// confirmed transactions.
@@ -142,6 +274,15 @@ impl AddressClient {
         Ok(parsed.Transactions)
     }
 
+    /// Get transaction history for the specified address, sorted by newest
+    /// first. Returns up to 50 mempool transactions plus the first 25
+    /// confirmed transactions.
+    pub async fn get_transactions_for_address(&self, address: String, network: Network) -> Result<Vec<ApiTx>, Error> {
+        let script_hash = scripthash_for_address(&address, network)?;
+
+        self.get_scripthash_transactions(script_hash).await
+    }
+
     /// Get transaction history for the specified scripthash, sorted by newest
     /// first. Returns up to 50 mempool transactions plus the first 25
     /// confirmed transactions at TxID.
@@ -160,6 +301,52 @@ impl AddressClient {
         Ok(parsed.Transactions)
     }
 
+    /// Get the whole transaction history for the specified scripthash, sorted
+    /// by newest first. Pages are fetched using the last transaction of the
+    /// previous page as cursor, until a page doesn't have a full set of
+    /// confirmed transactions. Fetching also stops if the cursor doesn't move
+    /// forward, i.e. when a page doesn't bring any new transaction.
+    pub async fn fetch_all_scripthash_transactions(&self, script_hash: String) -> Result<Vec<ApiTx>, Error> {
+        let count_confirmed = |page: &[ApiTx]| page.iter().filter(|tx| tx.TransactionStatus.IsConfirmed != 0).count();
+
+        let mut transactions = self.get_scripthash_transactions(script_hash.clone()).await?;
+        let mut seen_transaction_ids = transactions
+            .iter()
+            .map(|tx| tx.TransactionID.clone())
+            .collect::<HashSet<_>>();
+        // The first page also contains mempool transactions, on top of the
+        // confirmed ones
+        let mut last_page_confirmed = count_confirmed(&transactions);
+
+        while last_page_confirmed >= SCRIPTHASH_TRANSACTIONS_PER_PAGE {
+            let Some(cursor) = transactions.last().map(|tx| tx.TransactionID.clone()) else {
+                break;
+            };
+
+            let page = self
+                .get_scripthash_transactions_at_transaction_id(script_hash.clone(), cursor.clone())
+                .await?;
+
+            if page.last().map_or(true, |tx| tx.TransactionID == cursor) {
+                break;
+            }
+
+            last_page_confirmed = count_confirmed(&page);
+
+            let new_transactions = page
+                .into_iter()
+                .filter(|tx| seen_transaction_ids.insert(tx.TransactionID.clone()))
+                .collect::<Vec<_>>();
+            if new_transactions.is_empty() {
+                break;
+            }
+
+            transactions.extend(new_transactions);
+        }
+
+        Ok(transactions)
+    }
+
     /// Get transaction history for multiple scripthashes, sorted by newest
     /// first. Returns up to 50 mempool transactions plus the first 25
     /// confirmed transactions. Pass TxID in the structure to get more
@@ -182,20 +369,21 @@ impl AddressClient {
 
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr;
-
-    use bitcoin::{
-        hashes::{sha256, Hash},
-        Address,
-    };
+    use andromeda_common::Network;
     use wiremock::{
-        matchers::{body_json, method, path},
+        matchers::{body_json, method, path, path_regex},
         Mock, MockServer, ResponseTemplate,
     };
 
-    use super::{AddressClient, ScriptHashTransactionsPayload};
+    use bitcoin::Transaction;
+
+    use super::{
+        scripthash_for_address, AddressClient, AddressNetBalance, ApiTx, ScriptHashTransactionsPayload,
+        SCRIPTHASH_TRANSACTIONS_PER_PAGE,
+    };
     use crate::{
         core::ApiClient,
+        error::Error,
         read_mock_file,
         tests::utils::{common_api_client, setup_test_connection_arc},
         BASE_WALLET_API_V1,
@@ -220,13 +408,8 @@ mod tests {
         let api_client = common_api_client().await;
         let client = AddressClient::new(api_client);
 
-        let scripthash = sha256::Hash::hash(
-            Address::from_str("tb1q886jdswcmtn5u9memdlaz0lymua637a9aufqq6")
-                .unwrap()
-                .assume_checked()
-                .script_pubkey()
-                .as_bytes(),
-        );
+        let scripthash =
+            scripthash_for_address("tb1q886jdswcmtn5u9memdlaz0lymua637a9aufqq6", Network::Testnet).unwrap();
 
         let blocks = client.get_scripthash_transactions(scripthash.to_string()).await;
 
@@ -239,13 +422,8 @@ mod tests {
         let api_client = common_api_client().await;
         let client = AddressClient::new(api_client);
 
-        let scripthash = sha256::Hash::hash(
-            Address::from_str("tb1q886jdswcmtn5u9memdlaz0lymua637a9aufqq6")
-                .unwrap()
-                .assume_checked()
-                .script_pubkey()
-                .as_bytes(),
-        );
+        let scripthash =
+            scripthash_for_address("tb1q886jdswcmtn5u9memdlaz0lymua637a9aufqq6", Network::Testnet).unwrap();
 
         let blocks = client
             .get_scripthash_transactions_at_transaction_id(
@@ -261,21 +439,11 @@ mod tests {
     async fn test_get_scripthashes_transactions() {
         let mock_server = MockServer::start().await;
 
-        let script_hash_1 = sha256::Hash::hash(
-            Address::from_str("tb1q886jdswcmtn5u9memdlaz0lymua637a9aufqq6")
-                .unwrap()
-                .assume_checked()
-                .script_pubkey()
-                .as_bytes(),
-        );
+        let script_hash_1 =
+            scripthash_for_address("tb1q886jdswcmtn5u9memdlaz0lymua637a9aufqq6", Network::Testnet).unwrap();
 
-        let script_hash_2 = sha256::Hash::hash(
-            Address::from_str("tb1q3wp2q7xnsyxlcwtk9vnnwmgpuw0ftlmertppsu")
-                .unwrap()
-                .assume_checked()
-                .script_pubkey()
-                .as_bytes(),
-        );
+        let script_hash_2 =
+            scripthash_for_address("tb1q3wp2q7xnsyxlcwtk9vnnwmgpuw0ftlmertppsu", Network::Testnet).unwrap();
 
         let script_hashes = vec![
             ScriptHashTransactionsPayload {
@@ -543,16 +711,105 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_get_address_net_balance_success() {
+        let mock_server = MockServer::start().await;
+        let address = "tb1q886jdswcmtn5u9memdlaz0lymua637a9aufqq6";
+        let contents = read_mock_file!("get_address_balance_1000_body");
+        let response = ResponseTemplate::new(200).set_body_string(contents);
+        let req_path: String = format!("{}/addresses/{}/balance", BASE_WALLET_API_V1, address);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = AddressClient::new(api_client);
+
+        let result = client.get_address_net_balance(address.to_string()).await;
+
+        match result {
+            Ok(balance) => {
+                assert_eq!(
+                    balance,
+                    AddressNetBalance {
+                        confirmed: 122250,
+                        pending: -15400,
+                    }
+                );
+            }
+            Err(e) => panic!("Got Err. {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_address_balances_with_invalid_address() {
+        let mock_server = MockServer::start().await;
+        let addresses = [
+            "tb1q886jdswcmtn5u9memdlaz0lymua637a9aufqq6",
+            "not_an_address",
+            "tb1qsfhk3xzxkk7m63v9nhdn90vqn5vk54hff0lrq5",
+        ];
+
+        for address in [addresses[0], addresses[2]] {
+            let response_body = serde_json::json!({
+                "Code": 1000,
+                "Balance": {
+                  "Address": address,
+                  "ChainFundedBitcoin": 1000,
+                  "ChainSpentBitcoin": 0,
+                  "MempoolFundedBitcoin": 0,
+                  "MempoolSpentBitcoin": 0,
+                }
+            });
+            Mock::given(method("GET"))
+                .and(path(format!("{}/addresses/{}/balance", BASE_WALLET_API_V1, address)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(response_body))
+                .mount(&mock_server)
+                .await;
+        }
+
+        let error_body = serde_json::json!({
+            "Code": 2001,
+            "Details": {},
+            "Error": "Invalid address",
+        });
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "{}/addresses/{}/balance",
+                BASE_WALLET_API_V1, addresses[1]
+            )))
+            .respond_with(ResponseTemplate::new(422).set_body_json(error_body))
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = AddressClient::new(api_client);
+
+        let results = client
+            .get_address_balances(addresses.iter().map(|address| address.to_string()).collect())
+            .await;
+
+        assert_eq!(results.len(), 3);
+        for ((address, result), expected) in results.iter().zip(addresses) {
+            assert_eq!(address, expected);
+            match result {
+                Ok(balance) => assert_eq!(balance.Address, *address),
+                Err(Error::ErrorCode(_, error)) => {
+                    assert_eq!(*address, addresses[1]);
+                    assert_eq!(error.Code, 2001);
+                }
+                Err(e) => panic!("Got unexpected Err. {:?}", e),
+            }
+        }
+        assert_eq!(results.iter().filter(|(_, result)| result.is_ok()).count(), 2);
+    }
+
     #[tokio::test]
     async fn test_get_scripthash_transactions_success() {
         let mock_server = MockServer::start().await;
-        let script_hash_1 = sha256::Hash::hash(
-            Address::from_str("bc1qsv433rsr3e26wc3kxxapn36a3e7s6rkjrgeu0u")
-                .unwrap()
-                .assume_checked()
-                .script_pubkey()
-                .as_bytes(),
-        );
+        let script_hash_1 =
+            scripthash_for_address("bc1qsv433rsr3e26wc3kxxapn36a3e7s6rkjrgeu0u", Network::Bitcoin).unwrap();
         let contents = read_mock_file!("get_scripthash_transactions_1000_body");
         let response = ResponseTemplate::new(200).set_body_string(contents);
         let req_path: String = format!(
@@ -580,13 +837,8 @@ mod tests {
     #[tokio::test]
     async fn test_get_scripthash_transactions_at_transaction_id_success() {
         let mock_server = MockServer::start().await;
-        let script_hash_1 = sha256::Hash::hash(
-            Address::from_str("bc1qsv433rsr3e26wc3kxxapn36a3e7s6rkjrgeu0u")
-                .unwrap()
-                .assume_checked()
-                .script_pubkey()
-                .as_bytes(),
-        );
+        let script_hash_1 =
+            scripthash_for_address("bc1qsv433rsr3e26wc3kxxapn36a3e7s6rkjrgeu0u", Network::Bitcoin).unwrap();
         let transaction_id = "6bbfc06ef911e4b2fffe1150fa8f3729b3ee52c78ef21093b5ae45544ff690fa";
         let contents = read_mock_file!("get_scripthash_transactions_1000_body");
         let response = ResponseTemplate::new(200).set_body_string(contents);
@@ -613,4 +865,237 @@ mod tests {
             Err(e) => panic!("Got Err. {:?}", e),
         }
     }
+
+    #[test]
+    fn should_compute_scripthash_for_address() {
+        assert_eq!(
+            scripthash_for_address("tb1q886jdswcmtn5u9memdlaz0lymua637a9aufqq6", Network::Testnet).unwrap(),
+            "ae8939dfeae1f9a5cc0749c0576246e3df8d431eacc6f4f5fd42d2a8e9799a41"
+        );
+        assert_eq!(
+            scripthash_for_address("bc1qsv433rsr3e26wc3kxxapn36a3e7s6rkjrgeu0u", Network::Bitcoin).unwrap(),
+            "d9aaf7446fb2a7e07bcff9d3cc69c28eadd8fc067de8c1ad44654fc4729aea7c"
+        );
+    }
+
+    #[test]
+    fn should_not_compute_scripthash_for_address_on_wrong_network() {
+        assert!(scripthash_for_address("bc1qsv433rsr3e26wc3kxxapn36a3e7s6rkjrgeu0u", Network::Testnet).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_transactions_for_address_success() {
+        let mock_server = MockServer::start().await;
+        let address = "bc1qsv433rsr3e26wc3kxxapn36a3e7s6rkjrgeu0u";
+        let script_hash = scripthash_for_address(address, Network::Bitcoin).unwrap();
+        let contents = read_mock_file!("get_scripthash_transactions_1000_body");
+        let response = ResponseTemplate::new(200).set_body_string(contents);
+        let req_path: String = format!(
+            "{}/addresses/scripthash/{}/transactions",
+            BASE_WALLET_API_V1, script_hash
+        );
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = AddressClient::new(api_client);
+
+        let result = client
+            .get_transactions_for_address(address.to_string(), Network::Bitcoin)
+            .await;
+
+        match result {
+            Ok(transactions) => {
+                assert_eq!(transactions.len(), 2);
+            }
+            Err(e) => panic!("Got Err. {:?}", e),
+        }
+    }
+
+    fn get_mock_scripthash_transactions_body(first_index: usize, count: usize) -> serde_json::Value {
+        let transactions = (first_index..first_index + count)
+            .map(|index| {
+                serde_json::json!({
+                    "TransactionID": format!("{:064x}", index),
+                    "Version": 2,
+                    "Locktime": 0,
+                    "Vin": null,
+                    "Vout": null,
+                    "Size": 222,
+                    "Weight": 561,
+                    "Fee": 141,
+                    "TransactionStatus": {
+                        "IsConfirmed": 1,
+                        "BlockHeight": 2570576,
+                        "BlockHash": "00000000000000100898f2f1121229ae689e27b2d1f0970f015ee27ae81c9aff",
+                        "BlockTime": 1704358819
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::json!({
+            "Code": 1000,
+            "Transactions": transactions,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_scripthash_transactions_success() {
+        let mock_server = MockServer::start().await;
+        let script_hash =
+            scripthash_for_address("bc1qsv433rsr3e26wc3kxxapn36a3e7s6rkjrgeu0u", Network::Bitcoin).unwrap();
+
+        let first_page = get_mock_scripthash_transactions_body(0, SCRIPTHASH_TRANSACTIONS_PER_PAGE);
+        let req_path: String = format!(
+            "{}/addresses/scripthash/{}/transactions",
+            BASE_WALLET_API_V1, script_hash
+        );
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(first_page))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let second_page = get_mock_scripthash_transactions_body(SCRIPTHASH_TRANSACTIONS_PER_PAGE, 2);
+        let req_path: String = format!(
+            "{}/addresses/scripthash/{}/transactions/{:064x}",
+            BASE_WALLET_API_V1,
+            script_hash,
+            SCRIPTHASH_TRANSACTIONS_PER_PAGE - 1
+        );
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(second_page))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let client = AddressClient::new(api_client);
+
+        let result = client.fetch_all_scripthash_transactions(script_hash).await;
+
+        match result {
+            Ok(transactions) => {
+                assert_eq!(transactions.len(), SCRIPTHASH_TRANSACTIONS_PER_PAGE + 2);
+                assert_eq!(
+                    transactions.last().unwrap().TransactionID,
+                    format!("{:064x}", SCRIPTHASH_TRANSACTIONS_PER_PAGE + 1)
+                );
+            }
+            Err(e) => panic!("Got Err. {:?}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_scripthash_transactions_ignores_mempool_in_page_size() {
+        let mock_server = MockServer::start().await;
+        let script_hash =
+            scripthash_for_address("bc1qsv433rsr3e26wc3kxxapn36a3e7s6rkjrgeu0u", Network::Bitcoin).unwrap();
+
+        // 2 mempool transactions on top of a page that isn't full
+        let mut first_page = get_mock_scripthash_transactions_body(0, SCRIPTHASH_TRANSACTIONS_PER_PAGE + 1);
+        for index in 0..2 {
+            first_page["Transactions"][index]["TransactionStatus"]["IsConfirmed"] = 0.into();
+        }
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "{}/addresses/scripthash/{}/transactions",
+                BASE_WALLET_API_V1, script_hash
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(first_page))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path_regex(".*/transactions/.+"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_mock_scripthash_transactions_body(100, 1)))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let client = AddressClient::new(setup_test_connection_arc(mock_server.uri()));
+        let transactions = client.fetch_all_scripthash_transactions(script_hash).await.unwrap();
+
+        assert_eq!(transactions.len(), SCRIPTHASH_TRANSACTIONS_PER_PAGE + 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_scripthash_transactions_stops_when_cursor_does_not_move() {
+        let mock_server = MockServer::start().await;
+        let script_hash =
+            scripthash_for_address("bc1qsv433rsr3e26wc3kxxapn36a3e7s6rkjrgeu0u", Network::Bitcoin).unwrap();
+
+        let first_page = get_mock_scripthash_transactions_body(0, SCRIPTHASH_TRANSACTIONS_PER_PAGE);
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "{}/addresses/scripthash/{}/transactions",
+                BASE_WALLET_API_V1, script_hash
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(first_page.clone()))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        // Server ignoring the cursor and returning the same page again
+        Mock::given(method("GET"))
+            .and(path_regex(".*/transactions/.+"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(first_page))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let client = AddressClient::new(setup_test_connection_arc(mock_server.uri()));
+        let transactions = client.fetch_all_scripthash_transactions(script_hash).await.unwrap();
+
+        assert_eq!(transactions.len(), SCRIPTHASH_TRANSACTIONS_PER_PAGE);
+    }
+
+    #[test]
+    fn should_convert_api_tx_to_transaction() {
+        let contents = read_mock_file!("api_tx_1000_body");
+        let api_tx: ApiTx = serde_json::from_str(&contents).unwrap();
+
+        let transaction = Transaction::try_from(api_tx).unwrap();
+
+        assert_eq!(
+            transaction.compute_txid().to_string(),
+            "4864cd31446b99ddf378d5166fc66ab1672698f028e68968d004db0b13839ad7"
+        );
+        assert_eq!(transaction.input.len(), 1);
+        assert_eq!(transaction.input[0].witness.len(), 2);
+        assert_eq!(transaction.output.len(), 2);
+        assert_eq!(transaction.output[1].value.to_sat(), 7400);
+    }
+
+    #[test]
+    fn should_convert_api_tx_without_vin_nor_vout() {
+        let api_tx: ApiTx = serde_json::from_value(serde_json::json!({
+            "TransactionID": "6bbfc06ef911e4b2fffe1150fa8f3729b3ee52c78ef21093b5ae45544ff690fa",
+            "Version": 2,
+            "Locktime": 0,
+            "Vin": null,
+            "Vout": null,
+            "Size": 10,
+            "Weight": 40,
+            "Fee": 0,
+            "TransactionStatus": {
+                "IsConfirmed": 0,
+                "BlockHeight": null,
+                "BlockHash": null,
+                "BlockTime": null
+            }
+        }))
+        .unwrap();
+
+        let transaction = Transaction::try_from(api_tx).unwrap();
+
+        assert!(transaction.input.is_empty());
+        assert!(transaction.output.is_empty());
+    }
 }
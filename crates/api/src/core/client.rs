@@ -3,7 +3,7 @@ use std::sync::Arc;
 use muon::ProtonRequest;
 
 use super::ToProtonRequest;
-use crate::{get_default_time_constraint, ProtonWalletApiClient, DEFAULT_INTERACTIVITY, DEFAULT_SERVICE_TYPE};
+use crate::{ProtonWalletApiClient, DEFAULT_INTERACTIVITY, DEFAULT_SERVICE_TYPE};
 
 pub trait ApiClient {
     fn new(api_client: Arc<ProtonWalletApiClient>) -> Self;
@@ -15,28 +15,28 @@ pub trait ApiClient {
         return self
             .build_request(self.base_url(), endpoint)
             .to_get_request()
-            .allowed_time(get_default_time_constraint())
+            .allowed_time(self.api_client().request_timeout())
             .service_type(DEFAULT_SERVICE_TYPE, true);
     }
     fn post(&self, endpoint: impl ToString) -> ProtonRequest {
         return self
             .build_request(self.base_url(), endpoint)
             .to_post_request()
-            .allowed_time(get_default_time_constraint())
+            .allowed_time(self.api_client().request_timeout())
             .service_type(DEFAULT_INTERACTIVITY, true);
     }
     fn put(&self, endpoint: impl ToString) -> ProtonRequest {
         return self
             .build_request(self.base_url(), endpoint)
             .to_put_request()
-            .allowed_time(get_default_time_constraint())
+            .allowed_time(self.api_client().request_timeout())
             .service_type(DEFAULT_INTERACTIVITY, true);
     }
     fn delete(&self, endpoint: impl ToString) -> ProtonRequest {
         return self
             .build_request(self.base_url(), endpoint)
             .to_delete_request()
-            .allowed_time(get_default_time_constraint())
+            .allowed_time(self.api_client().request_timeout())
             .service_type(DEFAULT_INTERACTIVITY, true);
     }
     fn build_request(&self, version: &str, endpoint: impl ToString) -> String {
@@ -1,6 +1,7 @@
 use std::str::Utf8Error;
 
 use bitcoin::{
+    address::ParseError as BitcoinAddressParseError,
     consensus::encode::Error as BitcoinEncodingError,
     hashes::hex::{HexToArrayError, HexToBytesError},
 };
@@ -39,8 +40,12 @@ pub enum Error {
     HexToArrayDecoding(#[from] HexToArrayError),
     #[error("An error occurred when decoding hex to bytes: \n\t{0}")]
     HexToBytesErrorDecoding(#[from] HexToBytesError),
+    #[error("An error occurred when parsing bitcoin address: \n\t{0}")]
+    BitcoinAddressParse(#[from] BitcoinAddressParseError),
     #[error("HTTP error")]
     Http,
+    #[error("Request timed out")]
+    RequestTimeout,
     #[error("HTTP Response error")]
     ErrorCode(Status, ResponseError),
     #[error("Response parser error")]
@@ -49,6 +54,27 @@ pub enum Error {
     Utf8Error(#[from] Utf8Error),
 }
 
+impl Error {
+    /// Returns whether the failed request is worth retrying: rate limiting,
+    /// server and network errors are transient, while client errors (auth,
+    /// validation...) would fail the same way again. Nothing is retried
+    /// automatically, it is up to the caller to send the request again.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::ErrorCode(status, _) => status.as_u16() == 429 || status.is_server_error(),
+            Error::MuonError(error) => is_retryable_muon_error(error),
+            Error::RequestTimeout | Error::Http => true,
+            _ => false,
+        }
+    }
+}
+
+/// Returns whether a muon error is transient, i.e. the request couldn't be
+/// sent and sending it again may succeed
+pub fn is_retryable_muon_error(error: &MuonError) -> bool {
+    matches!(error.kind(), MuonErrorKind::Send)
+}
+
 impl From<MuonError> for Error {
     fn from(err: MuonError) -> Self {
         use std::error::Error as _;
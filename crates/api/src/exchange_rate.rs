@@ -29,6 +29,15 @@ pub struct ApiExchangeRate {
     pub Cents: u64,
 }
 
+/// Exchange rate resolved for a point in the past
+#[derive(Debug)]
+pub struct HistoricalExchangeRate {
+    pub exchange_rate: ApiExchangeRate,
+    /// Unix timestamp of the rate the API resolved to, which can differ from
+    /// the requested one
+    pub effective_timestamp: u64,
+}
+
 #[derive(Debug, Deserialize)]
 #[allow(non_snake_case)]
 struct GetExchangeRateResponseBody {
@@ -93,6 +102,28 @@ impl ExchangeRateClient {
         Ok(parsed.ExchangeRate)
     }
 
+    /// Returns the exchange rate for a given fiat currency at a given unix
+    /// timestamp, along with the timestamp of the rate it resolved to.
+    pub async fn get_exchange_rate_at(
+        &self,
+        fiat_currency: FiatCurrencySymbol,
+        timestamp: u64,
+    ) -> Result<HistoricalExchangeRate, Error> {
+        let exchange_rate = self.get_exchange_rate(fiat_currency, Some(timestamp)).await?;
+
+        let effective_timestamp = exchange_rate.ExchangeRateTime.parse::<u64>().map_err(|_| {
+            Error::Deserialize(format!(
+                "Invalid exchange rate time: {}",
+                exchange_rate.ExchangeRateTime
+            ))
+        })?;
+
+        Ok(HistoricalExchangeRate {
+            exchange_rate,
+            effective_timestamp,
+        })
+    }
+
     pub async fn get_all_fiat_currencies(&self) -> Result<Vec<ApiFiatCurrency>, Error> {
         let request = self.get("fiat-currencies");
 
@@ -190,6 +221,42 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_get_exchange_rate_at_success() {
+        let mock_server = MockServer::start().await;
+        let response_body = serde_json::json!(
+            {
+                "Code": 1000,
+                "ExchangeRate": {
+                    "ID": "BG2rHbE0giOBTvPWDVHdS_MMyxemjRxSzrKOTbxaINTH0zYnS5hD5zEqV9TURB-mzMy2LPC3qg4XnPq_kHmf9g==",
+                    "BitcoinUnit": "BTC",
+                    "FiatCurrency": "USD",
+                    "Sign": "$",
+                    "ExchangeRateTime": "1707287700",
+                    "ExchangeRate": 4331200,
+                    "Cents": 100
+                }
+            }
+        );
+        let fiat_currency = FiatCurrencySymbol::USD;
+        let req_path: String = format!("{}/rates", BASE_WALLET_API_V1);
+        let response = ResponseTemplate::new(200).set_body_json(response_body);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .and(query_param("FiatCurrency", fiat_currency.to_string()))
+            .and(query_param("Time", "1707287982"))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = ExchangeRateClient::new(Arc::new(api_client));
+
+        let historical_rate = client.get_exchange_rate_at(fiat_currency, 1707287982).await.unwrap();
+        assert_eq!(historical_rate.effective_timestamp, 1707287700);
+        assert_eq!(historical_rate.exchange_rate.ExchangeRate, 4331200);
+        assert_eq!(historical_rate.exchange_rate.FiatCurrency, FiatCurrencySymbol::USD);
+    }
+
     #[tokio::test]
     async fn test_get_all_fiat_currencies_success() {
         let mock_server = MockServer::start().await;
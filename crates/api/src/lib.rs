@@ -1,6 +1,7 @@
 use core::ApiClient;
 use std::{
-    env,
+    env, fmt,
+    future::Future,
     sync::{Arc, Mutex},
     time::Duration,
 };
@@ -14,6 +15,8 @@ use email_integration::EmailIntegrationClient;
 use error::Error;
 use event::EventClient;
 use exchange_rate::ExchangeRateClient;
+use futures::future::BoxFuture;
+use instant::Instant;
 use invite::InviteClient;
 use log::info;
 pub use muon::{
@@ -121,6 +124,11 @@ pub struct ProtonWalletApiClient {
     url_prefix: Option<String>,
     // cache the env, when doing the fork, we need to target same env
     env: Option<String>,
+    /// Maximum duration of a request
+    request_timeout: Duration,
+    /// Auth of the session, when the client owns its store
+    auth: Option<Arc<Mutex<Auth>>>,
+    on_unauthorized: Option<UnauthorizedHook>,
 }
 
 #[derive(Debug)]
@@ -138,6 +146,13 @@ pub struct ApiConfig {
     pub store: Option<DynStore>,
     /// The proxy address. Enable `allow-dangerous-env`` feature to use this
     pub proxy: Option<ProxyConfig>,
+    /// Maximum duration of a request, after which it fails with
+    /// [`Error::RequestTimeout`]. Defaults to 30s
+    pub request_timeout: Option<Duration>,
+    /// Called when the API rejects the session's auth, the request being
+    /// retried once with the returned one. Ignored when a custom `store` is
+    /// provided
+    pub on_unauthorized: Option<UnauthorizedHook>,
 }
 
 #[derive(Debug)]
@@ -146,6 +161,30 @@ pub struct ProxyConfig {
     pub port: u16,
 }
 
+/// Callback providing a fresh auth once the current one got rejected
+#[derive(Clone)]
+pub struct UnauthorizedHook(Arc<dyn Fn() -> BoxFuture<'static, Auth> + Send + Sync>);
+
+impl UnauthorizedHook {
+    pub fn new<F, Fut>(hook: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Auth> + Send + 'static,
+    {
+        Self(Arc::new(move || Box::pin(hook())))
+    }
+
+    async fn refresh(&self) -> Auth {
+        (self.0)().await
+    }
+}
+
+impl fmt::Debug for UnauthorizedHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("UnauthorizedHook")
+    }
+}
+
 pub struct Clients {
     pub block: BlockClient,
     pub network: NetworkClient,
@@ -182,6 +221,8 @@ impl ProtonWalletApiClient {
     ///     url_prefix: None,
     ///     store: None,
     ///     proxy: None,
+    ///     request_timeout: None,
+    ///     on_unauthorized: None,
     /// };
     /// let api_client = ProtonWalletApiClient::from_config(config);
     /// ```
@@ -191,12 +232,15 @@ impl ProtonWalletApiClient {
         let (app_version, user_agent) = config.spec;
         let app = App::new(app_version)?.with_user_agent(user_agent);
 
+        let mut auth = None;
         let store = config.store.unwrap_or_else(|| {
-            let auth = config.auth.unwrap_or(Auth::None);
+            let store_auth = Arc::new(Mutex::new(config.auth.unwrap_or(Auth::None)));
+            auth = Some(store_auth.clone());
+
             if config.proxy.is_none() {
-                Box::new(WalletAuthStore::from_env_str(env, Arc::new(Mutex::new(auth))))
+                Box::new(WalletAuthStore::from_env_str(env, store_auth))
             } else {
-                Box::new(WalletAuthStore::from_custom_env_str(env, Arc::new(Mutex::new(auth))))
+                Box::new(WalletAuthStore::from_custom_env_str(env, store_auth))
             }
         });
 
@@ -214,9 +258,17 @@ impl ProtonWalletApiClient {
             session,
             url_prefix: config.url_prefix,
             env: config.env,
+            request_timeout: config.request_timeout.unwrap_or_else(get_default_time_constraint),
+            auth,
+            on_unauthorized: config.on_unauthorized,
         })
     }
 
+    /// Returns the maximum duration of a request
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout
+    }
+
     pub fn clients(&self) -> Clients {
         let api_client = Arc::new(self.clone());
 
@@ -330,8 +382,43 @@ impl ProtonWalletApiClient {
         }
     }
 
-    async fn send(&self, request: ProtonRequest) -> Result<ProtonResponse, MuonError> {
-        self.session.clone().send(request).await
+    /// Sends the request, refreshing the session's auth and retrying once if
+    /// it got rejected and an [`UnauthorizedHook`] is set
+    async fn send(&self, request: ProtonRequest) -> Result<ProtonResponse, Error> {
+        let (Some(on_unauthorized), Some(auth)) = (&self.on_unauthorized, &self.auth) else {
+            return self.send_once(request).await;
+        };
+
+        let response = self.send_once(request.clone()).await;
+        let is_unauthorized = match &response {
+            Ok(response) => response.status().as_u16() == 401,
+            Err(error) => matches!(error, Error::AuthSession(_) | Error::AuthRefresh(_)),
+        };
+
+        if !is_unauthorized {
+            return response;
+        }
+
+        info!("session auth was rejected, refreshing it");
+        let refreshed_auth = on_unauthorized.refresh().await;
+        *auth.lock().unwrap() = refreshed_auth;
+
+        self.send_once(request).await
+    }
+
+    async fn send_once(&self, request: ProtonRequest) -> Result<ProtonResponse, Error> {
+        let started_at = Instant::now();
+
+        self.session
+            .clone()
+            .send(request)
+            .await
+            .map_err(|error| match Error::from(error) {
+                // Muon gives up on requests exceeding their allowed time with a
+                // transport error, auth failures are kept as is
+                Error::MuonError(_) if started_at.elapsed() >= self.request_timeout => Error::RequestTimeout,
+                error => error,
+            })
     }
 }
 
@@ -349,6 +436,8 @@ impl Default for ProtonWalletApiClient {
             store: None,
             auth: None,
             proxy: None,
+            request_timeout: None,
+            on_unauthorized: None,
         };
         Self::from_config(config).unwrap()
     }
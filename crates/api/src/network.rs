@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
 use andromeda_common::Network;
+use bitcoin::BlockHash;
 use serde::Deserialize;
 
 use crate::{
+    block::BlockClient,
     core::{ApiClient, ProtonResponseExt},
     error::Error,
     ProtonWalletApiClient, BASE_WALLET_API_V1,
@@ -52,11 +54,34 @@ impl NetworkClient {
 
         Ok(network)
     }
+
+    /// Returns the height of the current best block
+    pub async fn get_tip_height(&self) -> Result<u32, Error> {
+        BlockClient::new(self.api_client.clone()).get_tip_height().await
+    }
+
+    /// Returns the hash of the current best block
+    pub async fn get_tip_hash(&self) -> Result<BlockHash, Error> {
+        BlockClient::new(self.api_client.clone()).get_tip_hash().await
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use muon::env::EnvId;
+    use std::{
+        net::TcpListener,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use andromeda_common::Network;
+    use muon::{
+        client::{Auth, Tokens},
+        env::EnvId,
+    };
     use wiremock::{
         matchers::{method, path},
         Mock, MockServer, ResponseTemplate,
@@ -65,8 +90,10 @@ mod tests {
     use super::NetworkClient;
     use crate::{
         core::ApiClient,
-        tests::utils::{common_api_client, setup_test_connection_arc},
-        BASE_WALLET_API_V1,
+        error::Error,
+        read_mock_file,
+        tests::utils::{common_api_client, setup_test_connection_arc, setup_test_connection_with_timeout, test_spec},
+        ApiConfig, ChildSession, ProtonWalletApiClient, UnauthorizedHook, BASE_WALLET_API_V1,
     };
 
     #[tokio::test]
@@ -121,6 +148,30 @@ mod tests {
         assert_eq!(unmatched_requests.len(), 1, "There should be no unmatched requests");
     }
 
+    #[tokio::test]
+    async fn test_get_tip_1000() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/blocks/tip/height", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_string(read_mock_file!("get_tip_height_1000_body")))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/blocks/tip/hash", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_string(read_mock_file!("get_block_hash_1000_body")))
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection_arc(mock_server.uri());
+        let network_client = NetworkClient::new(api_client);
+
+        assert_eq!(network_client.get_tip_height().await.unwrap(), 871886);
+        assert_eq!(
+            network_client.get_tip_hash().await.unwrap().to_string(),
+            "00000000000000000000b3f5fed64a5eefd02589a036ce9f9bd40b627b4cb9a3"
+        );
+    }
+
     #[tokio::test]
     async fn test_get_network_timeout() {
         let mock_server = MockServer::start().await;
@@ -142,4 +193,159 @@ mod tests {
         let unmatched_requests = mock_server.received_requests().await.unwrap();
         assert_eq!(unmatched_requests.len(), 1, "There should be no unmatched requests");
     }
+
+    #[tokio::test]
+    async fn test_get_network_custom_timeout() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/network", BASE_WALLET_API_V1);
+        let response = ResponseTemplate::new(200).set_delay(Duration::from_secs(3));
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection_with_timeout(mock_server.uri(), Duration::from_secs(1));
+        assert_eq!(api_client.request_timeout(), Duration::from_secs(1));
+
+        let network_client = NetworkClient::new(Arc::new(api_client));
+        let res = network_client.get_network().await;
+        assert!(matches!(res, Err(Error::RequestTimeout)));
+    }
+
+    #[tokio::test]
+    async fn test_get_network_error_is_retryable() {
+        for (status, retryable) in [
+            (429, true),
+            (500, true),
+            (503, true),
+            (400, false),
+            (403, false),
+            (422, false),
+        ] {
+            let mock_server = MockServer::start().await;
+            let req_path: String = format!("{}/network", BASE_WALLET_API_V1);
+            let response = ResponseTemplate::new(status).set_body_json(serde_json::json!({
+                "Code": 2001,
+                "Error": "Error",
+                "Details": {}
+            }));
+            Mock::given(method("GET"))
+                .and(path(req_path))
+                .respond_with(response)
+                .mount(&mock_server)
+                .await;
+
+            let api_client = setup_test_connection_with_timeout(mock_server.uri(), Duration::from_secs(5));
+            let network_client = NetworkClient::new(Arc::new(api_client));
+            let error = network_client.get_network().await.unwrap_err();
+            assert_eq!(error.is_retryable(), retryable, "status {status}: {error:?}");
+        }
+
+        assert!(Error::RequestTimeout.is_retryable());
+        assert!(!Error::Deserialize("unexpected body".to_string()).is_retryable());
+
+        // Requests that couldn't be sent at all are worth retrying
+        let closed_port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+
+        let api_client =
+            setup_test_connection_with_timeout(format!("http://127.0.0.1:{closed_port}"), Duration::from_secs(5));
+        let network_client = NetworkClient::new(Arc::new(api_client));
+        let error = network_client.get_network().await.unwrap_err();
+        assert!(matches!(error, Error::MuonError(_)), "{error:?}");
+        assert!(error.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_get_network_refreshes_auth_on_unauthorized() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/network", BASE_WALLET_API_V1);
+        Mock::given(method("GET"))
+            .and(path(req_path.clone()))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "Code": 401,
+                "Error": "Invalid access token",
+                "Details": {}
+            })))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Code": 1000,
+                "Network": 1
+            })))
+            .with_priority(2)
+            .mount(&mock_server)
+            .await;
+
+        let refresh_count = Arc::new(AtomicUsize::new(0));
+        let on_unauthorized = {
+            let refresh_count = refresh_count.clone();
+            UnauthorizedHook::new(move || {
+                refresh_count.fetch_add(1, Ordering::SeqCst);
+                async { Auth::internal("uid", Tokens::access("acc_tok", "ref_tok", ["scopes"])) }
+            })
+        };
+
+        let config = ApiConfig {
+            spec: test_spec(),
+            url_prefix: None,
+            env: Some(mock_server.uri()),
+            store: None,
+            auth: None,
+            proxy: None,
+            request_timeout: None,
+            on_unauthorized: Some(on_unauthorized),
+        };
+        let api_client = ProtonWalletApiClient::from_config(config).unwrap();
+
+        let network_client = NetworkClient::new(Arc::new(api_client));
+        let network = network_client.get_network().await.unwrap();
+
+        assert_eq!(network, Network::Testnet);
+        assert_eq!(refresh_count.load(Ordering::SeqCst), 1);
+        assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_network_with_restored_session() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/network", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Code": 1000,
+                "Network": 1
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let session = ChildSession {
+            session_id: "session_uid".to_string(),
+            access_token: "access_token".to_string(),
+            refresh_token: "refresh_token".to_string(),
+            scopes: vec!["wallet".to_string()],
+        };
+        let config = ApiConfig {
+            spec: test_spec(),
+            url_prefix: None,
+            env: Some(mock_server.uri()),
+            store: None,
+            auth: Some(session.into()),
+            proxy: None,
+            request_timeout: None,
+            on_unauthorized: None,
+        };
+        let api_client = ProtonWalletApiClient::from_config(config).unwrap();
+
+        let network_client = NetworkClient::new(Arc::new(api_client));
+        network_client.get_network().await.unwrap();
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let authorization = requests[0].headers.get("authorization").unwrap().to_str().unwrap();
+        assert!(authorization.contains("access_token"));
+        assert_eq!(requests[0].headers.get("x-pm-uid").unwrap(), "session_uid");
+    }
 }
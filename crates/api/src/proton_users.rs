@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use muon::rest::core::v4::{keys::salts::KeySalt, users::User};
+use muon::{
+    client::{Auth, Tokens},
+    rest::core::v4::{keys::salts::KeySalt, users::User},
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone)]
@@ -10,6 +13,17 @@ pub struct ChildSession {
     pub refresh_token: String,
     pub scopes: Vec<String>,
 }
+
+impl From<ChildSession> for Auth {
+    /// Rebuilds the auth of an authenticated session, e.g. to resume it
+    fn from(session: ChildSession) -> Self {
+        Auth::internal(
+            session.session_id,
+            Tokens::access(session.access_token, session.refresh_token, session.scopes),
+        )
+    }
+}
+
 pub struct UserData {
     pub user: User,
     pub key_salts: Vec<KeySalt>,
@@ -302,6 +302,28 @@ impl SettingsClient {
         Ok(parsed.WalletUserSettings)
     }
 
+    /// Returns the unit the user wants amounts to be displayed in
+    pub async fn get_preferred_unit(&self) -> Result<BitcoinUnit, Error> {
+        Ok(self.get_user_settings().await?.BitcoinUnit)
+    }
+
+    /// Returns the symbol of the fiat currency the user wants amounts to be
+    /// converted to
+    pub async fn get_preferred_fiat(&self) -> Result<String, Error> {
+        Ok(self.get_user_settings().await?.FiatCurrency.to_string())
+    }
+
+    /// Persists the user's preferred unit and returns the saved one
+    pub async fn set_preferred_unit(&self, unit: BitcoinUnit) -> Result<BitcoinUnit, Error> {
+        Ok(self.update_bitcoin_unit(unit).await?.BitcoinUnit)
+    }
+
+    /// Persists the user's preferred fiat currency and returns the symbol of
+    /// the saved one
+    pub async fn set_preferred_fiat(&self, fiat: FiatCurrencySymbol) -> Result<String, Error> {
+        Ok(self.update_fiat_currency(fiat).await?.FiatCurrency.to_string())
+    }
+
     pub async fn get_user_wallet_eligibility(&self) -> Result<u8, Error> {
         let request = self.get("settings/eligible");
 
@@ -468,6 +490,32 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_get_preferred_unit_and_fiat() {
+        let mock_server = MockServer::start().await;
+        let response_body = serde_json::json!(
+            {
+                "Code": 1000,
+                "WalletUserSettings": {
+                    "BitcoinUnit": "SAT",
+                    "FiatCurrency": "EUR",
+                    "HideEmptyUsedAddresses": 0,
+                }
+            }
+        );
+        let req_path: String = format!("{}/settings", BASE_WALLET_API_V1);
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(response_body))
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = SettingsClient::new(Arc::new(api_client));
+
+        assert_eq!(client.get_preferred_unit().await.unwrap(), BitcoinUnit::SATS);
+        assert_eq!(client.get_preferred_fiat().await.unwrap(), "EUR");
+    }
+
     #[tokio::test]
     async fn test_update_bitcoin_unit_success() {
         let mock_server = MockServer::start().await;
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use crate::{ApiConfig, ProtonWalletApiClient};
 
@@ -14,6 +14,23 @@ pub fn setup_test_connection(url: String) -> ProtonWalletApiClient {
         store: None,
         auth: None,
         proxy: None,
+        request_timeout: None,
+        on_unauthorized: None,
+    };
+
+    ProtonWalletApiClient::from_config(config).unwrap()
+}
+
+pub fn setup_test_connection_with_timeout(url: String, request_timeout: Duration) -> ProtonWalletApiClient {
+    let config = ApiConfig {
+        spec: test_spec(),
+        url_prefix: None,
+        env: Some(url),
+        store: None,
+        auth: None,
+        proxy: None,
+        request_timeout: Some(request_timeout),
+        on_unauthorized: None,
     };
 
     ProtonWalletApiClient::from_config(config).unwrap()
@@ -31,6 +48,8 @@ pub async fn common_api_client() -> Arc<ProtonWalletApiClient> {
         store: None,
         auth: None,
         proxy: None,
+        request_timeout: None,
+        on_unauthorized: None,
     };
     let api = ProtonWalletApiClient::from_config(config).unwrap();
     api.login("bart", "bart").await.unwrap();
@@ -159,6 +159,29 @@ pub struct MempoolInfo {
     pub FullRbf: u8,
 }
 
+#[derive(Debug, Serialize)]
+#[allow(non_snake_case)]
+struct TestMempoolAcceptRequestBody {
+    SignedTransactionHex: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(non_snake_case)]
+pub struct MempoolAcceptResult {
+    /// Whether the transaction would be accepted in the mempool
+    pub Allowed: u8,
+    /// Reason of the rejection given by the node, e.g. `min relay fee not met`
+    pub RejectReason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(non_snake_case)]
+struct TestMempoolAcceptResponseBody {
+    #[allow(dead_code)]
+    pub Code: u16,
+    pub MempoolAccept: MempoolAcceptResult,
+}
+
 #[derive(Debug, Deserialize)]
 #[allow(non_snake_case)]
 struct GetMempoolInfoResponseBody {
@@ -313,6 +336,21 @@ impl TransactionClient {
         Ok(parsed.MempoolInfo)
     }
 
+    /// Checks whether a signed transaction would be accepted in the mempool,
+    /// without broadcasting it
+    pub async fn test_mempool_accept(&self, signed_transaction_hex: String) -> Result<MempoolAcceptResult, Error> {
+        let body = TestMempoolAcceptRequestBody {
+            SignedTransactionHex: signed_transaction_hex,
+        };
+
+        let request = self.post("transactions/test-mempool-accept").body_json(body)?;
+
+        let response = self.api_client.send(request).await?;
+        let parsed = response.parse_response::<TestMempoolAcceptResponseBody>()?;
+
+        Ok(parsed.MempoolAccept)
+    }
+
     pub async fn get_recommended_fees(&self) -> Result<RecommendedFees, Error> {
         let request = self.get("fees/recommended");
 
@@ -712,6 +750,38 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_test_mempool_accept_rejected() {
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!("{}/transactions/test-mempool-accept", BASE_WALLET_API_V1);
+        let response_body = serde_json::json!(
+            {
+                "Code": 1000,
+                "MempoolAccept": {
+                    "Allowed": 0,
+                    "RejectReason": "min relay fee not met"
+                }
+            }
+        );
+        let response = ResponseTemplate::new(200).set_body_json(response_body);
+        Mock::given(method("POST"))
+            .and(path(req_path))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = TransactionClient::new(Arc::new(api_client));
+        let result = client.test_mempool_accept("signed_transaction_hex".to_string()).await;
+        match result {
+            Ok(accept) => {
+                assert_eq!(accept.Allowed, 0);
+                assert_eq!(accept.RejectReason.unwrap(), "min relay fee not met");
+                return;
+            }
+            Err(e) => panic!("Got Err. {:?}", e),
+        }
+    }
+
     #[tokio::test]
     async fn test_broadcast_transaction_success() {
         let mock_server = MockServer::start().await;
@@ -1,35 +1,77 @@
-use std::{collections::BTreeMap, fmt::Debug, str::FromStr, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Debug,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
-use andromeda_common::{utils::now, Network, ScriptType};
+use andromeda_api::bitcoin_address::{ApiBitcoinAddressCreationPayload, ApiWalletBitcoinAddress};
+use andromeda_common::{utils::now, FromParts, Network, ScriptType};
 use async_std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use bdk_wallet::{
     bitcoin::{
-        bip32::{ChildNumber, DerivationPath, Xpriv},
+        bip32::{ChildNumber, DerivationPath, Fingerprint, Xpriv, Xpub},
         constants::genesis_block,
         psbt::Psbt as BdkPsbt,
         secp256k1::Secp256k1,
-        Address, Network as BdkNetwork, Txid,
+        Address, BlockHash, Network as BdkNetwork, NetworkKind, OutPoint, Script, ScriptBuf, Transaction, Txid,
     },
-    descriptor, AddressInfo, Balance as BdkBalance, ChangeSet, KeychainKind, LocalOutput as LocalUtxo, PersistedWallet,
+    chain::{BlockId, ChainPosition, CheckPoint, DescriptorExt, Indexer, TxUpdate},
+    descriptor, serde_json,
+    signer::{SignerCommon, TapLeavesOptions},
+    tx_builder::{ChangeSpendPolicy, TxOrdering},
+    AddressInfo, Balance as BdkBalance, ChangeSet, KeychainKind, LocalOutput as LocalUtxo, PersistedWallet,
     SignOptions, Update, Wallet as BdkWallet, WalletPersister,
 };
-use bitcoin::{params::Params, Amount};
-use miniscript::{descriptor::DescriptorSecretKey, DescriptorPublicKey};
+use bitcoin::{
+    base64::{engine::general_purpose::STANDARD, Engine},
+    constants::COINBASE_MATURITY,
+    hashes::Hash,
+    params::Params,
+    secp256k1::{Message, SecretKey},
+    sign_message::signed_msg_hash,
+    taproot::TapNodeHash,
+    AddressType, Amount, FeeRate, Weight,
+};
+use miniscript::{
+    descriptor::{DerivPaths, DescriptorMultiXKey, DescriptorSecretKey, DescriptorType, DescriptorXKey, Wildcard},
+    translate_hash_clone, DescriptorPublicKey, ForEachKey, TranslateErr, Translator,
+};
+use serde::{Deserialize, Serialize};
 
-use super::{payment_link::PaymentLink, transactions::Pagination, utils::sort_and_paginate_txs};
+use super::{
+    payment_link::PaymentLink,
+    transactions::Pagination,
+    utils::{sort_and_paginate_txs, sort_and_paginate_utxos},
+};
 use crate::{
-    address::AddressDetails,
+    address::{AddressDetails, UsedPoolAddress},
     bdk_wallet_ext::BdkWalletExt,
-    blockchain_client::BlockchainClient,
+    blockchain_client::{BlockchainClient, SyncReport, SyncScope},
     error::Error,
+    proof_of_reserves,
     psbt::Psbt,
     storage::{WalletConnectorFactory, WalletPersisterConnector},
-    transactions::{ToTransactionDetails, TransactionDetails},
-    utils::SortOrder,
+    transaction_builder::{CoinSelection, TxBuilder},
+    transactions::{
+        get_confirmations_at_height, ToTransactionDetails, TransactionDetails, TransactionTime, TransactionsCursor,
+    },
+    utils::{parse_txid, SortOrder, UtxoSort},
 };
 
 const EXTERNAL_KEYCHAIN: KeychainKind = KeychainKind::External;
 
+/// Weight of an input without its satisfaction: outpoint (36 bytes), script
+/// sig length (1 byte) and sequence (4 bytes)
+const TXIN_BASE_WEIGHT: Weight = Weight::from_wu(41 * 4);
+
+/// Default number of consecutive unused addresses after which full syncs stop
+/// looking for activity, as recommended by BIP44
+pub const DEFAULT_GAP_LIMIT: usize = 20;
+
 /// TLDR; A wallet is defined by its mnemonic + passphrase combo whereas a
 /// wallet account is defined by its derivation path from the wallet masterkey.
 /// In order to support wallet import from other major softwares, it has been
@@ -54,8 +96,172 @@ const EXTERNAL_KEYCHAIN: KeychainKind = KeychainKind::External;
 #[derive(Debug, Clone)]
 pub struct Account<C: WalletPersisterConnector<P>, P: WalletPersister> {
     derivation_path: DerivationPath,
+    network: Network,
+    script_type: ScriptType,
+    account_xpub: Xpub,
+    /// Fingerprint of the wallet master key, unknown for watch-only accounts
+    master_fingerprint: Option<Fingerprint>,
     wallet: Arc<RwLock<PersistedWallet<P>>>,
     persister_connector: C,
+    /// Outpoints excluded from coin selection. They are only kept in memory
+    frozen_utxos: Arc<RwLock<HashSet<OutPoint>>>,
+    /// Number of consecutive unused addresses after which full syncs stop
+    /// looking for activity, unless overridden
+    gap_limit: Arc<AtomicUsize>,
+    /// Keychains scanned by full syncs, both by default
+    sync_scope: Arc<RwLock<SyncScope>>,
+    /// External and internal descriptors of taproot accounts imported with a
+    /// script tree, which can't be rebuilt from the account key alone
+    tap_tree_descriptors: Option<(
+        miniscript::Descriptor<DescriptorPublicKey>,
+        miniscript::Descriptor<DescriptorPublicKey>,
+    )>,
+    /// External and internal descriptors of multisig accounts, involving
+    /// keys of other signers
+    multisig_descriptors: Option<(
+        miniscript::Descriptor<DescriptorPublicKey>,
+        miniscript::Descriptor<DescriptorPublicKey>,
+    )>,
+}
+
+/// Synced chain tip and revealed addresses of an account. Unlike a snapshot,
+/// it doesn't hold any transaction, it is only meant to be persisted so that
+/// a reopened account can resume with a partial sync instead of a full rescan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub tip_height: u32,
+    pub tip_hash: BlockHash,
+    /// Last revealed index of the external keychain, if any
+    pub external_index: Option<u32>,
+    /// Last revealed index of the internal keychain, if any
+    pub internal_index: Option<u32>,
+}
+
+/// Rules deciding which UTXOs can be spent, matching the ones used when
+/// creating a transaction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpendPolicy {
+    pub change_policy: ChangeSpendPolicy,
+    /// Whether unconfirmed UTXOs received from third parties can be spent
+    pub include_untrusted_pending: bool,
+}
+
+impl Default for SpendPolicy {
+    fn default() -> Self {
+        SpendPolicy {
+            change_policy: ChangeSpendPolicy::ChangeAllowed,
+            include_untrusted_pending: true,
+        }
+    }
+}
+
+/// Builder of an [`Account`] from a master private key, with named setters
+/// instead of [`Account::new`] positional arguments.
+///
+/// Unless set, the network is inferred from the master key, script type is
+/// native segwit, derivation path is the first BIP44 account of the script
+/// type and gap limit is `DEFAULT_GAP_LIMIT`. Storage must be set before
+/// building the account.
+///
+/// ```rust
+/// # use bdk_wallet::bitcoin::{NetworkKind, bip32::Xpriv};
+/// #
+/// # use andromeda_bitcoin::account::{Account, AccountConfig};
+/// # use andromeda_bitcoin::mnemonic::Mnemonic;
+/// # use andromeda_bitcoin::storage::MemoryPersisted;
+/// # use andromeda_common::{Network, ScriptType};
+/// #
+/// let mnemonic = Mnemonic::from_string(String::from("desk prevent enhance husband hungry idle member vessel room moment simple behave")).unwrap();
+/// let mprv = Xpriv::new_master(NetworkKind::Test, &mnemonic.inner().to_seed("")).unwrap();
+/// let account: Account<MemoryPersisted, MemoryPersisted> = AccountConfig::new(mprv)
+///     .network(Network::Testnet)
+///     .script_type(ScriptType::Taproot)
+///     .gap_limit(100)
+///     .storage(MemoryPersisted)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct AccountConfig<F> {
+    master_secret_key: Xpriv,
+    network: Network,
+    script_type: ScriptType,
+    derivation_path: Option<DerivationPath>,
+    gap_limit: usize,
+    storage: F,
+}
+
+impl AccountConfig<()> {
+    pub fn new(master_secret_key: Xpriv) -> Self {
+        let network = match master_secret_key.network {
+            NetworkKind::Main => Network::Bitcoin,
+            NetworkKind::Test => Network::Testnet,
+        };
+
+        AccountConfig {
+            master_secret_key,
+            network,
+            script_type: ScriptType::NativeSegwit,
+            derivation_path: None,
+            gap_limit: DEFAULT_GAP_LIMIT,
+            storage: (),
+        }
+    }
+}
+
+impl<F> AccountConfig<F> {
+    pub fn network(self, network: Network) -> Self {
+        Self { network, ..self }
+    }
+
+    pub fn script_type(self, script_type: ScriptType) -> Self {
+        Self { script_type, ..self }
+    }
+
+    pub fn derivation_path(self, derivation_path: DerivationPath) -> Self {
+        Self {
+            derivation_path: Some(derivation_path),
+            ..self
+        }
+    }
+
+    pub fn gap_limit(self, gap_limit: usize) -> Self {
+        Self { gap_limit, ..self }
+    }
+
+    pub fn storage<S>(self, storage: S) -> AccountConfig<S> {
+        AccountConfig {
+            master_secret_key: self.master_secret_key,
+            network: self.network,
+            script_type: self.script_type,
+            derivation_path: self.derivation_path,
+            gap_limit: self.gap_limit,
+            storage,
+        }
+    }
+
+    /// Builds the account, see [`Account::new`] for possible errors
+    pub fn build<C, P>(self) -> Result<Account<C, P>, Error>
+    where
+        C: WalletPersisterConnector<P>,
+        P: WalletPersister,
+        F: WalletConnectorFactory<C, P>,
+    {
+        let derivation_path = self
+            .derivation_path
+            .unwrap_or_else(|| DerivationPath::from_parts(self.script_type, self.network, 0));
+
+        let account = Account::new_with_options(
+            self.master_secret_key,
+            self.network,
+            self.script_type,
+            derivation_path,
+            self.storage,
+            false,
+        )?;
+
+        Ok(account.with_gap_limit(self.gap_limit))
+    }
 }
 
 type ReturnedDescriptor = (
@@ -94,6 +300,338 @@ fn build_account_descriptors(
     Ok((external, internal))
 }
 
+fn build_watch_only_account_descriptors(
+    account_xpub: Xpub,
+    script_type: ScriptType,
+) -> Result<(ReturnedDescriptor, ReturnedDescriptor), Error> {
+    let builder = match script_type {
+        ScriptType::Legacy => |xkey: (Xpub, DerivationPath)| descriptor!(pkh(xkey)),
+        ScriptType::NestedSegwit => |xkey: (Xpub, DerivationPath)| descriptor!(sh(wpkh(xkey))),
+        ScriptType::NativeSegwit => |xkey: (Xpub, DerivationPath)| descriptor!(wpkh(xkey)),
+        ScriptType::Taproot => |xkey: (Xpub, DerivationPath)| descriptor!(tr(xkey)),
+    };
+
+    let internal = builder((
+        account_xpub,
+        vec![ChildNumber::Normal {
+            index: KeychainKind::Internal as u32,
+        }]
+        .into(),
+    ))?;
+
+    let external = builder((
+        account_xpub,
+        vec![ChildNumber::Normal {
+            index: KeychainKind::External as u32,
+        }]
+        .into(),
+    ))?;
+
+    Ok((external, internal))
+}
+
+/// Returns the derivation path of a watch-only account, inferred from the
+/// script type, the network and the child number of its extended public key
+/// as defined by BIP44. The key must be an account-level one (hardened, at
+/// depth 3) for the provided network.
+pub(crate) fn watch_only_derivation_path(
+    account_xpub: &Xpub,
+    network: Network,
+    script_type: ScriptType,
+) -> Result<DerivationPath, Error> {
+    if account_xpub.depth != 3 {
+        return Err(Error::InvalidAccountXpub(format!(
+            "key is at depth {}, expected 3",
+            account_xpub.depth
+        )));
+    }
+
+    if account_xpub.network != NetworkKind::from(BdkNetwork::from(network)) {
+        return Err(Error::InvalidAccountXpub(format!("key is not for {network} network")));
+    }
+
+    let account_index = match account_xpub.child_number {
+        ChildNumber::Hardened { index } => index,
+        ChildNumber::Normal { .. } => {
+            return Err(Error::InvalidAccountXpub("account index is not hardened".to_string()));
+        }
+    };
+
+    Ok(DerivationPath::from_parts(script_type, network, account_index))
+}
+
+/// Splits a BIP389 multipath descriptor (e.g. `wpkh(xpub.../<0;1>/*)`) into
+/// its external and internal descriptors, along with their private keys.
+fn split_multipath_descriptor(
+    (descriptor, keymap, networks): ReturnedDescriptor,
+) -> Result<(ReturnedDescriptor, ReturnedDescriptor), Error> {
+    let descriptors = descriptor
+        .into_single_descriptors()
+        .map_err(|e| Error::InvalidDescriptor(e.to_string()))?;
+    let [external_descriptor, internal_descriptor]: [_; 2] = descriptors
+        .try_into()
+        .map_err(|_| Error::InvalidDescriptor("multipath descriptor must have exactly two paths".to_string()))?;
+
+    let mut keymaps: [BTreeMap<_, _>; 2] = Default::default();
+    for (public_key, secret_key) in keymap {
+        let keys = public_key
+            .into_single_keys()
+            .into_iter()
+            .zip(secret_key.into_single_keys())
+            .collect::<Vec<_>>();
+
+        for (index, keymap) in keymaps.iter_mut().enumerate() {
+            // Keys without multipath derivation are shared by both descriptors
+            let (public_key, secret_key) = keys[index % keys.len()].clone();
+            keymap.insert(public_key, secret_key);
+        }
+    }
+
+    let [external_keymap, internal_keymap] = keymaps;
+    Ok((
+        (external_descriptor, external_keymap, networks.clone()),
+        (internal_descriptor, internal_keymap, networks),
+    ))
+}
+
+/// Combines each key of the external descriptor with the matching one of the
+/// internal descriptor into a BIP389 multipath key (e.g. `xpub.../<0;1>/*`).
+/// Keys that are the same in both descriptors are kept as is.
+struct MultipathKeyTranslator {
+    internal_keys: Vec<DescriptorPublicKey>,
+}
+
+impl Translator<DescriptorPublicKey, DescriptorPublicKey, Error> for MultipathKeyTranslator {
+    fn pk(&mut self, pk: &DescriptorPublicKey) -> Result<DescriptorPublicKey, Error> {
+        if self.internal_keys.contains(pk) {
+            return Ok(pk.clone());
+        }
+
+        let DescriptorPublicKey::XPub(external_xkey) = pk else {
+            return Err(Error::InvalidDescriptor(format!("no internal key matches {pk}")));
+        };
+
+        // Matching key only differs by a single derivation step
+        let internal_xkey = self
+            .internal_keys
+            .iter()
+            .find_map(|key| match key {
+                DescriptorPublicKey::XPub(internal_xkey)
+                    if internal_xkey.origin == external_xkey.origin
+                        && internal_xkey.xkey == external_xkey.xkey
+                        && internal_xkey.wildcard == external_xkey.wildcard
+                        && internal_xkey.derivation_path.len() == external_xkey.derivation_path.len()
+                        && internal_xkey
+                            .derivation_path
+                            .into_iter()
+                            .zip(&external_xkey.derivation_path)
+                            .filter(|(internal_step, external_step)| internal_step != external_step)
+                            .count()
+                            == 1 =>
+                {
+                    Some(internal_xkey)
+                }
+                _ => None,
+            })
+            .ok_or_else(|| Error::InvalidDescriptor(format!("no internal key matches {pk}")))?;
+
+        let derivation_paths = DerivPaths::new(vec![
+            external_xkey.derivation_path.clone(),
+            internal_xkey.derivation_path.clone(),
+        ])
+        .ok_or_else(|| Error::InvalidDescriptor("multipath key must have derivation paths".to_string()))?;
+
+        Ok(DescriptorPublicKey::MultiXPub(DescriptorMultiXKey {
+            origin: external_xkey.origin.clone(),
+            xkey: external_xkey.xkey,
+            derivation_paths,
+            wildcard: external_xkey.wildcard,
+        }))
+    }
+
+    translate_hash_clone!(DescriptorPublicKey, DescriptorPublicKey, Error);
+}
+
+/// Builds the BIP389 multipath descriptor covering both the external and
+/// internal descriptors. Fails if they don't only differ by their keys'
+/// derivation paths.
+fn combine_multipath_descriptor(
+    external_descriptor: &miniscript::Descriptor<DescriptorPublicKey>,
+    internal_descriptor: &miniscript::Descriptor<DescriptorPublicKey>,
+) -> Result<miniscript::Descriptor<DescriptorPublicKey>, Error> {
+    let mut internal_keys = Vec::new();
+    internal_descriptor.for_each_key(|key| {
+        internal_keys.push(key.clone());
+        true
+    });
+
+    let multipath_descriptor = external_descriptor
+        .translate_pk(&mut MultipathKeyTranslator { internal_keys })
+        .map_err(|e| match e {
+            TranslateErr::TranslatorErr(e) => e,
+            TranslateErr::OuterError(e) => Error::InvalidDescriptor(e.to_string()),
+        })?;
+
+    // Descriptors can also differ by their scripts, not only by their keys
+    let descriptors = multipath_descriptor
+        .clone()
+        .into_single_descriptors()
+        .map_err(|e| Error::InvalidDescriptor(e.to_string()))?;
+    if descriptors != [external_descriptor.clone(), internal_descriptor.clone()] {
+        return Err(Error::InvalidDescriptor(
+            "external and internal descriptors cannot be combined into a multipath one".to_string(),
+        ));
+    }
+
+    Ok(multipath_descriptor)
+}
+
+/// Moves an extended key deriving receive addresses (`.../0/*`) to the
+/// change keychain (`.../1/*`). Keys without wildcard are left as is.
+fn internal_keychain_xkey<K: Clone>(xkey: &DescriptorXKey<K>) -> Result<DescriptorXKey<K>, Error> {
+    if xkey.wildcard == Wildcard::None {
+        return Ok(xkey.clone());
+    }
+
+    match xkey.derivation_path[..].split_last() {
+        Some((ChildNumber::Normal { index }, parent))
+            if *index == KeychainKind::External as u32 && xkey.wildcard == Wildcard::Unhardened =>
+        {
+            let derivation_path = parent
+                .iter()
+                .copied()
+                .chain([ChildNumber::Normal {
+                    index: KeychainKind::Internal as u32,
+                }])
+                .collect::<Vec<_>>();
+
+            Ok(DescriptorXKey {
+                derivation_path: derivation_path.into(),
+                ..xkey.clone()
+            })
+        }
+        _ => Err(Error::InvalidDescriptor(
+            "cannot infer internal descriptor, keys must derive from the receive keychain (/0/*)".to_string(),
+        )),
+    }
+}
+
+fn internal_keychain_key(key: &DescriptorPublicKey) -> Result<DescriptorPublicKey, Error> {
+    match key {
+        DescriptorPublicKey::XPub(xkey) => Ok(DescriptorPublicKey::XPub(internal_keychain_xkey(xkey)?)),
+        key => Ok(key.clone()),
+    }
+}
+
+struct InternalKeychainTranslator;
+
+impl Translator<DescriptorPublicKey, DescriptorPublicKey, Error> for InternalKeychainTranslator {
+    fn pk(&mut self, pk: &DescriptorPublicKey) -> Result<DescriptorPublicKey, Error> {
+        internal_keychain_key(pk)
+    }
+
+    translate_hash_clone!(DescriptorPublicKey, DescriptorPublicKey, Error);
+}
+
+/// Parses a descriptor, checking that its extended keys are for the given
+/// network
+fn parse_descriptor(descriptor: &str, network: Network) -> Result<ReturnedDescriptor, Error> {
+    let secp = Secp256k1::new();
+    let (descriptor, keymap) = miniscript::Descriptor::<DescriptorPublicKey>::parse_descriptor(&secp, descriptor)
+        .map_err(|e| Error::InvalidDescriptor(e.to_string()))?;
+
+    let network_kind = NetworkKind::from(BdkNetwork::from(network));
+    let is_network_valid = descriptor.for_each_key(|key| match key {
+        DescriptorPublicKey::Single(_) => true,
+        DescriptorPublicKey::XPub(xkey) => xkey.xkey.network == network_kind,
+        DescriptorPublicKey::MultiXPub(xkey) => xkey.xkey.network == network_kind,
+    });
+
+    if !is_network_valid {
+        return Err(Error::InvalidDescriptor(format!(
+            "descriptor keys are not for {network} network"
+        )));
+    }
+
+    Ok((descriptor, keymap, HashSet::from([BdkNetwork::from(network)])))
+}
+
+/// Derives the internal descriptor from the external one, moving each of its
+/// extended keys (and their private counterpart) from the receive keychain
+/// (`/0/*`) to the change one (`/1/*`).
+fn derive_internal_descriptor(
+    (external_descriptor, external_keymap, networks): &ReturnedDescriptor,
+) -> Result<ReturnedDescriptor, Error> {
+    if !external_descriptor.has_wildcard() {
+        return Err(Error::InvalidDescriptor(
+            "cannot infer internal descriptor from a descriptor without wildcard".to_string(),
+        ));
+    }
+
+    let internal_descriptor = external_descriptor
+        .translate_pk(&mut InternalKeychainTranslator)
+        .map_err(|e| match e {
+            TranslateErr::TranslatorErr(e) => e,
+            TranslateErr::OuterError(e) => Error::InvalidDescriptor(e.to_string()),
+        })?;
+
+    let internal_keymap = external_keymap
+        .iter()
+        .map(|(public_key, secret_key)| {
+            let secret_key = match secret_key {
+                DescriptorSecretKey::XPrv(xkey) => DescriptorSecretKey::XPrv(internal_keychain_xkey(xkey)?),
+                secret_key => secret_key.clone(),
+            };
+
+            Ok((internal_keychain_key(public_key)?, secret_key))
+        })
+        .collect::<Result<_, Error>>()?;
+
+    Ok((internal_descriptor, internal_keymap, networks.clone()))
+}
+
+/// Returns the external and internal descriptors of an account, the internal
+/// one being either provided, derived from a BIP389 multipath external
+/// descriptor or inferred from the external one following the `/0/*`
+/// (receive) and `/1/*` (change) convention. Descriptors' extended keys must
+/// be for the given network.
+fn resolve_descriptor_pair(
+    external_descriptor: &str,
+    internal_descriptor: Option<&str>,
+    network: Network,
+) -> Result<(ReturnedDescriptor, ReturnedDescriptor), Error> {
+    let external_descriptor = parse_descriptor(external_descriptor, network)?;
+
+    if external_descriptor.0.is_multipath() {
+        if internal_descriptor.is_some() {
+            return Err(Error::InvalidDescriptor(
+                "internal descriptor cannot be provided along a multipath descriptor".to_string(),
+            ));
+        }
+
+        return split_multipath_descriptor(external_descriptor);
+    }
+
+    let internal_descriptor = match internal_descriptor {
+        Some(internal_descriptor) => parse_descriptor(internal_descriptor, network)?,
+        None => derive_internal_descriptor(&external_descriptor)?,
+    };
+
+    Ok((external_descriptor, internal_descriptor))
+}
+
+/// Checks that the purpose index of the derivation path (first level, as
+/// defined in BIP44) is the one of the script type
+fn check_derivation_path_purpose(script_type: ScriptType, derivation_path: &DerivationPath) -> Result<(), Error> {
+    match derivation_path.into_iter().next() {
+        Some(purpose) if *purpose == ChildNumber::from(script_type) => Ok(()),
+        _ => Err(Error::ScriptTypePathMismatch {
+            script_type,
+            derivation_path: derivation_path.clone(),
+        }),
+    }
+}
+
 impl<C: WalletPersisterConnector<P>, P: WalletPersister> Account<C, P> {
     fn build_wallet_with_descriptors(
         external_descriptor: ReturnedDescriptor,
@@ -131,13 +669,10 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Account<C, P> {
     }
 
     fn build_wallet(
-        account_xprv: Xpriv,
+        (external_descriptor, internal_descriptor): (ReturnedDescriptor, ReturnedDescriptor),
         network: Network,
-        script_type: ScriptType,
         persister: &mut P,
     ) -> Result<PersistedWallet<P>, Error> {
-        let (external_descriptor, internal_descriptor) = build_account_descriptors(account_xprv, script_type)?;
-
         let wallet = Self::build_wallet_with_descriptors(
             external_descriptor.clone(),
             internal_descriptor.clone(),
@@ -184,9 +719,14 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Account<C, P> {
     /// #
     /// let mnemonic = Mnemonic::from_string(String::from("desk prevent enhance husband hungry idle member vessel room moment simple behave")).unwrap();
     /// let mprv = Xpriv::new_master(NetworkKind::Test, &mnemonic.inner().to_seed("")).unwrap();
-    /// let account = Account::new(mprv, Network::Testnet, ScriptType::NativeSegwit, DerivationPath::from_str("m/86'/1'/0'").unwrap(), MemoryPersisted);
+    /// let account = Account::new(mprv, Network::Testnet, ScriptType::NativeSegwit, DerivationPath::from_str("m/84'/1'/0'").unwrap(), MemoryPersisted);
     /// # })
     /// ```
+    ///
+    /// Returns [`Error::ScriptTypePathMismatch`] if the purpose of the
+    /// derivation path isn't the script type's one (e.g. `m/86'/...` for a
+    /// native segwit account), see [`Account::new_allowing_mismatch`] to skip
+    /// this check.
     pub fn new<F>(
         master_secret_key: Xpriv,
         network: Network,
@@ -197,6 +737,45 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Account<C, P> {
     where
         F: WalletConnectorFactory<C, P>,
     {
+        AccountConfig::new(master_secret_key)
+            .network(network)
+            .script_type(script_type)
+            .derivation_path(derivation_path)
+            .storage(factory)
+            .build()
+    }
+
+    /// Same as [`Account::new`], but accepts a derivation path whose purpose
+    /// doesn't match the script type, e.g. to restore accounts created by
+    /// wallets not following BIP44.
+    pub fn new_allowing_mismatch<F>(
+        master_secret_key: Xpriv,
+        network: Network,
+        script_type: ScriptType,
+        derivation_path: DerivationPath,
+        factory: F,
+    ) -> Result<Self, Error>
+    where
+        F: WalletConnectorFactory<C, P>,
+    {
+        Self::new_with_options(master_secret_key, network, script_type, derivation_path, factory, true)
+    }
+
+    fn new_with_options<F>(
+        master_secret_key: Xpriv,
+        network: Network,
+        script_type: ScriptType,
+        derivation_path: DerivationPath,
+        factory: F,
+        allow_mismatch: bool,
+    ) -> Result<Self, Error>
+    where
+        F: WalletConnectorFactory<C, P>,
+    {
+        if !allow_mismatch {
+            check_derivation_path_purpose(script_type, &derivation_path)?;
+        }
+
         let secp = Secp256k1::new();
 
         let account_xprv = master_secret_key.derive_priv(&secp, &derivation_path)?;
@@ -208,717 +787,3272 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Account<C, P> {
 
         Ok(Self {
             derivation_path,
+            network,
+            script_type,
+            account_xpub: Xpub::from_priv(&secp, &account_xprv),
+            master_fingerprint: Some(master_secret_key.fingerprint(&secp)),
             persister_connector: connector.clone(),
+            frozen_utxos: Default::default(),
+            gap_limit: Arc::new(AtomicUsize::new(DEFAULT_GAP_LIMIT)),
+            sync_scope: Default::default(),
+            tap_tree_descriptors: None,
+            multisig_descriptors: None,
             wallet: Arc::new(RwLock::new(Self::build_wallet(
-                account_xprv,
+                build_account_descriptors(account_xprv, script_type)?,
                 network,
-                script_type,
                 &mut persister,
             )?)),
         })
     }
 
-    /// Returns cloned derivation path
-    pub fn get_derivation_path(&self) -> DerivationPath {
-        self.derivation_path.clone()
-    }
-
-    /// Returns the last synced balance of an account.
-    ///
-    /// # Notes
+    /// From an account-level extended public key, returns a watch-only bitcoin
+    /// account. It can derive addresses, sync and list balance, transactions
+    /// and UTXOs, but cannot sign.
     ///
-    /// Balance details includes :
-    /// * immature coins
-    /// * trusted pending (unconfirmed internal)
-    /// * untrusted pending (unconfirmed external)
-    /// * confirmed coins
-    pub async fn get_balance(&self) -> BdkBalance {
-        self.get_wallet().await.balance()
-    }
+    /// The derivation path of the account is inferred from the script type,
+    /// the network and the child number of the extended public key, as defined
+    /// by BIP44. The key must be an account-level one for the given network.
+    pub fn new_watch_only<F>(
+        account_xpub: Xpub,
+        network: Network,
+        script_type: ScriptType,
+        factory: F,
+    ) -> Result<Self, Error>
+    where
+        F: WalletConnectorFactory<C, P>,
+    {
+        let derivation_path = watch_only_derivation_path(&account_xpub, network, script_type)?;
 
-    /// Returns a list of unspent outputs as a vector
-    ///
-    /// # Notes
-    ///
-    /// Later we might want to add pagination on top of that.
-    pub async fn get_utxos(&self) -> Vec<LocalUtxo> {
-        self.get_wallet().await.list_unspent().collect::<Vec<_>>()
+        let store_key = format!("{}_{}", account_xpub.fingerprint(), derivation_path);
+
+        let connector = factory.build(store_key);
+        let mut persister = connector.connect();
+
+        Ok(Self {
+            derivation_path,
+            network,
+            script_type,
+            account_xpub,
+            master_fingerprint: None,
+            persister_connector: connector.clone(),
+            frozen_utxos: Default::default(),
+            gap_limit: Arc::new(AtomicUsize::new(DEFAULT_GAP_LIMIT)),
+            sync_scope: Default::default(),
+            tap_tree_descriptors: None,
+            multisig_descriptors: None,
+            wallet: Arc::new(RwLock::new(Self::build_wallet(
+                build_watch_only_account_descriptors(account_xpub, script_type)?,
+                network,
+                &mut persister,
+            )?)),
+        })
     }
 
-    /// Marks a range of receive addresses (external keychain) as used and
-    /// persists the changes.
+    /// Builds an account from descriptor strings, e.g. exported by another
+    /// wallet software. The account can sign if descriptors contain private
+    /// keys, otherwise it is watch-only.
     ///
-    /// Acquires a mutable wallet lock, marks addresses from `from` to `to`
-    /// (exclusive) as used, and persists the changes. If `to` is `None`, it
-    /// defaults to `from + 1`.
+    /// If no internal descriptor is provided, it is derived from the external
+    /// one following the `/0/*` (receive) and `/1/*` (change) convention.
+    /// External descriptor can also be a BIP389 multipath descriptor (e.g.
+    /// `wpkh(xpub.../<0;1>/*)`) covering both keychains. Descriptor keys must
+    /// be for the given network.
     ///
-    /// # Parameters
-    /// - `from`: Starting index of addresses to mark as used.
-    /// - `to`: Optional ending index (exclusive).
-    pub async fn mark_receive_addresses_used_to(&self, from: u32, to: Option<u32>) -> Result<(), Error> {
-        let mut write_lock = self.get_mutable_wallet().await;
+    /// The account key must derive addresses right below the keychain level
+    /// (`[fingerprint/84h/0h/0h]xpub.../0/*`), so that the account's public
+    /// descriptors are the imported ones. The derivation path is the one of
+    /// the key's origin. Keys without origin, such as the ones exported by
+    /// watch-only accounts, are imported as [`Account::new_watch_only`] does.
+    ///
+    /// Taproot descriptors with a script tree (`tr(KEY,{...})`) are
+    /// supported, the account key being the internal one. Such accounts only
+    /// sign for the key path, script paths can be watched but not spent.
+    pub fn from_descriptor<F>(
+        external_descriptor: &str,
+        internal_descriptor: Option<&str>,
+        network: Network,
+        factory: F,
+    ) -> Result<Self, Error>
+    where
+        F: WalletConnectorFactory<C, P>,
+    {
+        let (
+            (external_descriptor, external_keymap, external_networks),
+            (internal_descriptor, internal_keymap, internal_networks),
+        ) = resolve_descriptor_pair(external_descriptor, internal_descriptor, network)?;
+
+        let script_type = match external_descriptor.desc_type() {
+            DescriptorType::Pkh => ScriptType::Legacy,
+            DescriptorType::ShWpkh => ScriptType::NestedSegwit,
+            DescriptorType::Wpkh => ScriptType::NativeSegwit,
+            DescriptorType::Tr => ScriptType::Taproot,
+            desc_type => {
+                return Err(Error::InvalidDescriptor(format!(
+                    "unsupported descriptor type: {:?}",
+                    desc_type
+                )))
+            }
+        };
 
-        write_lock.mark_used_to(EXTERNAL_KEYCHAIN, from, to);
+        let mut account_key = None;
+        match &external_descriptor {
+            // Leaves keys aren't the account's one
+            miniscript::Descriptor::Tr(tr) => account_key = Some(tr.internal_key().clone()),
+            _ => {
+                external_descriptor.for_each_key(|key| {
+                    account_key.get_or_insert_with(|| key.clone());
+                    true
+                });
+            }
+        }
 
-        Ok(())
-    }
+        let Some(DescriptorPublicKey::XPub(account_key)) = account_key else {
+            return Err(Error::InvalidDescriptor(
+                "descriptor key must be an extended key".to_string(),
+            ));
+        };
 
-    /// Returns the highest used address index `Some(u32)` from the wallet's outputs for a specific keychain,
-    /// or `None` if no outputs are found for the given keychain.
-    /// # Parameters
-    ///
-    /// - `keychain`: The type of keychain (e.g., external or internal) to filter the outputs by.
-    pub async fn get_highest_used_address_index_in_output(&self, keychain: KeychainKind) -> Result<Option<u32>, Error> {
-        let highest_index = self
-            .get_wallet()
-            .await
-            .list_output()
-            .into_iter()
-            .filter(|output| output.keychain == keychain)
-            .map(|output| output.derivation_index)
-            .reduce(|a, b| a.max(b));
+        if account_key.derivation_path.len() != 1 {
+            return Err(Error::InvalidDescriptor(
+                "descriptor key must derive addresses right below the keychain level (xpub.../0/*)".to_string(),
+            ));
+        }
 
-        Ok(highest_index)
+        let (master_fingerprint, derivation_path, store_key) = match account_key.origin {
+            Some((master_fingerprint, derivation_path)) => {
+                let store_key = format!("{}_{}", master_fingerprint, derivation_path);
+
+                (Some(master_fingerprint), derivation_path, store_key)
+            }
+            None => {
+                let derivation_path = watch_only_derivation_path(&account_key.xkey, network, script_type)?;
+                let store_key = format!("{}_{}", account_key.xkey.fingerprint(), derivation_path);
+
+                (None, derivation_path, store_key)
+            }
+        };
+
+        let tap_tree_descriptors = match &external_descriptor {
+            miniscript::Descriptor::Tr(tr) if tr.tap_tree().is_some() => {
+                Some((external_descriptor.clone(), internal_descriptor.clone()))
+            }
+            _ => None,
+        };
+
+        let connector = factory.build(store_key);
+        let mut persister = connector.connect();
+
+        Ok(Self {
+            derivation_path,
+            network,
+            script_type,
+            account_xpub: account_key.xkey,
+            master_fingerprint,
+            persister_connector: connector.clone(),
+            frozen_utxos: Default::default(),
+            gap_limit: Arc::new(AtomicUsize::new(DEFAULT_GAP_LIMIT)),
+            sync_scope: Default::default(),
+            tap_tree_descriptors,
+            multisig_descriptors: None,
+            wallet: Arc::new(RwLock::new(Self::build_wallet(
+                (
+                    (external_descriptor, external_keymap, external_networks),
+                    (internal_descriptor, internal_keymap, internal_networks),
+                ),
+                network,
+                &mut persister,
+            )?)),
+        })
     }
 
-    /// Returns the next address to be used to receive coins and marks it as
-    /// used
+    /// Builds a multisig account from a descriptor whose spending condition
+    /// involves several extended keys, such as
+    /// `wsh(sortedmulti(2,xpub1/0/*,xpub2/0/*,xpub3/0/*))` or a `tr(...)`
+    /// descriptor with `multi_a` leaves. As for [`Account::from_descriptor`],
+    /// the internal descriptor is inferred from the external one, which can
+    /// also be a BIP389 multipath descriptor.
     ///
-    /// Note:
-    /// The following should be done prior to calling this function:
-    /// - Syncing chain data
-    /// - Mark indexes up to LastUsedIndex (returned from API) as used
-    /// - BvE pool bitcoin addresses must be marked as used
-    pub async fn get_next_receive_address(&self) -> Result<AddressInfo, Error> {
-        let mut write_lock = self.get_mutable_wallet().await;
+    /// Addresses, balance and transactions are available watch-only. When
+    /// `our_signer` is provided, it must be the extended private key of one
+    /// of the descriptor's keys, and [`Account::sign`] then adds our partial
+    /// signature to PSBTs. The derivation path and master fingerprint of the
+    /// account are the ones of this key's origin.
+    pub fn new_multisig<F>(
+        descriptor: &str,
+        our_signer: Option<DescriptorSecretKey>,
+        network: Network,
+        factory: F,
+    ) -> Result<Self, Error>
+    where
+        F: WalletConnectorFactory<C, P>,
+    {
+        let secp = Secp256k1::new();
 
-        let address = write_lock.next_unused_address(EXTERNAL_KEYCHAIN);
-        write_lock.mark_used(EXTERNAL_KEYCHAIN, address.index);
+        let (
+            (external_descriptor, mut external_keymap, external_networks),
+            (internal_descriptor, mut internal_keymap, internal_networks),
+        ) = resolve_descriptor_pair(descriptor, None, network)?;
+
+        let script_type = match external_descriptor.desc_type() {
+            DescriptorType::Wsh | DescriptorType::WshSortedMulti => ScriptType::NativeSegwit,
+            DescriptorType::Tr => ScriptType::Taproot,
+            desc_type => {
+                return Err(Error::InvalidDescriptor(format!(
+                    "unsupported multisig descriptor type: {:?}",
+                    desc_type
+                )))
+            }
+        };
 
-        Ok(address)
-    }
+        let mut keys = Vec::new();
+        let all_extended = external_descriptor.for_each_key(|key| match key {
+            DescriptorPublicKey::XPub(xkey) => {
+                keys.push(xkey.clone());
+                true
+            }
+            _ => false,
+        });
 
-    /// Peeks a specific address to be used to receive coins and marks it as
-    /// used
-    pub async fn peek_receive_address(&self, index: u32) -> Result<AddressInfo, Error> {
-        let mut write_lock = self.get_mutable_wallet().await;
+        if !all_extended {
+            return Err(Error::InvalidDescriptor(
+                "multisig descriptor keys must be extended keys".to_string(),
+            ));
+        }
 
-        let address = write_lock.peek_address(EXTERNAL_KEYCHAIN, index);
-        write_lock.mark_used(EXTERNAL_KEYCHAIN, address.index);
+        if keys.iter().map(|key| key.xkey).collect::<HashSet<_>>().len() < 2 {
+            return Err(Error::InvalidDescriptor(
+                "multisig descriptor must involve at least two keys".to_string(),
+            ));
+        }
 
-        Ok(address)
+        let account_key = match our_signer {
+            Some(DescriptorSecretKey::XPrv(signer)) => {
+                let signer_xprv = signer.xkey.derive_priv(&secp, &signer.derivation_path)?;
+                let signer_xpub = Xpub::from_priv(&secp, &signer_xprv);
+
+                let account_key = keys
+                    .iter()
+                    .find(|key| key.xkey == signer_xpub)
+                    .cloned()
+                    .ok_or_else(|| Error::InvalidDescriptor("signer key isn't part of the descriptor".to_string()))?;
+
+                // Signer is registered for each of its keys, with their own origin and derivation
+                for (descriptor, keymap) in [
+                    (&external_descriptor, &mut external_keymap),
+                    (&internal_descriptor, &mut internal_keymap),
+                ] {
+                    descriptor.for_each_key(|key| {
+                        if let DescriptorPublicKey::XPub(xkey) = key {
+                            if xkey.xkey == signer_xpub {
+                                keymap.insert(
+                                    key.clone(),
+                                    DescriptorSecretKey::XPrv(DescriptorXKey {
+                                        origin: xkey.origin.clone(),
+                                        xkey: signer_xprv,
+                                        derivation_path: xkey.derivation_path.clone(),
+                                        wildcard: xkey.wildcard,
+                                    }),
+                                );
+                            }
+                        }
+                        true
+                    });
+                }
+
+                account_key
+            }
+            Some(_) => {
+                return Err(Error::InvalidDescriptor(
+                    "signer must be a single path extended private key".to_string(),
+                ))
+            }
+            None => keys[0].clone(),
+        };
+
+        let (master_fingerprint, derivation_path) = match account_key.origin {
+            Some((fingerprint, derivation_path)) => (Some(fingerprint), derivation_path),
+            None => (None, DerivationPath::master()),
+        };
+
+        // Several accounts can share the same key, the descriptor identifies the multisig one
+        let store_key = format!("multisig_{}", external_descriptor.descriptor_id());
+
+        let connector = factory.build(store_key);
+        let mut persister = connector.connect();
+
+        Ok(Self {
+            derivation_path,
+            network,
+            script_type,
+            account_xpub: account_key.xkey,
+            master_fingerprint,
+            persister_connector: connector.clone(),
+            frozen_utxos: Default::default(),
+            gap_limit: Arc::new(AtomicUsize::new(DEFAULT_GAP_LIMIT)),
+            sync_scope: Default::default(),
+            tap_tree_descriptors: None,
+            multisig_descriptors: Some((external_descriptor.clone(), internal_descriptor.clone())),
+            wallet: Arc::new(RwLock::new(Self::build_wallet(
+                (
+                    (external_descriptor, external_keymap, external_networks),
+                    (internal_descriptor, internal_keymap, internal_networks),
+                ),
+                network,
+                &mut persister,
+            )?)),
+        })
     }
 
-    /// Returns a boolean indicating whether or not the account owns the
-    /// provided address
-    pub async fn owns(&self, address: &Address) -> bool {
-        self.get_wallet().await.is_mine(address.script_pubkey())
+    /// Returns whether the account holds no private keys, i.e. it has been
+    /// built from an extended public key
+    pub async fn is_watch_only(&self) -> bool {
+        let wallet_lock = self.get_wallet().await;
+
+        wallet_lock.get_signers(KeychainKind::External).signers().is_empty()
+            && wallet_lock.get_signers(KeychainKind::Internal).signers().is_empty()
     }
 
-    /// Returns the maximum gap size `Some(u32)` from the wallet's outputs for a specific keychain,
-    /// or `None` if no outputs are found for the given keychain.
-    /// # Parameters
-    ///
-    /// - `keychain`: The type of keychain (e.g., external or internal) to filter the outputs by.
-    pub async fn get_maximum_gap_size(&self, keychain: KeychainKind) -> Result<Option<u32>, Error> {
-        let mut used_indices: Vec<u32> = self
-            .get_wallet()
-            .await
-            .list_output()
-            .into_iter()
-            .filter(|output| output.keychain == keychain)
-            .map(|output| output.derivation_index)
-            .collect();
+    /// Returns cloned derivation path
+    pub fn get_derivation_path(&self) -> DerivationPath {
+        self.derivation_path.clone()
+    }
 
-        if used_indices.len() == 0 {
-            // no index was used, return None
-            return Ok(None);
-        } else if used_indices.len() == 1 {
-            // only 1 address was used, return the address index directly
-            // for example, used_indices = [33]
-            return Ok(Some(used_indices[0]));
-        } else {
-            // calculate the maximum gap between indices and return it
-            // for example, used_indices = [33, 18, 100]
-            used_indices.sort();
-            // after sorted, used_indices = [18, 33, 100]
+    pub fn get_network(&self) -> Network {
+        self.network
+    }
 
-            // add 0 at first element, so we can get correct gap for first used index
-            // used_indices will become [0, 18, 33, 100]
-            used_indices.insert(0, 0);
+    pub fn get_script_type(&self) -> ScriptType {
+        self.script_type
+    }
 
-            let max_gap = used_indices.windows(2).map(|indices| indices[1] - indices[0]).max();
-            return Ok(max_gap);
-        }
+    pub fn get_account_xpub(&self) -> Xpub {
+        self.account_xpub
     }
 
-    /// Returns the stop gap range
-    pub fn get_stop_gap_range(&self, max_gap: u32) -> Result<u32, Error> {
-        let ranges = vec![20, 50, 100, 200, 500];
+    fn account_descriptor_key(&self, derivation_path: DerivationPath, wildcard: Wildcard) -> DescriptorPublicKey {
+        DescriptorPublicKey::XPub(DescriptorXKey {
+            origin: self
+                .master_fingerprint
+                .map(|fingerprint| (fingerprint, self.derivation_path.clone())),
+            xkey: self.account_xpub,
+            derivation_path,
+            wildcard,
+        })
+    }
 
-        match ranges.binary_search(&max_gap) {
-            Ok(index) => Ok(ranges[index]),
-            Err(index) => {
-                if index >= ranges.len() {
-                    Ok(*ranges.last().unwrap_or(&500))
-                } else {
-                    Ok(ranges[index])
-                }
-            }
-        }
+    /// Returns the account-level extended public key, prefixed with its key
+    /// origin (master fingerprint and derivation path) when known.
+    pub fn export_account_xpub(&self) -> String {
+        self.account_descriptor_key(DerivationPath::master(), Wildcard::None)
+            .to_string()
     }
 
-    /// Returns a bitcoin uri as defined in https://bips.dev/21/
-    pub async fn get_bitcoin_uri(
-        &mut self,
-        amount: Option<u64>,
-        label: Option<String>,
-        message: Option<String>,
-    ) -> Result<PaymentLink, Error> {
-        let address = self.get_next_receive_address().await?;
+    /// Returns the public descriptor of the given keychain, without any
+    /// private key, so that it can be imported in other wallets.
+    pub fn public_descriptor(&self, keychain: KeychainKind) -> Result<String, Error> {
+        if let Some((external_descriptor, internal_descriptor)) = self
+            .tap_tree_descriptors
+            .as_ref()
+            .or(self.multisig_descriptors.as_ref())
+        {
+            return Ok(match keychain {
+                KeychainKind::External => external_descriptor.to_string(),
+                KeychainKind::Internal => internal_descriptor.to_string(),
+            });
+        }
+
+        let key = self.account_descriptor_key(
+            vec![ChildNumber::Normal { index: keychain as u32 }].into(),
+            Wildcard::Unhardened,
+        );
 
-        Ok(PaymentLink::new_bitcoin_uri(address.address, amount, label, message))
+        let (descriptor, _, _) = match self.script_type {
+            ScriptType::Legacy => descriptor!(pkh(key))?,
+            ScriptType::NestedSegwit => descriptor!(sh(wpkh(key)))?,
+            ScriptType::NativeSegwit => descriptor!(wpkh(key))?,
+            ScriptType::Taproot => descriptor!(tr(key))?,
+        };
+
+        Ok(descriptor.to_string())
     }
 
-    /// Returns a paginated list of transactions.
-    ///
-    /// # Notes
-    ///
-    /// Returned transaction are simple ones with only amount value, txid,
-    /// confirmation time and fees value. For more details, `get_transaction`
-    /// can be called with txid
-    pub async fn get_transactions(
-        &self,
-        pagination: Pagination,
-        sort: Option<SortOrder>,
-    ) -> Result<Vec<TransactionDetails>, Error> {
-        let wallet_lock = self.get_wallet().await;
-        let transactions = wallet_lock.transactions().collect::<Vec<_>>();
+    /// Returns a BIP389 multipath public descriptor covering both the
+    /// external and internal keychains (`/<0;1>/*`).
+    pub fn public_multipath_descriptor(&self) -> Result<String, Error> {
+        let parse_public_descriptor = |keychain| {
+            miniscript::Descriptor::<DescriptorPublicKey>::from_str(&self.public_descriptor(keychain)?)
+                .map_err(|e| Error::InvalidDescriptor(e.to_string()))
+        };
 
-        // We first need to sort transactions by their time (last_seen for unconfirmed
-        // ones and confirmation_time for confirmed one) The collection that
-        // happen here might be consuming, maybe later we need to rework this part
-        let transactions = transactions
-            .into_iter()
-            .map(|tx| tx.to_transaction_details((&wallet_lock, (self.get_derivation_path()))))
-            .collect::<Result<Vec<_>, _>>()?;
+        let descriptor = combine_multipath_descriptor(
+            &parse_public_descriptor(KeychainKind::External)?,
+            &parse_public_descriptor(KeychainKind::Internal)?,
+        )?;
 
-        Ok(sort_and_paginate_txs(transactions, pagination, sort))
+        Ok(descriptor.to_string())
     }
 
-    /// Returns a single address if found in the graph.
+    /// Returns the last synced balance of an account.
     ///
     /// # Notes
     ///
-    /// The returned address detail contains balance and transactions
-    /// with output to the address, in addition to index and serialised
-    /// address. It can then be used to build an address list with enhanced
-    /// details
-    pub async fn get_address(
-        &self,
-        network: Network,
-        address_str: String,
-        client: Arc<BlockchainClient>,
-        sync: bool,
-    ) -> Result<Option<AddressDetails>, Error> {
-        let spk: bitcoin::ScriptBuf = Address::from_str(&address_str)?
-            .require_network(network.into())?
-            .script_pubkey();
+    /// Balance details includes :
+    /// * immature coins
+    /// * trusted pending (unconfirmed internal)
+    /// * untrusted pending (unconfirmed external)
+    /// * confirmed coins
+    pub async fn get_balance(&self) -> BdkBalance {
+        self.get_wallet().await.balance()
+    }
 
+    /// Same as [`Account::get_balance`], but only coins with at least
+    /// `min_confirmations` confirmations are considered confirmed, the other
+    /// ones being reclassified as pending. Confirmations are counted up to
+    /// `tip_height`, the wallet's latest checkpoint by default.
+    pub async fn get_balance_with_confirmations(&self, min_confirmations: u32, tip_height: Option<u32>) -> BdkBalance {
         let wallet_lock = self.get_wallet().await;
+        let wallet_tip_height = wallet_lock.latest_checkpoint().height();
+        let tip_height = tip_height.unwrap_or(wallet_tip_height);
 
-        if let Some((keychain, spk_index)) = wallet_lock.derivation_of_spk(spk.clone()) {
-            let update = {
-                if sync {
-                    Some(client.sync_spks(&wallet_lock, vec![spk]).await?)
-                } else {
-                    None
-                }
+        let mut balance = wallet_lock.balance();
+        for utxo in wallet_lock.list_unspent() {
+            let ChainPosition::Confirmed { anchor, .. } = utxo.chain_position else {
+                continue;
             };
 
-            if let Some(update) = update {
-                self.apply_update(update).await?;
+            // Immature coinbase outputs aren't part of the confirmed balance
+            let is_immature = wallet_lock
+                .get_tx(utxo.outpoint.txid)
+                .is_some_and(|tx| tx.tx_node.tx.is_coinbase())
+                && wallet_tip_height + 1 < anchor.block_id.height + COINBASE_MATURITY;
+
+            if is_immature || get_confirmations_at_height(anchor.block_id.height, tip_height) >= min_confirmations {
+                continue;
             }
 
-            let wallet_lock = self.get_wallet().await;
+            balance.confirmed -= utxo.txout.value;
+            if utxo.keychain == KeychainKind::Internal {
+                balance.trusted_pending += utxo.txout.value;
+            } else {
+                balance.untrusted_pending += utxo.txout.value;
+            }
+        }
 
-            let outpoints = wallet_lock.outpoints_from_spk_index(keychain, spk_index);
-            let spk_balance = wallet_lock.tx_graph().balance(
-                wallet_lock.local_chain(),
-                wallet_lock.local_chain().tip().block_id(),
-                outpoints,
-                |_, _| false,
-            );
+        balance
+    }
 
-            let transactions = wallet_lock
-                .outpoints_from_spk_index(keychain, spk_index)
-                .filter_map(|(_, op)| wallet_lock.tx_graph().get_tx_node(op.txid))
-                .map(|tx_node| tx_node.to_transaction_details((&wallet_lock, self.get_derivation_path())))
-                .collect::<Result<Vec<_>, _>>()?;
+    /// Returns the amount that can actually be spent right now under the given
+    /// policy. Frozen UTXOs and immature coinbase outputs are excluded.
+    pub async fn get_spendable_balance(&self, policy: SpendPolicy) -> Amount {
+        let frozen_utxos = self.get_frozen_utxos().await;
+        let wallet_lock = self.get_wallet().await;
+        let tip_height = wallet_lock.latest_checkpoint().height();
+
+        wallet_lock
+            .list_unspent()
+            .filter(|utxo| !frozen_utxos.contains(&utxo.outpoint))
+            .filter(|utxo| match policy.change_policy {
+                ChangeSpendPolicy::ChangeAllowed => true,
+                ChangeSpendPolicy::OnlyChange => utxo.keychain == KeychainKind::Internal,
+                ChangeSpendPolicy::ChangeForbidden => utxo.keychain == KeychainKind::External,
+            })
+            .filter(|utxo| match utxo.chain_position {
+                ChainPosition::Confirmed { anchor, .. } => {
+                    let is_coinbase = wallet_lock
+                        .get_tx(utxo.outpoint.txid)
+                        .is_some_and(|tx| tx.tx_node.tx.is_coinbase());
+
+                    !is_coinbase || tip_height + 1 >= anchor.block_id.height + COINBASE_MATURITY
+                }
+                // Unconfirmed change is trusted as we created it
+                ChainPosition::Unconfirmed { .. } => {
+                    utxo.keychain == KeychainKind::Internal || policy.include_untrusted_pending
+                }
+            })
+            .map(|utxo| utxo.txout.value)
+            .sum()
+    }
 
-            let address_str = wallet_lock.peek_address(keychain, spk_index).address.to_string();
-            return Ok(Some(AddressDetails {
-                index: spk_index,
-                address: address_str,
-                balance: spk_balance,
-                transactions: transactions,
-                keychain: keychain,
-            }));
+    /// Sets the gap limit used by full syncs when no stop gap is provided,
+    /// `DEFAULT_GAP_LIMIT` by default
+    pub fn with_gap_limit(self, gap_limit: usize) -> Self {
+        Self {
+            gap_limit: Arc::new(AtomicUsize::new(gap_limit)),
+            ..self
         }
+    }
 
-        Ok(None)
+    /// Returns the gap limit used by full syncs when no stop gap is provided
+    pub fn get_gap_limit(&self) -> usize {
+        self.gap_limit.load(Ordering::Relaxed)
     }
 
-    /// Returns a paginated list of addresses.
-    ///
-    /// # Notes
-    ///
-    /// Each returned address detail contains balance and transactions
-    /// with output to the address, in addition to index and serialised
-    /// address. It can then be used to build an address list with enhanced
-    /// details
-    pub async fn get_addresses(
-        &self,
-        pagination: Pagination,
-        client: Arc<BlockchainClient>,
-        keychain: KeychainKind,
-        force_sync: bool,
-    ) -> Result<Vec<AddressDetails>, Error> {
-        let (skip, take) = (pagination.skip as u32, pagination.take as u32);
-        let spks_range = skip..(skip + take - 1);
+    /// Updates the gap limit used by full syncs when no stop gap is provided
+    pub fn set_gap_limit(&self, gap_limit: usize) {
+        self.gap_limit.store(gap_limit, Ordering::Relaxed);
+    }
 
-        // We need to reveal addresses to be able to sync them
-        let mut wallet_lock = self.get_mutable_wallet().await;
-        let _ = wallet_lock.reveal_addresses_to(keychain, spks_range.end);
-        drop(wallet_lock);
+    /// Returns the keychains scanned by full syncs
+    pub async fn get_sync_scope(&self) -> SyncScope {
+        *self.sync_scope.read().await
+    }
 
-        let update = {
-            let wallet_lock = self.get_wallet().await;
+    /// Restricts the keychains scanned by full syncs, e.g. to skip change
+    /// scanning for receive-only accounts
+    pub async fn set_sync_scope(&self, sync_scope: SyncScope) {
+        *self.sync_scope.write().await = sync_scope;
+    }
 
-            let spks = spks_range
-                .clone()
-                .filter_map(|index| wallet_lock.spk_index().spk_at_index(keychain, index))
-                .collect::<Vec<_>>();
+    /// Excludes an UTXO from coin selection until it is unfrozen
+    pub async fn freeze_utxo(&self, outpoint: OutPoint) {
+        self.frozen_utxos.write().await.insert(outpoint);
+    }
 
-            // Sync missing spks (or all if forced)
-            let spks_to_sync = if force_sync {
-                spks
-            } else {
-                client.inner().filter_already_fetched(spks).await
-            };
+    /// Makes a frozen UTXO available to coin selection again
+    pub async fn unfreeze_utxo(&self, outpoint: &OutPoint) {
+        self.frozen_utxos.write().await.remove(outpoint);
+    }
 
-            if !spks_to_sync.is_empty() {
-                Some(client.sync_spks(&wallet_lock, spks_to_sync).await?)
-            } else {
-                None
-            }
-        };
+    /// Returns the outpoints excluded from coin selection
+    pub async fn get_frozen_utxos(&self) -> HashSet<OutPoint> {
+        self.frozen_utxos.read().await.clone()
+    }
 
-        if let Some(update) = update {
-            self.apply_update(update).await?;
-        }
+    /// Returns a sorted page of unspent outputs, along with the total number
+    /// of unspent outputs of the account
+    pub async fn get_utxos(&self, pagination: Pagination, sort: Option<UtxoSort>) -> (Vec<LocalUtxo>, usize) {
+        let utxos = self.get_wallet().await.list_unspent().collect::<Vec<_>>();
+        let total = utxos.len();
 
-        let wallet_lock = self.get_wallet().await;
+        (sort_and_paginate_utxos(utxos, pagination, sort), total)
+    }
 
-        // Find tx data from spk
-        let mut address_details = Vec::new();
-        for spk_index in spks_range {
-            let outpoints = wallet_lock.outpoints_from_spk_index(keychain, spk_index);
-            let spk_balance = wallet_lock.tx_graph().balance(
-                wallet_lock.local_chain(),
-                wallet_lock.local_chain().tip().block_id(),
-                outpoints,
-                |_, _| false,
-            );
+    /// Returns UTXOs whose value is lower than the fee needed to spend them at
+    /// the given fee rate (sat/vB), meaning they are economically
+    /// unspendable.
+    pub async fn dust_utxos(&self, fee_rate: u64) -> Result<Vec<LocalUtxo>, Error> {
+        let fee_rate = FeeRate::from_sat_per_vb(fee_rate).unwrap_or(FeeRate::MAX);
+        let wallet_lock = self.get_wallet().await;
 
-            let transactions = wallet_lock
-                .outpoints_from_spk_index(keychain, spk_index)
-                .filter_map(|(_, op)| wallet_lock.tx_graph().get_tx_node(op.txid))
-                .map(|tx_node| tx_node.to_transaction_details((&wallet_lock, self.get_derivation_path())))
-                .collect::<Result<Vec<_>, _>>()?;
+        let mut dust_utxos = Vec::new();
+        for utxo in wallet_lock.list_unspent() {
+            let satisfaction_weight = wallet_lock
+                .public_descriptor(utxo.keychain)
+                .max_weight_to_satisfy()
+                .map_err(|e| Error::InvalidDescriptor(e.to_string()))?;
 
-            let address_str = wallet_lock.peek_address(keychain, spk_index).address.to_string();
+            let spending_vsize = (TXIN_BASE_WEIGHT + satisfaction_weight).to_vbytes_ceil();
+            let spending_fee = fee_rate.fee_vb(spending_vsize).unwrap_or(Amount::MAX);
 
-            address_details.push(AddressDetails {
-                index: spk_index,
-                address: address_str,
-                balance: spk_balance,
-                transactions: transactions,
-                keychain: keychain,
-            });
+            if utxo.txout.value < spending_fee {
+                dust_utxos.push(utxo);
+            }
         }
 
-        Ok(address_details)
+        Ok(dust_utxos)
     }
 
-    /// Given a txid, returns a complete transaction    
-    pub async fn get_transaction(&self, txid: String) -> Result<TransactionDetails, Error> {
-        let txid = Txid::from_str(&txid)?;
+    /// Returns a transaction builder consolidating the account's UTXOs into a
+    /// single output paying to a fresh change address.
+    ///
+    /// Up to `max_inputs` UTXOs are spent, oldest first, all of them if not
+    /// provided. Frozen UTXOs are skipped. Fee rate is in sat/vB.
+    pub async fn build_consolidation(
+        self: Arc<Self>,
+        fee_rate: u64,
+        max_inputs: Option<usize>,
+    ) -> Result<TxBuilder<C, P>, Error> {
+        let frozen_utxos = self.get_frozen_utxos().await;
+        let (utxos, _) = self.get_utxos(Pagination::default(), Some(UtxoSort::Oldest)).await;
+
+        let utxos = utxos
+            .into_iter()
+            .filter(|utxo| !frozen_utxos.contains(&utxo.outpoint))
+            .take(max_inputs.unwrap_or(usize::MAX));
+
+        let tx_builder = TxBuilder::new()
+            .set_account(self.clone())
+            .clear_recipients()
+            .set_coin_selection(CoinSelection::Manual)
+            .set_fee_rate(fee_rate as f32)?;
 
-        let wallet_lock = self.get_wallet().await;
-        let tx = wallet_lock
-            .transactions()
-            .find(|tx| tx.tx_node.compute_txid() == txid)
-            .ok_or(Error::TransactionNotFound)?;
+        // Change address is only reserved once the fee rate is validated
+        let drain_to = {
+            let mut write_lock = self.get_mutable_wallet().await;
 
-        tx.to_transaction_details((&wallet_lock, self.get_derivation_path()))
-    }
+            let address = write_lock.next_unused_address(KeychainKind::Internal);
+            write_lock.mark_used(KeychainKind::Internal, address.index);
 
-    /// Given a mutable reference to a PSBT, and sign options, tries to sign
-    /// inputs elligible
-    pub async fn sign(&self, psbt: &mut BdkPsbt, sign_options: Option<SignOptions>) -> Result<(), Error> {
-        let sign_options = sign_options.unwrap_or_default();
-        self.get_wallet().await.sign(psbt, sign_options)?;
+            address.script_pubkey()
+        };
 
-        Ok(())
-    }
+        let tx_builder = utxos.fold(tx_builder.set_drain_to(Some(drain_to)), |tx_builder, utxo| {
+            tx_builder.add_utxo_to_spend(&utxo.outpoint)
+        });
 
-    /// Returns whether or not the account's wallet has already been synced at
-    /// least once
-    pub async fn has_sync_data(&self) -> bool {
-        let wallet_lock = self.get_wallet().await;
-        wallet_lock.latest_checkpoint().hash() != genesis_block(wallet_lock.network()).block_hash()
+        Ok(tx_builder)
     }
 
-    pub async fn bump_transactions_fees(&self, txid: String, fees: u64) -> Result<Psbt, Error> {
-        let mut wallet_lock: RwLockWriteGuard<'_, PersistedWallet<P>> = self.get_mutable_wallet().await;
-        let mut fee_bump_tx = wallet_lock.build_fee_bump(Txid::from_str(&txid)?)?;
-
-        fee_bump_tx.fee_absolute(Amount::from_sat(fees));
+    /// Builds and signs a BIP-127 style proof of reserves committing to the
+    /// given message: a transaction spending a challenge input derived from
+    /// the message along with all the account's UTXOs into a single
+    /// unspendable output. It can be checked with
+    /// [`proof_of_reserves::verify_proof_of_reserves`] and can't be broadcast.
+    pub async fn build_proof_of_reserves(&self, message: &str) -> Result<Psbt, Error> {
+        let mut psbt = {
+            let mut wallet_lock = self.get_mutable_wallet().await;
+            let (challenge_outpoint, challenge_input, challenge_weight) = proof_of_reserves::challenge_input(message);
+
+            let mut tx_builder = wallet_lock.build_tx();
+            tx_builder
+                .drain_wallet()
+                .drain_to(proof_of_reserves::unspendable_script())
+                .add_foreign_utxo(challenge_outpoint, challenge_input, challenge_weight)?
+                .fee_absolute(Amount::ZERO)
+                .ordering(TxOrdering::Untouched);
+
+            tx_builder.finish()?
+        };
 
-        let psbt = fee_bump_tx.finish()?;
+        self.sign(&mut psbt, None).await?;
 
         Ok(psbt.into())
     }
 
-    pub async fn apply_update(&self, update: impl Into<Update>) -> Result<(), Error> {
-        let mut wallet_lock = self.get_mutable_wallet().await;
-        wallet_lock.apply_update_at(update, now().as_secs())?;
+    /// Marks a range of receive addresses (external keychain) as used and
+    /// persists the changes.
+    ///
+    /// Acquires a mutable wallet lock, marks addresses from `from` to `to`
+    /// (exclusive) as used, and persists the changes. If `to` is `None`, it
+    /// defaults to `from + 1`.
+    ///
+    /// # Parameters
+    /// - `from`: Starting index of addresses to mark as used.
+    /// - `to`: Optional ending index (exclusive).
+    pub async fn mark_receive_addresses_used_to(&self, from: u32, to: Option<u32>) -> Result<(), Error> {
+        let mut write_lock = self.get_mutable_wallet().await;
 
-        self.persist(wallet_lock).await?;
+        write_lock.mark_used_to(EXTERNAL_KEYCHAIN, from, to);
 
         Ok(())
     }
 
-    async fn persist(&self, mut wallet_lock: RwLockWriteGuard<'_, PersistedWallet<P>>) -> Result<(), Error> {
-        let mut persister = self.persister_connector.connect();
-
-        wallet_lock.persist(&mut persister).map_err(|_e| Error::PersistError)?;
-        drop(wallet_lock);
+    /// Returns the highest used address index `Some(u32)` from the wallet's outputs for a specific keychain,
+    /// or `None` if no outputs are found for the given keychain.
+    /// # Parameters
+    ///
+    /// - `keychain`: The type of keychain (e.g., external or internal) to filter the outputs by.
+    pub async fn get_highest_used_address_index_in_output(&self, keychain: KeychainKind) -> Result<Option<u32>, Error> {
+        let highest_index = self
+            .get_wallet()
+            .await
+            .list_output()
+            .into_iter()
+            .filter(|output| output.keychain == keychain)
+            .map(|output| output.derivation_index)
+            .reduce(|a, b| a.max(b));
 
-        Ok(())
+        Ok(highest_index)
     }
 
-    pub fn clear_store(&self) -> Result<(), Error> {
-        let mut persister = self.persister_connector.connect();
+    /// Returns the index of the next unused address of the keychain: the
+    /// lowest revealed one that is still unused, or else the next one to be
+    /// revealed. Addresses are used once they received coins or were marked
+    /// as used.
+    pub async fn next_unused_index(&self, keychain: KeychainKind) -> u32 {
+        let wallet_lock = self.get_wallet().await;
+        let spk_index = wallet_lock.spk_index();
 
-        P::persist(&mut persister, &ChangeSet::default()).map_err(|_e| Error::PersistError)?;
-        Ok(())
+        spk_index
+            .unused_keychain_spks(keychain)
+            .next()
+            .map(|(index, _)| index)
+            .unwrap_or_else(|| spk_index.next_index(keychain).map_or(0, |(index, _)| index))
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use std::{str::FromStr, sync::Arc};
 
-    use andromeda_api::{
-        tests::utils::{common_api_client, setup_test_connection},
-        BASE_WALLET_API_V1,
-    };
-    use andromeda_common::Network;
-    use bdk_wallet::{
-        bitcoin::{
-            bip32::{DerivationPath, Xpriv},
-            Address, NetworkKind,
-        },
-        serde_json,
-    };
-    use wiremock::{
-        matchers::{body_string_contains, method, path, path_regex},
-        Mock, MockServer, ResponseTemplate,
-    };
+    /// Returns the number of revealed addresses of the keychain that are used
+    pub async fn addresses_used_count(&self, keychain: KeychainKind) -> u32 {
+        let wallet_lock = self.get_wallet().await;
+        let spk_index = wallet_lock.spk_index();
 
-    use super::{Account, ScriptType};
-    use crate::{
-        blockchain_client::BlockchainClient, mnemonic::Mnemonic, read_mock_file, storage::MemoryPersisted,
-        transactions::Pagination, utils::SortOrder,
-    };
+        spk_index
+            .revealed_keychain_spks(keychain)
+            .filter(|(index, _)| spk_index.is_used(keychain, *index))
+            .count() as u32
+    }
 
-    fn set_test_account(script_type: ScriptType, derivation_path: &str) -> Account<MemoryPersisted, MemoryPersisted> {
-        let network = NetworkKind::Test;
-        let mnemonic = Mnemonic::from_string("category law logic swear involve banner pink room diesel fragile sunset remove whale lounge captain code hobby lesson material current moment funny vast fade".to_string()).unwrap();
-        let master_secret_key = Xpriv::new_master(network, &mnemonic.inner().to_seed("")).unwrap();
+    /// Returns the next address to be used to receive coins and marks it as
+    /// used
+    ///
+    /// Note:
+    /// The following should be done prior to calling this function:
+    /// - Syncing chain data
+    /// - Mark indexes up to LastUsedIndex (returned from API) as used
+    /// - BvE pool bitcoin addresses must be marked as used
+    pub async fn get_next_receive_address(&self) -> Result<AddressInfo, Error> {
+        let mut write_lock = self.get_mutable_wallet().await;
 
-        let derivation_path = DerivationPath::from_str(derivation_path).unwrap();
+        let address = write_lock.next_unused_address(EXTERNAL_KEYCHAIN);
+        write_lock.mark_used(EXTERNAL_KEYCHAIN, address.index);
 
-        Account::new(
-            master_secret_key,
-            Network::Testnet,
-            script_type,
-            derivation_path,
-            MemoryPersisted {},
-        )
-        .unwrap()
+        Ok(address)
     }
 
-    fn set_test_account_regtest(
-        script_type: ScriptType,
-        derivation_path: &str,
-    ) -> Account<MemoryPersisted, MemoryPersisted> {
-        let network = NetworkKind::Test;
-        let mnemonic = Mnemonic::from_string(
-            "onion ancient develop team busy purchase salmon robust danger wheat rich empower".to_string(),
-        )
-        .unwrap();
-        let master_secret_key = Xpriv::new_master(network, &mnemonic.inner().to_seed("")).unwrap();
+    /// Peeks a specific address to be used to receive coins and marks it as
+    /// used
+    pub async fn peek_receive_address(&self, index: u32) -> Result<AddressInfo, Error> {
+        let mut write_lock = self.get_mutable_wallet().await;
 
-        let derivation_path = DerivationPath::from_str(derivation_path).unwrap();
+        let address = write_lock.peek_address(EXTERNAL_KEYCHAIN, index);
+        write_lock.mark_used(EXTERNAL_KEYCHAIN, address.index);
 
-        Account::new(
-            master_secret_key,
-            Network::Regtest,
-            script_type,
-            derivation_path,
-            MemoryPersisted {},
-        )
-        .unwrap()
+        Ok(address)
     }
 
-    fn set_test_account_regtest2(
-        script_type: ScriptType,
-        derivation_path: &str,
-    ) -> Account<MemoryPersisted, MemoryPersisted> {
-        let network = NetworkKind::Test;
-        let mnemonic = Mnemonic::from_string(
-            "remove over athlete patient priority unable memory axis sunset home balance sausage".to_string(),
-        )
-        .unwrap();
-        let master_secret_key = Xpriv::new_master(network, &mnemonic.inner().to_seed("")).unwrap();
-
-        let derivation_path = DerivationPath::from_str(derivation_path).unwrap();
+    /// Returns an address of the given keychain, e.g. to verify a change
+    /// address. When `index` is provided, the address at this index is
+    /// peeked, otherwise the next unused one is revealed. Unlike receive
+    /// addresses getters, the address is not marked as used.
+    pub async fn get_address_for_keychain(&self, keychain: KeychainKind, index: Option<u32>) -> AddressInfo {
+        let mut write_lock = self.get_mutable_wallet().await;
 
-        Account::new(
-            master_secret_key,
-            Network::Regtest,
-            script_type,
-            derivation_path,
-            MemoryPersisted {},
-        )
-        .unwrap()
+        match index {
+            Some(index) => write_lock.peek_address(keychain, index),
+            None => write_lock.next_unused_address(keychain),
+        }
     }
 
-    fn set_test_account_regtest3(
-        script_type: ScriptType,
-        derivation_path: &str,
-    ) -> Account<MemoryPersisted, MemoryPersisted> {
-        let network = NetworkKind::Test;
-        let mnemonic = Mnemonic::from_string(
-            "submit exhibit banner enter situate exact north fog era family south style".to_string(),
-        )
-        .unwrap();
-        let master_secret_key = Xpriv::new_master(network, &mnemonic.inner().to_seed("")).unwrap();
+    /// Reveals the next `count` receive addresses, marks them as used and
+    /// signs each of them with the provided signer, returning payloads ready
+    /// to be sent to the bitcoin addresses API.
+    ///
+    /// # Notes
+    ///
+    /// `highest_used_index` should be the one returned by
+    /// `get_bitcoin_address_highest_index`, so that the pool never overlaps
+    /// addresses already registered on the API side.
+    pub async fn generate_address_pool<F>(
+        &self,
+        count: u32,
+        highest_used_index: Option<u64>,
+        signer: F,
+    ) -> Result<Vec<ApiBitcoinAddressCreationPayload>, Error>
+    where
+        F: Fn(&AddressInfo) -> Result<String, Error>,
+    {
+        let mut write_lock = self.get_mutable_wallet().await;
 
-        let derivation_path = DerivationPath::from_str(derivation_path).unwrap();
+        let next_index = write_lock
+            .derivation_index(EXTERNAL_KEYCHAIN)
+            .map_or(0, |index| index + 1);
+        let first_index = match highest_used_index {
+            Some(highest_used_index) => u32::try_from(highest_used_index)
+                .ok()
+                .and_then(|highest_used_index| highest_used_index.checked_add(1))
+                .ok_or(Error::AddressIndexOutOfRange)?
+                .max(next_index),
+            None => next_index,
+        };
 
-        Account::new(
-            master_secret_key,
-            Network::Regtest,
-            script_type,
-            derivation_path,
-            MemoryPersisted {},
-        )
-        .unwrap()
+        // Addresses are derived at unhardened indexes only
+        let end_index = first_index
+            .checked_add(count)
+            .filter(|end_index| *end_index <= 1 << 31)
+            .ok_or(Error::AddressIndexOutOfRange)?;
+
+        let pool = (first_index..end_index)
+            .map(|index| {
+                let address = write_lock.peek_address(EXTERNAL_KEYCHAIN, index);
+
+                Ok(ApiBitcoinAddressCreationPayload {
+                    BitcoinAddress: address.to_string(),
+                    BitcoinAddressSignature: signer(&address)?,
+                    BitcoinAddressIndex: index as u64,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        // Addresses are only handed out once they are all signed, and must be revealed to be marked as used
+        if count > 0 {
+            let _ = write_lock.reveal_addresses_to(EXTERNAL_KEYCHAIN, end_index - 1);
+        }
+        for index in first_index..end_index {
+            write_lock.mark_used(EXTERNAL_KEYCHAIN, index);
+        }
+
+        Ok(pool)
     }
 
-    fn set_test_account_for_mainnet(
-        script_type: ScriptType,
-        derivation_path: &str,
+    /// Returns the pool addresses that received funds on-chain but are not
+    /// flagged as used by the API yet, each along with a newly generated and
+    /// signed address to replace it via `update_bitcoin_address`. This
+    /// prevents handing out an address twice. Pool addresses whose index
+    /// can't be one of the account's addresses are skipped.
+    ///
+    /// # Notes
+    ///
+    /// Wallet should be synced prior to calling this function.
+    pub async fn reconcile_address_pool<F>(
+        &self,
+        pool: &[ApiWalletBitcoinAddress],
+        highest_used_index: Option<u64>,
+        signer: F,
+    ) -> Result<Vec<UsedPoolAddress>, Error>
+    where
+        F: Fn(&AddressInfo) -> Result<String, Error>,
+    {
+        let used_pool_addresses = {
+            let wallet_lock = self.get_wallet().await;
+
+            pool.iter()
+                .filter(|pool_address| pool_address.Used == 0)
+                .filter_map(|pool_address| {
+                    let index = pool_address.BitcoinAddressIndex?;
+                    let has_received = wallet_lock
+                        .outpoints_from_spk_index(EXTERNAL_KEYCHAIN, u32::try_from(index).ok()?)
+                        .next()
+                        .is_some();
+
+                    has_received.then(|| (pool_address.ID.clone(), index))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        if used_pool_addresses.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let replacements = self
+            .generate_address_pool(used_pool_addresses.len() as u32, highest_used_index, signer)
+            .await?;
+
+        Ok(used_pool_addresses
+            .into_iter()
+            .zip(replacements)
+            .map(|((id, index), replacement)| UsedPoolAddress { id, index, replacement })
+            .collect())
+    }
+
+    /// Returns a boolean indicating whether or not the account owns the
+    /// provided address
+    pub async fn owns(&self, address: &Address) -> bool {
+        self.get_wallet().await.is_mine(address.script_pubkey())
+    }
+
+    /// Returns a boolean indicating whether or not the account owns the
+    /// provided script. Unlike [`Account::owns`], it also works for scripts
+    /// that can't be represented as an address.
+    pub async fn owns_script(&self, script: &Script) -> bool {
+        self.get_wallet().await.is_mine(script.to_owned())
+    }
+
+    /// Signs a message with the private key deriving one of the account's
+    /// addresses, following Bitcoin's message signing scheme. The returned
+    /// signature is base64-encoded and can be verified by recovering the
+    /// public key and checking it against the address.
+    ///
+    /// # Notes
+    ///
+    /// The header byte of the signature flags the address type as defined in
+    /// BIP137. This scheme is ECDSA-based, thus taproot addresses are signed
+    /// following BIP-322 simple signature format instead, as in
+    /// [`Account::sign_message`].
+    pub async fn sign_bitcoin_address(&self, address: &str, message: &str) -> Result<String, Error> {
+        let (address, private_key, merkle_root) = self.get_address_private_key(address).await?;
+
+        let header = match address.address_type() {
+            Some(AddressType::P2pkh) => 31,
+            // Accounts only derive P2SH-P2WPKH addresses
+            Some(AddressType::P2sh) => 35,
+            Some(AddressType::P2wpkh) => 39,
+            Some(AddressType::P2tr) => {
+                return crate::message::sign_message(message, &address, &private_key, merkle_root)
+            }
+            _ => return Err(Error::MessageSigningNotSupported),
+        };
+
+        let secp = Secp256k1::new();
+        let msg_hash = signed_msg_hash(message);
+        let signature = secp.sign_ecdsa_recoverable(&Message::from_digest(msg_hash.to_byte_array()), &private_key);
+        let (recovery_id, compact_signature) = signature.serialize_compact();
+
+        let mut serialized = [0u8; 65];
+        serialized[0] = header + recovery_id.to_i32() as u8;
+        serialized[1..].copy_from_slice(&compact_signature);
+
+        Ok(STANDARD.encode(serialized))
+    }
+
+    /// Signs a message with the private key deriving one of the account's
+    /// addresses, following BIP-322 simple signature format. The returned
+    /// signature is base64-encoded and can be checked with
+    /// [`crate::message::verify_message`].
+    ///
+    /// # Notes
+    ///
+    /// Only native segwit and taproot accounts are supported.
+    pub async fn sign_message(&self, message: &str, address: &str) -> Result<String, Error> {
+        let (address, private_key, merkle_root) = self.get_address_private_key(address).await?;
+
+        crate::message::sign_message(message, &address, &private_key, merkle_root)
+    }
+
+    /// Returns the private key deriving the provided address, after checking
+    /// that it belongs to the account, along with the merkle root of the
+    /// script tree taproot addresses commit to, if any.
+    async fn get_address_private_key(&self, address: &str) -> Result<(Address, SecretKey, Option<TapNodeHash>), Error> {
+        // Multisig addresses aren't controlled by a single key
+        if self.multisig_descriptors.is_some() {
+            return Err(Error::MessageSigningNotSupported);
+        }
+
+        let wallet_lock = self.get_wallet().await;
+        let address = Address::from_str(address)?.require_network(wallet_lock.network())?;
+
+        let (keychain, index) = wallet_lock
+            .derivation_of_spk(address.script_pubkey())
+            .ok_or_else(|| Error::AddressNotOwned(address.to_string()))?;
+
+        // Address is derived from the account key, taproot leaves keys can't sign for it
+        let descriptor = wallet_lock
+            .public_descriptor(keychain)
+            .at_derivation_index(index)
+            .map_err(|e| Error::InvalidDescriptor(e.to_string()))?;
+        let mut address_key = None;
+        let mut merkle_root = None;
+        match &descriptor {
+            miniscript::Descriptor::Tr(tr) => {
+                address_key = Some(tr.internal_key().clone());
+                merkle_root = tr.spend_info().merkle_root();
+            }
+            _ => {
+                descriptor.for_each_key(|key| {
+                    address_key.get_or_insert_with(|| key.clone());
+                    true
+                });
+            }
+        }
+
+        let secp = Secp256k1::new();
+        let address_public_key = address_key
+            .ok_or_else(|| Error::InvalidDescriptor("descriptor has no key".to_string()))?
+            .derive_public_key(&secp)
+            .map_err(|e| Error::InvalidDescriptor(e.to_string()))?;
+
+        let private_key = wallet_lock
+            .get_signers(keychain)
+            .signers()
+            .iter()
+            .filter_map(|signer| match signer.descriptor_secret_key() {
+                Some(DescriptorSecretKey::XPrv(descriptor_xkey)) => Some(descriptor_xkey),
+                _ => None,
+            })
+            .filter_map(|descriptor_xkey| {
+                let path = descriptor_xkey.derivation_path.child(ChildNumber::Normal { index });
+                descriptor_xkey.xkey.derive_priv(&secp, &path).ok()
+            })
+            .map(|xprv| xprv.private_key)
+            .find(|private_key| private_key.public_key(&secp) == address_public_key.inner)
+            .ok_or(Error::WatchOnlyCannotSign)?;
+
+        Ok((address, private_key, merkle_root))
+    }
+
+    /// Returns the maximum gap size `Some(u32)` from the wallet's outputs for a specific keychain,
+    /// or `None` if no outputs are found for the given keychain.
+    /// # Parameters
+    ///
+    /// - `keychain`: The type of keychain (e.g., external or internal) to filter the outputs by.
+    pub async fn get_maximum_gap_size(&self, keychain: KeychainKind) -> Result<Option<u32>, Error> {
+        let mut used_indices: Vec<u32> = self
+            .get_wallet()
+            .await
+            .list_output()
+            .into_iter()
+            .filter(|output| output.keychain == keychain)
+            .map(|output| output.derivation_index)
+            .collect();
+
+        if used_indices.len() == 0 {
+            // no index was used, return None
+            return Ok(None);
+        } else if used_indices.len() == 1 {
+            // only 1 address was used, return the address index directly
+            // for example, used_indices = [33]
+            return Ok(Some(used_indices[0]));
+        } else {
+            // calculate the maximum gap between indices and return it
+            // for example, used_indices = [33, 18, 100]
+            used_indices.sort();
+            // after sorted, used_indices = [18, 33, 100]
+
+            // add 0 at first element, so we can get correct gap for first used index
+            // used_indices will become [0, 18, 33, 100]
+            used_indices.insert(0, 0);
+
+            let max_gap = used_indices.windows(2).map(|indices| indices[1] - indices[0]).max();
+            return Ok(max_gap);
+        }
+    }
+
+    /// Returns the stop gap range
+    pub fn get_stop_gap_range(&self, max_gap: u32) -> Result<u32, Error> {
+        let ranges = vec![20, 50, 100, 200, 500];
+
+        match ranges.binary_search(&max_gap) {
+            Ok(index) => Ok(ranges[index]),
+            Err(index) => {
+                if index >= ranges.len() {
+                    Ok(*ranges.last().unwrap_or(&500))
+                } else {
+                    Ok(ranges[index])
+                }
+            }
+        }
+    }
+
+    /// Returns a bitcoin uri as defined in https://bips.dev/21/
+    pub async fn get_bitcoin_uri(
+        &mut self,
+        amount: Option<u64>,
+        label: Option<String>,
+        message: Option<String>,
+    ) -> Result<PaymentLink, Error> {
+        let address = self.get_next_receive_address().await?;
+
+        PaymentLink::new_bitcoin_uri(address.address, amount, label, message)
+    }
+
+    /// Returns a paginated list of transactions.
+    ///
+    /// # Notes
+    ///
+    /// Returned transaction are simple ones with only amount value, txid,
+    /// confirmation time and fees value. For more details, `get_transaction`
+    /// can be called with txid
+    pub async fn get_transactions(
+        &self,
+        pagination: Pagination,
+        sort: Option<SortOrder>,
+    ) -> Result<Vec<TransactionDetails>, Error> {
+        let wallet_lock = self.get_wallet().await;
+        let transactions = wallet_lock.transactions().collect::<Vec<_>>();
+
+        // We first need to sort transactions by their time (last_seen for unconfirmed
+        // ones and confirmation_time for confirmed one) The collection that
+        // happen here might be consuming, maybe later we need to rework this part
+        let transactions = transactions
+            .into_iter()
+            .map(|tx| tx.to_transaction_details((&wallet_lock, (self.get_derivation_path()))))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(sort_and_paginate_txs(transactions, pagination, sort))
+    }
+
+    /// Returns a cursor over the account's transactions, to pull them page
+    /// by page instead of converting the whole history as
+    /// [`Account::get_transactions`] does.
+    pub async fn transactions_iter(&self, sort: Option<SortOrder>) -> TransactionsCursor<P> {
+        TransactionsCursor::new(self.wallet.clone(), self.get_derivation_path(), sort).await
+    }
+
+    /// Returns a single address if found in the graph.
+    ///
+    /// # Notes
+    ///
+    /// The returned address detail contains balance and transactions
+    /// with output to the address, in addition to index and serialised
+    /// address. It can then be used to build an address list with enhanced
+    /// details
+    pub async fn get_address(
+        &self,
+        network: Network,
+        address_str: String,
+        client: Arc<BlockchainClient>,
+        sync: bool,
+    ) -> Result<Option<AddressDetails>, Error> {
+        let spk: bitcoin::ScriptBuf = Address::from_str(&address_str)?
+            .require_network(network.into())?
+            .script_pubkey();
+
+        let wallet_lock = self.get_wallet().await;
+
+        if let Some((keychain, spk_index)) = wallet_lock.derivation_of_spk(spk.clone()) {
+            let update = {
+                if sync {
+                    Some(client.sync_spks(&wallet_lock, vec![spk]).await?)
+                } else {
+                    None
+                }
+            };
+
+            if let Some(update) = update {
+                self.apply_update(update).await?;
+            }
+
+            let wallet_lock = self.get_wallet().await;
+
+            let outpoints = wallet_lock.outpoints_from_spk_index(keychain, spk_index);
+            let spk_balance = wallet_lock.tx_graph().balance(
+                wallet_lock.local_chain(),
+                wallet_lock.local_chain().tip().block_id(),
+                outpoints,
+                |_, _| false,
+            );
+
+            let transactions = wallet_lock
+                .outpoints_from_spk_index(keychain, spk_index)
+                .filter_map(|(_, op)| wallet_lock.tx_graph().get_tx_node(op.txid))
+                .map(|tx_node| tx_node.to_transaction_details((&wallet_lock, self.get_derivation_path())))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let address_str = wallet_lock.peek_address(keychain, spk_index).address.to_string();
+            return Ok(Some(AddressDetails {
+                index: spk_index,
+                address: address_str,
+                balance: spk_balance,
+                transactions: transactions,
+                keychain: keychain,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    /// Syncs only the provided scripts and applies the resulting update. This
+    /// is much cheaper than a full or partial sync when we only expect
+    /// activity on a few addresses, e.g. right after revealing a receive
+    /// address to check for an incoming payment
+    pub async fn sync_scripts(
+        &self,
+        client: Arc<BlockchainClient>,
+        scripts: Vec<ScriptBuf>,
+    ) -> Result<SyncReport, Error> {
+        let update = {
+            let wallet_lock = self.get_wallet().await;
+            client.sync_spks(&wallet_lock, scripts).await?
+        };
+
+        self.apply_update(update).await
+    }
+
+    /// Returns the total amount (sats) ever received on an address of the
+    /// account, summing every output paying to it, spent or not. Useful to
+    /// reconcile invoices paid in several times.
+    pub async fn get_received_on_address(
+        &self,
+        network: Network,
+        address_str: String,
+        client: Arc<BlockchainClient>,
+    ) -> Result<u64, Error> {
+        let spk = Address::from_str(&address_str)?
+            .require_network(network.into())?
+            .script_pubkey();
+
+        if !self.get_wallet().await.is_mine(spk.clone()) {
+            return Err(Error::AddressNotOwned(address_str));
+        }
+
+        let transactions = client.inner().scripthash_api_txs(&spk).await?;
+
+        let mut received = 0;
+        for api_tx in transactions {
+            let tx = bitcoin::Transaction::try_from(api_tx)?;
+
+            received += tx
+                .output
+                .iter()
+                .filter(|output| output.script_pubkey == spk)
+                .map(|output| output.value.to_sat())
+                .sum::<u64>();
+        }
+
+        Ok(received)
+    }
+
+    /// Returns a paginated list of addresses.
+    ///
+    /// # Notes
+    ///
+    /// Each returned address detail contains balance and transactions
+    /// with output to the address, in addition to index and serialised
+    /// address. It can then be used to build an address list with enhanced
+    /// details
+    pub async fn get_addresses(
+        &self,
+        pagination: Pagination,
+        client: Arc<BlockchainClient>,
+        keychain: KeychainKind,
+        force_sync: bool,
+    ) -> Result<Vec<AddressDetails>, Error> {
+        let (skip, take) = (pagination.skip as u32, pagination.take as u32);
+        let spks_range = skip..(skip + take - 1);
+
+        // We need to reveal addresses to be able to sync them
+        let mut wallet_lock = self.get_mutable_wallet().await;
+        let _ = wallet_lock.reveal_addresses_to(keychain, spks_range.end);
+        drop(wallet_lock);
+
+        let update = {
+            let wallet_lock = self.get_wallet().await;
+
+            let spks = spks_range
+                .clone()
+                .filter_map(|index| wallet_lock.spk_index().spk_at_index(keychain, index))
+                .collect::<Vec<_>>();
+
+            // Sync missing spks (or all if forced)
+            let spks_to_sync = if force_sync {
+                spks
+            } else {
+                client.inner().filter_already_fetched(spks).await
+            };
+
+            if !spks_to_sync.is_empty() {
+                Some(client.sync_spks(&wallet_lock, spks_to_sync).await?)
+            } else {
+                None
+            }
+        };
+
+        if let Some(update) = update {
+            self.apply_update(update).await?;
+        }
+
+        let wallet_lock = self.get_wallet().await;
+
+        // Find tx data from spk
+        let mut address_details = Vec::new();
+        for spk_index in spks_range {
+            let outpoints = wallet_lock.outpoints_from_spk_index(keychain, spk_index);
+            let spk_balance = wallet_lock.tx_graph().balance(
+                wallet_lock.local_chain(),
+                wallet_lock.local_chain().tip().block_id(),
+                outpoints,
+                |_, _| false,
+            );
+
+            let transactions = wallet_lock
+                .outpoints_from_spk_index(keychain, spk_index)
+                .filter_map(|(_, op)| wallet_lock.tx_graph().get_tx_node(op.txid))
+                .map(|tx_node| tx_node.to_transaction_details((&wallet_lock, self.get_derivation_path())))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let address_str = wallet_lock.peek_address(keychain, spk_index).address.to_string();
+
+            address_details.push(AddressDetails {
+                index: spk_index,
+                address: address_str,
+                balance: spk_balance,
+                transactions: transactions,
+                keychain: keychain,
+            });
+        }
+
+        Ok(address_details)
+    }
+
+    /// Given a txid, returns a complete transaction
+    ///
+    /// When the transaction isn't in the account's wallet yet, e.g. because
+    /// it wasn't synced, it is fetched from the provided blockchain client if
+    /// any. It is then only returned if it sends from or to the account, and
+    /// unconfirmed ones are cached in the wallet until the next sync.
+    pub async fn get_transaction(
+        &self,
+        txid: String,
+        client: Option<Arc<BlockchainClient>>,
+    ) -> Result<TransactionDetails, Error> {
+        let txid = parse_txid(&txid)?;
+
+        {
+            let wallet_lock = self.get_wallet().await;
+            if let Some(tx) = wallet_lock.transactions().find(|tx| tx.tx_node.compute_txid() == txid) {
+                return tx.to_transaction_details((&wallet_lock, self.get_derivation_path()));
+            }
+        }
+
+        let client = client.ok_or(Error::TransactionNotFound)?;
+        let api_tx = client
+            .inner()
+            .get_api_tx_info(&txid)
+            .await?
+            .ok_or(Error::TransactionNotFound)?;
+        let tip_height = client.inner().get_height().await?;
+        let tx = Transaction::try_from(api_tx.clone())?;
+
+        let details = {
+            let wallet_lock = self.get_wallet().await;
+            TransactionDetails::from_api_tx_with_wallet(
+                api_tx,
+                &wallet_lock,
+                self.get_derivation_path(),
+                Some(tip_height),
+            )?
+        };
+
+        if details.sent == 0 && details.received == 0 {
+            return Err(Error::TransactionNotFound);
+        }
+
+        // Confirmed transactions can't be anchored until their block is synced
+        if let TransactionTime::Unconfirmed { last_seen } = details.time {
+            let mut wallet_lock = self.get_mutable_wallet().await;
+            wallet_lock.apply_unconfirmed_txs([(tx, last_seen)]);
+
+            self.persist(wallet_lock).await?;
+        }
+
+        Ok(details)
+    }
+
+    /// Given a mutable reference to a PSBT, and sign options, tries to sign
+    /// inputs elligible
+    pub async fn sign(&self, psbt: &mut BdkPsbt, sign_options: Option<SignOptions>) -> Result<(), Error> {
+        if self.is_watch_only().await {
+            return Err(Error::WatchOnlyCannotSign);
+        }
+
+        let mut sign_options = sign_options.unwrap_or_default();
+        // Script paths of imported tap trees are only watched
+        if self.tap_tree_descriptors.is_some() {
+            sign_options.tap_leaves_options = TapLeavesOptions::None;
+        }
+
+        self.get_wallet().await.sign(psbt, sign_options)?;
+
+        Ok(())
+    }
+
+    /// Returns whether or not the account's wallet has already been synced at
+    /// least once
+    pub async fn has_sync_data(&self) -> bool {
+        let wallet_lock = self.get_wallet().await;
+        wallet_lock.latest_checkpoint().hash() != genesis_block(wallet_lock.network()).block_hash()
+    }
+
+    pub async fn bump_transactions_fees(&self, txid: String, fees: u64) -> Result<Psbt, Error> {
+        let mut wallet_lock: RwLockWriteGuard<'_, PersistedWallet<P>> = self.get_mutable_wallet().await;
+        let mut fee_bump_tx = wallet_lock.build_fee_bump(parse_txid(&txid)?)?;
+
+        fee_bump_tx.fee_absolute(Amount::from_sat(fees));
+
+        let psbt = fee_bump_tx.finish()?;
+
+        Ok(psbt.into())
+    }
+
+    /// Applies a sync update to the account's wallet and persists it.
+    /// Returns a report of the transactions that were added, confirmed or
+    /// reorged by the update
+    pub async fn apply_update(&self, update: impl Into<Update>) -> Result<SyncReport, Error> {
+        let mut wallet_lock = self.get_mutable_wallet().await;
+
+        let previous_status = Self::confirmation_status(&wallet_lock);
+        wallet_lock.apply_update_at(update, now().as_secs())?;
+
+        Self::restore_reorged_txs(&mut wallet_lock, &previous_status);
+
+        let report = SyncReport::from_confirmation_status(
+            &previous_status,
+            &Self::confirmation_status(&wallet_lock),
+            wallet_lock.latest_checkpoint().height(),
+        );
+
+        self.persist(wallet_lock).await?;
+
+        Ok(report)
+    }
+
+    /// Exports the account's synced state (chain, transactions and revealed
+    /// addresses) as a snapshot that can later be imported with
+    /// [`Account::import_snapshot`], e.g. to reopen the account offline
+    /// without a full rescan
+    pub async fn export_snapshot(&self) -> Result<Vec<u8>, Error> {
+        let wallet_lock = self.get_wallet().await;
+
+        let changeset = ChangeSet {
+            descriptor: Some(wallet_lock.public_descriptor(KeychainKind::External).clone()),
+            change_descriptor: Some(wallet_lock.public_descriptor(KeychainKind::Internal).clone()),
+            network: Some(wallet_lock.network()),
+            local_chain: wallet_lock.local_chain().initial_changeset(),
+            tx_graph: wallet_lock.tx_graph().initial_changeset(),
+            indexer: wallet_lock.spk_index().initial_changeset(),
+        };
+
+        serde_json::to_vec(&changeset).map_err(|e| Error::InvalidSnapshot(e.to_string()))
+    }
+
+    /// Restores a snapshot exported with [`Account::export_snapshot`] into
+    /// the account's wallet and persists it. The snapshot must have been
+    /// exported from an account with the same descriptors.
+    pub async fn import_snapshot(&self, snapshot: &[u8]) -> Result<SyncReport, Error> {
+        let changeset =
+            serde_json::from_slice::<ChangeSet>(snapshot).map_err(|e| Error::InvalidSnapshot(e.to_string()))?;
+
+        let update = {
+            let wallet_lock = self.get_wallet().await;
+
+            if changeset.descriptor.as_ref() != Some(wallet_lock.public_descriptor(KeychainKind::External))
+                || changeset.change_descriptor.as_ref() != Some(wallet_lock.public_descriptor(KeychainKind::Internal))
+            {
+                return Err(Error::InvalidSnapshot(
+                    "snapshot was exported from another account".to_string(),
+                ));
+            }
+
+            let last_active_indices = [KeychainKind::External, KeychainKind::Internal]
+                .into_iter()
+                .filter_map(|keychain| {
+                    let descriptor_id = wallet_lock.public_descriptor(keychain).descriptor_id();
+                    changeset
+                        .indexer
+                        .last_revealed
+                        .get(&descriptor_id)
+                        .map(|index| (keychain, *index))
+                })
+                .collect();
+
+            let chain = CheckPoint::from_block_ids(
+                changeset
+                    .local_chain
+                    .blocks
+                    .into_iter()
+                    .filter_map(|(height, hash)| hash.map(|hash| BlockId { height, hash })),
+            )
+            .map_err(|_| Error::InvalidSnapshot("snapshot has no chain".to_string()))?;
+
+            let mut tx_update = TxUpdate::default();
+            tx_update.txs = changeset.tx_graph.txs.into_iter().collect();
+            tx_update.txouts = changeset.tx_graph.txouts;
+            tx_update.anchors = changeset.tx_graph.anchors;
+            tx_update.seen_ats = changeset.tx_graph.last_seen.into_iter().collect();
+
+            Update {
+                last_active_indices,
+                tx_update,
+                chain: Some(chain),
+            }
+        };
+
+        self.apply_update(update).await
+    }
+
+    /// Returns the account's synced chain tip and revealed keychain indices,
+    /// to be restored with [`Account::restore_checkpoint`]
+    pub async fn checkpoint(&self) -> Checkpoint {
+        let wallet_lock = self.get_wallet().await;
+        let tip = wallet_lock.latest_checkpoint().block_id();
+
+        Checkpoint {
+            tip_height: tip.height,
+            tip_hash: tip.hash,
+            external_index: wallet_lock.spk_index().last_revealed_index(KeychainKind::External),
+            internal_index: wallet_lock.spk_index().last_revealed_index(KeychainKind::Internal),
+        }
+    }
+
+    /// Restores a checkpoint created with [`Account::checkpoint`] and
+    /// persists it. Following partial syncs then track the revealed addresses
+    /// from the checkpoint's tip.
+    ///
+    /// # Notes
+    ///
+    /// Transactions aren't part of the checkpoint, the ones from before it are
+    /// only fetched back as partial syncs query the revealed addresses.
+    pub async fn restore_checkpoint(&self, checkpoint: Checkpoint) -> Result<(), Error> {
+        let chain = self.get_wallet().await.latest_checkpoint().insert(BlockId {
+            height: checkpoint.tip_height,
+            hash: checkpoint.tip_hash,
+        });
+
+        let last_active_indices = [
+            (KeychainKind::External, checkpoint.external_index),
+            (KeychainKind::Internal, checkpoint.internal_index),
+        ]
+        .into_iter()
+        .filter_map(|(keychain, index)| index.map(|index| (keychain, index)))
+        .collect();
+
+        self.apply_update(Update {
+            last_active_indices,
+            tx_update: TxUpdate::default(),
+            chain: Some(chain),
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    fn confirmation_status(wallet: &BdkWallet) -> BTreeMap<Txid, bool> {
+        wallet
+            .transactions()
+            .map(|tx| (tx.tx_node.compute_txid(), tx.chain_position.is_confirmed()))
+            .collect()
+    }
+
+    /// When a block gets reorged out of the best chain, transactions anchored
+    /// in it are not confirmed anymore but, since they were never seen
+    /// unconfirmed, they don't have any last seen time. We mark them as seen
+    /// now so that they are tracked back as unconfirmed until a later sync
+    /// finds them mined again.
+    fn restore_reorged_txs(wallet: &mut BdkWallet, previous_status: &BTreeMap<Txid, bool>) {
+        let reorged_txs = previous_status
+            .iter()
+            .filter(|(txid, confirmed)| {
+                **confirmed && !wallet.get_tx(**txid).is_some_and(|tx| tx.chain_position.is_confirmed())
+            })
+            .filter_map(|(txid, _)| wallet.tx_graph().get_tx(*txid))
+            .collect::<Vec<_>>();
+
+        let seen_at = now().as_secs();
+        wallet.apply_unconfirmed_txs(reorged_txs.into_iter().map(|tx| (tx, seen_at)));
+    }
+
+    async fn persist(&self, mut wallet_lock: RwLockWriteGuard<'_, PersistedWallet<P>>) -> Result<(), Error> {
+        let mut persister = self.persister_connector.connect();
+
+        wallet_lock.persist(&mut persister).map_err(|_e| Error::PersistError)?;
+        drop(wallet_lock);
+
+        Ok(())
+    }
+
+    pub fn clear_store(&self) -> Result<(), Error> {
+        let mut persister = self.persister_connector.connect();
+
+        P::persist(&mut persister, &ChangeSet::default()).map_err(|_e| Error::PersistError)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        str::FromStr,
+        sync::{Arc, Mutex},
+    };
+
+    use andromeda_api::{
+        address::scripthash_for_script,
+        bitcoin_address::ApiWalletBitcoinAddress,
+        tests::utils::{common_api_client, setup_test_connection},
+        BASE_WALLET_API_V1,
+    };
+    use andromeda_common::Network;
+    use bdk_wallet::{
+        bitcoin::{
+            absolute::LockTime,
+            bip32::{DerivationPath, Xpriv, Xpub},
+            secp256k1::Secp256k1,
+            transaction::Version,
+            Address, AddressType, Amount, BlockHash, NetworkKind, OutPoint, Psbt, Sequence, Transaction, TxIn, TxOut,
+            Txid, Witness,
+        },
+        chain::{BlockId, ChainPosition, ConfirmationBlockTime, TxUpdate},
+        serde_json, AddressInfo, KeychainKind, SignOptions, Update,
+    };
+    use bitcoin::{
+        base64::{engine::general_purpose::STANDARD, Engine},
+        consensus::encode::deserialize_hex,
+        secp256k1::ecdsa::{RecoverableSignature, RecoveryId},
+        sign_message::{signed_msg_hash, MessageSignature},
+        ScriptBuf,
+    };
+    use futures::future::AbortHandle;
+    use miniscript::{descriptor::DescriptorSecretKey, Descriptor, DescriptorPublicKey};
+    use wiremock::{
+        matchers::{body_string_contains, method, path, path_regex},
+        Mock, MockServer, Request, Respond, ResponseTemplate,
+    };
+
+    use super::{Account, AccountConfig, Checkpoint, ScriptType, SpendPolicy, DEFAULT_GAP_LIMIT};
+    use crate::{
+        blockchain_client::{BlockchainClient, SyncScope},
+        error::Error,
+        message::verify_message,
+        mnemonic::Mnemonic,
+        proof_of_reserves::verify_proof_of_reserves,
+        read_mock_file,
+        storage::MemoryPersisted,
+        tests::utils::{apply_unconfirmed_txs, foreign_outpoint, funding_tx, receive_unconfirmed_tx},
+        transaction_builder::TxBuilder,
+        transactions::{Pagination, TransactionDetails},
+        utils::{SortOrder, UtxoSort},
+    };
+
+    fn set_test_account(script_type: ScriptType, derivation_path: &str) -> Account<MemoryPersisted, MemoryPersisted> {
+        let network = NetworkKind::Test;
+        let mnemonic = Mnemonic::from_string("category law logic swear involve banner pink room diesel fragile sunset remove whale lounge captain code hobby lesson material current moment funny vast fade".to_string()).unwrap();
+        let master_secret_key = Xpriv::new_master(network, &mnemonic.inner().to_seed("")).unwrap();
+
+        let derivation_path = DerivationPath::from_str(derivation_path).unwrap();
+
+        Account::new(
+            master_secret_key,
+            Network::Testnet,
+            script_type,
+            derivation_path,
+            MemoryPersisted {},
+        )
+        .unwrap()
+    }
+
+    fn set_test_account_regtest(
+        script_type: ScriptType,
+        derivation_path: &str,
+    ) -> Account<MemoryPersisted, MemoryPersisted> {
+        let network = NetworkKind::Test;
+        let mnemonic = Mnemonic::from_string(
+            "onion ancient develop team busy purchase salmon robust danger wheat rich empower".to_string(),
+        )
+        .unwrap();
+        let master_secret_key = Xpriv::new_master(network, &mnemonic.inner().to_seed("")).unwrap();
+
+        let derivation_path = DerivationPath::from_str(derivation_path).unwrap();
+
+        Account::new(
+            master_secret_key,
+            Network::Regtest,
+            script_type,
+            derivation_path,
+            MemoryPersisted {},
+        )
+        .unwrap()
+    }
+
+    fn set_test_account_regtest2(
+        script_type: ScriptType,
+        derivation_path: &str,
+    ) -> Account<MemoryPersisted, MemoryPersisted> {
+        let network = NetworkKind::Test;
+        let mnemonic = Mnemonic::from_string(
+            "remove over athlete patient priority unable memory axis sunset home balance sausage".to_string(),
+        )
+        .unwrap();
+        let master_secret_key = Xpriv::new_master(network, &mnemonic.inner().to_seed("")).unwrap();
+
+        let derivation_path = DerivationPath::from_str(derivation_path).unwrap();
+
+        Account::new(
+            master_secret_key,
+            Network::Regtest,
+            script_type,
+            derivation_path,
+            MemoryPersisted {},
+        )
+        .unwrap()
+    }
+
+    fn set_test_account_regtest3(
+        script_type: ScriptType,
+        derivation_path: &str,
+    ) -> Account<MemoryPersisted, MemoryPersisted> {
+        let network = NetworkKind::Test;
+        let mnemonic = Mnemonic::from_string(
+            "submit exhibit banner enter situate exact north fog era family south style".to_string(),
+        )
+        .unwrap();
+        let master_secret_key = Xpriv::new_master(network, &mnemonic.inner().to_seed("")).unwrap();
+
+        let derivation_path = DerivationPath::from_str(derivation_path).unwrap();
+
+        Account::new(
+            master_secret_key,
+            Network::Regtest,
+            script_type,
+            derivation_path,
+            MemoryPersisted {},
+        )
+        .unwrap()
+    }
+
+    fn set_test_account_for_mainnet(
+        script_type: ScriptType,
+        derivation_path: &str,
     ) -> Account<MemoryPersisted, MemoryPersisted> {
         let network = NetworkKind::Main;
         let mnemonic = Mnemonic::from_string(
-            "sense giggle bulb easily canal since toilet diagram deer able inflict bacon".to_string(),
+            "sense giggle bulb easily canal since toilet diagram deer able inflict bacon".to_string(),
+        )
+        .unwrap();
+        let master_secret_key = Xpriv::new_master(network, &mnemonic.inner().to_seed("")).unwrap();
+
+        let derivation_path = DerivationPath::from_str(derivation_path).unwrap();
+
+        Account::new(
+            master_secret_key,
+            Network::Bitcoin,
+            script_type,
+            derivation_path,
+            MemoryPersisted {},
+        )
+        .unwrap()
+    }
+
+    async fn get_mock_server_for_mainnet() -> MockServer {
+        let mock_server = MockServer::start().await;
+
+        let req_path_blocks: String = format!("{}/blocks", BASE_WALLET_API_V1);
+
+        let response_contents = read_mock_file!("get_blocks_body_mainnet");
+        let response = ResponseTemplate::new(200).set_body_string(response_contents);
+        Mock::given(method("GET"))
+            .and(path(req_path_blocks.clone()))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+
+        let req_path: String = format!("{}/addresses/scripthashes/transactions", BASE_WALLET_API_V1);
+
+        let response_contents1 = read_mock_file!("get_scripthashes_transactions_body_mainnet_1");
+        let response1 = ResponseTemplate::new(200).set_body_string(response_contents1);
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .and(body_string_contains(
+                "5433431edc5b618c67ddc237ea86a996f32004022daa49bd40dc607052e892e0",
+            ))
+            .respond_with(response1)
+            .mount(&mock_server)
+            .await;
+
+        let response_contents2 = read_mock_file!("get_scripthashes_transactions_body_mainnet_2");
+        let response2 = ResponseTemplate::new(200).set_body_string(response_contents2);
+
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .and(body_string_contains(
+                "364923b99c5e330ddf19692077b8d69700e71083542ef0a6ebc9e657d5e1ef56",
+            ))
+            .respond_with(response2)
+            .mount(&mock_server)
+            .await;
+
+        let response_contents3 = read_mock_file!("get_scripthashes_transactions_body_mainnet_3");
+        let response3 = ResponseTemplate::new(200).set_body_string(response_contents3);
+
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .and(body_string_contains(
+                "032f964badcda0acf21c0b7642fd7a94056e3cfa49a38ed92d9c2f46acba4417",
+            ))
+            .respond_with(response3)
+            .mount(&mock_server)
+            .await;
+
+        let response_contents4 = read_mock_file!("get_scripthashes_transactions_body_mainnet_4");
+        let response4 = ResponseTemplate::new(200).set_body_string(response_contents4);
+
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .and(body_string_contains(
+                "278a61a1f06888fab709301b570b9c3e4b9efdc54a1f26b9bfbad34b508e8433",
+            ))
+            .respond_with(response4)
+            .mount(&mock_server)
+            .await;
+
+        let block_hash_data = vec![
+            (0, "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f"),
+            (
+                869089,
+                "000000000000000000015c4d376f65d85378b3b6f5eaae656078dc319dd7fcdf",
+            ),
+            (
+                869090,
+                "000000000000000000002067d2cd097be40e1d7d1876d38b8025eb1bb2f28fc4",
+            ),
+            (
+                869434,
+                "00000000000000000000556ef62a310d7230ec72afc2a577b953072320f03c5b",
+            ),
+            (
+                871611,
+                "00000000000000000001f9a7c7c50359f190a71fcd658f5efe02808adfa8027b",
+            ),
+            (
+                872365,
+                "000000000000000000001d74687b6170827dad9bf59c4d612db3d7cd73009a5b",
+            ),
+            (
+                872366,
+                "000000000000000000018700dd168b5672808addc35d6aa015eb817e80a00f2d",
+            ),
+        ];
+        for (block_height, block_hash) in block_hash_data {
+            Mock::given(method("GET"))
+                .and(path_regex(format!(".*blocks/height/{}/hash", block_height)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "Code": 1000,
+                    "BlockHash": block_hash,
+                })))
+                .mount(&mock_server)
+                .await;
+        }
+        return mock_server;
+    }
+
+    async fn get_mock_server_for_regtest() -> MockServer {
+        let mock_server = MockServer::start().await;
+
+        let req_path_blocks: String = format!("{}/blocks", BASE_WALLET_API_V1);
+
+        let response_contents = read_mock_file!("get_blocks_body");
+        let response = ResponseTemplate::new(200).set_body_string(response_contents);
+        Mock::given(method("GET"))
+            .and(path(req_path_blocks.clone()))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+
+        let req_path: String = format!("{}/addresses/scripthashes/transactions", BASE_WALLET_API_V1);
+
+        let response_contents1 = read_mock_file!("get_scripthashes_transactions_body_1");
+        let response1 = ResponseTemplate::new(200).set_body_string(response_contents1);
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .and(body_string_contains(
+                "89a10f34b9e0ad8b770c381d5bbb1f566124d3164781f41fb98218d1362069ec",
+            ))
+            .respond_with(response1)
+            .mount(&mock_server)
+            .await;
+
+        let response_contents2 = read_mock_file!("get_scripthashes_transactions_body_2");
+        let response2 = ResponseTemplate::new(200).set_body_string(response_contents2);
+
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .and(body_string_contains(
+                "b6c3616a787f87ed96b70770d84d45acf637ed3ad6f2706b2dfc282cc3ba4c05",
+            ))
+            .respond_with(response2)
+            .mount(&mock_server)
+            .await;
+
+        let response_contents3 = read_mock_file!("get_scripthashes_transactions_body_3");
+        let response3 = ResponseTemplate::new(200).set_body_string(response_contents3);
+
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .and(body_string_contains(
+                "5eac955f250ff14fd8c61e29e9531bc3e49d69038981a1344e88b985bd200a29",
+            ))
+            .respond_with(response3)
+            .mount(&mock_server)
+            .await;
+
+        let response_contents_block_hash = read_mock_file!("get_block_hash_body");
+        let response_block_hash = ResponseTemplate::new(200).set_body_string(response_contents_block_hash);
+
+        Mock::given(method("GET"))
+            .and(path_regex(".*/height/.*"))
+            .respond_with(response_block_hash)
+            .mount(&mock_server)
+            .await;
+
+        return mock_server;
+    }
+
+    /// Mock server serving regtest blocks and an empty history for every
+    /// script, so that syncs scan up to their stop gap
+    async fn get_mock_server_with_empty_history() -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/blocks", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_string(read_mock_file!("get_blocks_body")))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(".*/height/.*"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(read_mock_file!("get_block_hash_body")))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "{}/addresses/scripthashes/transactions",
+                BASE_WALLET_API_V1
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Code": 1000,
+                "Transactions": {}
+            })))
+            .mount(&mock_server)
+            .await;
+
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_get_wallet() {
+        let account = set_test_account(ScriptType::Legacy, "m/44'/1'/0'");
+        let wallet = account.get_wallet().await;
+        assert!(wallet.balance().total().to_sat() == 0);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_get_address_sync_true() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let network = Network::Regtest;
+        let address_str = "bcrt1q4zpmdp77e9ff4ls8ajgqapdhgqutrkcpqpzcqw".to_string();
+
+        let api_client = common_api_client().await;
+        let client = BlockchainClient::new(api_client.as_ref().clone());
+        let address_detail = account
+            .get_address(network, address_str.clone(), Arc::new(client.clone()), true)
+            .await
+            .unwrap();
+        assert!(address_detail.is_some());
+        assert!(address_detail.unwrap().balance.confirmed.to_sat() == 0);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_get_address_sync_false() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let network = Network::Regtest;
+        let address_str = "bcrt1q4zpmdp77e9ff4ls8ajgqapdhgqutrkcpqpzcqw".to_string();
+
+        let api_client = common_api_client().await;
+        let client = BlockchainClient::new(api_client.as_ref().clone());
+        let address_detail = account
+            .get_address(network, address_str.clone(), Arc::new(client.clone()), false)
+            .await
+            .unwrap();
+        assert!(address_detail.is_some());
+        assert!(address_detail.unwrap().balance.confirmed.to_sat() == 0);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_get_addresses_sync_false() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let api_client = common_api_client().await;
+        let client = BlockchainClient::new(api_client.as_ref().clone());
+        let pagination = Pagination::new(0, 10);
+        let addresses = account
+            .get_addresses(
+                pagination,
+                Arc::new(client.clone()),
+                bdk_wallet::KeychainKind::Internal,
+                false,
+            )
+            .await
+            .unwrap();
+        assert!(addresses.len() == 9);
+    }
+
+    #[tokio::test]
+    async fn test_get_addresses() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let mock_server = MockServer::start().await;
+
+        let req_path_blocks: String = format!("{}/blocks", BASE_WALLET_API_V1);
+
+        let response_contents = read_mock_file!("get_blocks_body");
+        let response = ResponseTemplate::new(200).set_body_string(response_contents);
+        Mock::given(method("GET"))
+            .and(path(req_path_blocks.clone()))
+            .respond_with(response)
+            .mount(&mock_server)
+            .await;
+
+        let req_path: String = format!("{}/addresses/scripthashes/transactions", BASE_WALLET_API_V1);
+
+        let response_contents1 = read_mock_file!("get_addresses_body_1");
+        let response1 = ResponseTemplate::new(200).set_body_string(response_contents1);
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .and(body_string_contains(
+                "3979339df938c20edd4f7505ba17ded9c999c4901091660072628aa0b69fe004",
+            ))
+            .respond_with(response1)
+            .mount(&mock_server)
+            .await;
+
+        let response_contents_block_hash = read_mock_file!("get_block_hash_body");
+        let response_block_hash = ResponseTemplate::new(200).set_body_string(response_contents_block_hash);
+
+        Mock::given(method("GET"))
+            .and(path_regex(".*/height/.*"))
+            .respond_with(response_block_hash)
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = BlockchainClient::new(api_client.clone());
+        let pagination = Pagination::new(0, 10);
+        let addresses = account
+            .get_addresses(
+                pagination,
+                Arc::new(client.clone()),
+                bdk_wallet::KeychainKind::Internal,
+                false,
+            )
+            .await
+            .unwrap();
+        assert!(addresses.len() == 9);
+    }
+
+    #[tokio::test]
+    async fn test_get_address() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let network = Network::Regtest;
+        let address_str = "bcrt1q4zpmdp77e9ff4ls8ajgqapdhgqutrkcpqpzcqw".to_string();
+
+        let mock_server = MockServer::start().await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = BlockchainClient::new(api_client.clone());
+        let address_detail = account
+            .get_address(network, address_str.clone(), Arc::new(client.clone()), false)
+            .await
+            .unwrap();
+        assert!(address_detail.is_some());
+        assert!(address_detail.unwrap().balance.confirmed.to_sat() == 0);
+    }
+
+    #[test]
+    fn should_check_derivation_path_purpose() {
+        let mnemonic = Mnemonic::from_string("category law logic swear involve banner pink room diesel fragile sunset remove whale lounge captain code hobby lesson material current moment funny vast fade".to_string()).unwrap();
+        let master_secret_key = Xpriv::new_master(NetworkKind::Test, &mnemonic.inner().to_seed("")).unwrap();
+        let derivation_path = DerivationPath::from_str("m/86'/1'/0'").unwrap();
+
+        assert!(Account::<MemoryPersisted, MemoryPersisted>::new(
+            master_secret_key,
+            Network::Testnet,
+            ScriptType::Taproot,
+            derivation_path.clone(),
+            MemoryPersisted {},
+        )
+        .is_ok());
+
+        assert!(matches!(
+            Account::<MemoryPersisted, MemoryPersisted>::new(
+                master_secret_key,
+                Network::Testnet,
+                ScriptType::NativeSegwit,
+                derivation_path.clone(),
+                MemoryPersisted {},
+            ),
+            Err(Error::ScriptTypePathMismatch {
+                script_type: ScriptType::NativeSegwit,
+                ..
+            })
+        ));
+
+        assert!(Account::<MemoryPersisted, MemoryPersisted>::new_allowing_mismatch(
+            master_secret_key,
+            Network::Testnet,
+            ScriptType::NativeSegwit,
+            derivation_path,
+            MemoryPersisted {},
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_public_descriptor() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let external_descriptor = account.public_descriptor(KeychainKind::External).unwrap();
+        assert!(external_descriptor.starts_with("wpkh([a7fd5114/84'/1'/0']tpub"));
+        assert!(external_descriptor.contains("/0/*)"));
+        assert!(!external_descriptor.contains("tprv"));
+
+        let internal_descriptor = account.public_descriptor(KeychainKind::Internal).unwrap();
+        assert!(internal_descriptor.starts_with("wpkh([a7fd5114/84'/1'/0']tpub"));
+        assert!(internal_descriptor.contains("/1/*)"));
+    }
+
+    #[tokio::test]
+    async fn test_account_xpub() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let account_xpub = account.export_account_xpub();
+        assert!(account_xpub.starts_with("[a7fd5114/84'/1'/0']tpub"));
+
+        // Rebuilding a watch-only account from the exported xpub gives the same addresses
+        let xpub = Xpub::from_str(account_xpub.split(']').last().unwrap()).unwrap();
+        let watch_only_account = Account::<MemoryPersisted, MemoryPersisted>::new_watch_only(
+            xpub,
+            Network::Testnet,
+            ScriptType::NativeSegwit,
+            MemoryPersisted {},
+        )
+        .unwrap();
+        assert_eq!(
+            watch_only_account.peek_receive_address(0).await.unwrap().address,
+            account.peek_receive_address(0).await.unwrap().address
+        );
+        assert!(watch_only_account.export_account_xpub().starts_with("tpub"));
+    }
+
+    #[tokio::test]
+    async fn test_account_from_config() {
+        let mnemonic = Mnemonic::from_string("category law logic swear involve banner pink room diesel fragile sunset remove whale lounge captain code hobby lesson material current moment funny vast fade".to_string()).unwrap();
+        let master_secret_key = Xpriv::new_master(NetworkKind::Test, &mnemonic.inner().to_seed("")).unwrap();
+
+        let account = Account::<MemoryPersisted, MemoryPersisted>::new(
+            master_secret_key,
+            Network::Testnet,
+            ScriptType::Taproot,
+            DerivationPath::from_str("m/86'/1'/0'").unwrap(),
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        let configured_account: Account<MemoryPersisted, MemoryPersisted> = AccountConfig::new(master_secret_key)
+            .script_type(ScriptType::Taproot)
+            .derivation_path(DerivationPath::from_str("m/86'/1'/0'").unwrap())
+            .network(Network::Testnet)
+            .gap_limit(30)
+            .storage(MemoryPersisted {})
+            .build()
+            .unwrap();
+
+        assert_eq!(configured_account.get_network(), account.get_network());
+        assert_eq!(configured_account.get_derivation_path(), account.get_derivation_path());
+        assert_eq!(configured_account.export_account_xpub(), account.export_account_xpub());
+        assert_eq!(
+            configured_account.peek_receive_address(0).await.unwrap().address,
+            account.peek_receive_address(0).await.unwrap().address
+        );
+        assert_eq!(configured_account.get_gap_limit(), 30);
+
+        // Network is inferred from the master key and derivation path from the
+        // script type
+        let default_account: Account<MemoryPersisted, MemoryPersisted> = AccountConfig::new(master_secret_key)
+            .script_type(ScriptType::Taproot)
+            .storage(MemoryPersisted {})
+            .build()
+            .unwrap();
+        assert_eq!(default_account.get_network(), Network::Testnet);
+        assert_eq!(default_account.get_derivation_path(), account.get_derivation_path());
+        assert_eq!(default_account.get_gap_limit(), DEFAULT_GAP_LIMIT);
+    }
+
+    #[tokio::test]
+    async fn test_account_from_descriptor() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        // wpkh([a7fd5114/84h/1h/0h]tpub.../0/*), as exported by other wallet softwares
+        let external_descriptor = account.public_descriptor(KeychainKind::External).unwrap();
+        let external_descriptor = external_descriptor.split('#').next().unwrap().replace('\'', "h");
+        assert!(external_descriptor.starts_with("wpkh([a7fd5114/84h/1h/0h]tpub"));
+
+        let imported_account = Account::<MemoryPersisted, MemoryPersisted>::from_descriptor(
+            &external_descriptor,
+            None,
+            Network::Testnet,
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        assert_eq!(
+            imported_account.get_derivation_path(),
+            DerivationPath::from_str("m/84'/1'/0'").unwrap()
+        );
+        assert!(imported_account.is_watch_only().await);
+        assert_eq!(
+            imported_account.peek_receive_address(0).await.unwrap().address,
+            account.peek_receive_address(0).await.unwrap().address
+        );
+        assert_eq!(
+            imported_account.public_descriptor(KeychainKind::Internal).unwrap(),
+            account.public_descriptor(KeychainKind::Internal).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_account_from_multipath_descriptor() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let multipath_descriptor = account.public_multipath_descriptor().unwrap();
+        assert!(multipath_descriptor.starts_with("wpkh([a7fd5114/84'/1'/0']tpub"));
+        assert!(multipath_descriptor.contains("/<0;1>/*)"));
+
+        let imported_account = Account::<MemoryPersisted, MemoryPersisted>::from_descriptor(
+            &multipath_descriptor,
+            None,
+            Network::Testnet,
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        // Multipath form derives the same addresses as the split one
+        let split_account = Account::<MemoryPersisted, MemoryPersisted>::from_descriptor(
+            &account.public_descriptor(KeychainKind::External).unwrap(),
+            Some(&account.public_descriptor(KeychainKind::Internal).unwrap()),
+            Network::Testnet,
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        for keychain in [KeychainKind::External, KeychainKind::Internal] {
+            assert_eq!(
+                imported_account.get_wallet().await.peek_address(keychain, 3).address,
+                split_account.get_wallet().await.peek_address(keychain, 3).address
+            );
+            assert_eq!(
+                imported_account.public_descriptor(keychain).unwrap(),
+                account.public_descriptor(keychain).unwrap()
+            );
+        }
+
+        let result = Account::<MemoryPersisted, MemoryPersisted>::from_descriptor(
+            &multipath_descriptor.replace("<0;1>", "<0;1;2>"),
+            None,
+            Network::Testnet,
+            MemoryPersisted {},
+        );
+        assert!(matches!(result, Err(Error::InvalidDescriptor(_))));
+    }
+
+    #[tokio::test]
+    async fn test_account_from_taproot_descriptor_with_script_tree() {
+        let account = set_test_account(ScriptType::Taproot, "m/86'/1'/0'");
+        let leaf_account = set_test_account(ScriptType::Taproot, "m/86'/1'/1'");
+
+        let external_descriptor = format!(
+            "tr({}/0/*,pk({}/0/*))",
+            account.export_account_xpub(),
+            leaf_account.export_account_xpub()
+        );
+
+        let imported_account = Account::<MemoryPersisted, MemoryPersisted>::from_descriptor(
+            &external_descriptor,
+            None,
+            Network::Testnet,
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        assert_eq!(
+            imported_account.get_derivation_path(),
+            DerivationPath::from_str("m/86'/1'/0'").unwrap()
+        );
+
+        let expected_address = Descriptor::<DescriptorPublicKey>::from_str(&external_descriptor)
+            .unwrap()
+            .at_derivation_index(0)
+            .unwrap()
+            .address(Network::Testnet.into())
+            .unwrap();
+        let address = imported_account.peek_receive_address(0).await.unwrap().address;
+        assert_eq!(address, expected_address);
+        // The script tree commits to the output key
+        assert_ne!(address, account.peek_receive_address(0).await.unwrap().address);
+
+        let public_descriptor = imported_account.public_descriptor(KeychainKind::External).unwrap();
+        assert!(public_descriptor.contains(&leaf_account.export_account_xpub()));
+        assert!(imported_account
+            .public_descriptor(KeychainKind::Internal)
+            .unwrap()
+            .starts_with(&format!("tr({}/1/*,pk(", account.export_account_xpub())));
+    }
+
+    #[tokio::test]
+    async fn test_multipath_descriptor_keeps_keys_shared_by_keychains() {
+        let account = set_test_account(ScriptType::Taproot, "m/86'/1'/0'");
+        let leaf_account = set_test_account(ScriptType::Taproot, "m/86'/1'/1'");
+
+        // Leaf key is the same for both keychains
+        let imported_account = Account::<MemoryPersisted, MemoryPersisted>::from_descriptor(
+            &format!(
+                "tr({}/0/*,pk({}/0/*))",
+                account.export_account_xpub(),
+                leaf_account.export_account_xpub()
+            ),
+            Some(&format!(
+                "tr({}/1/*,pk({}/0/*))",
+                account.export_account_xpub(),
+                leaf_account.export_account_xpub()
+            )),
+            Network::Testnet,
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        let multipath_descriptor = imported_account.public_multipath_descriptor().unwrap();
+        assert!(multipath_descriptor.starts_with(&format!("tr({}/<0;1>/*,pk(", account.export_account_xpub())));
+        assert!(multipath_descriptor.contains(&format!("pk({}/0/*)", leaf_account.export_account_xpub())));
+
+        let reimported_account = Account::<MemoryPersisted, MemoryPersisted>::from_descriptor(
+            &multipath_descriptor,
+            None,
+            Network::Testnet,
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        for keychain in [KeychainKind::External, KeychainKind::Internal] {
+            assert_eq!(
+                reimported_account.get_wallet().await.peek_address(keychain, 3).address,
+                imported_account.get_wallet().await.peek_address(keychain, 3).address
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_account_from_private_descriptor() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let mnemonic = Mnemonic::from_string("category law logic swear involve banner pink room diesel fragile sunset remove whale lounge captain code hobby lesson material current moment funny vast fade".to_string()).unwrap();
+        let master_secret_key = Xpriv::new_master(NetworkKind::Test, &mnemonic.inner().to_seed("")).unwrap();
+        let account_xprv = master_secret_key
+            .derive_priv(&Secp256k1::new(), &DerivationPath::from_str("m/84'/1'/0'").unwrap())
+            .unwrap();
+
+        let imported_account = Account::<MemoryPersisted, MemoryPersisted>::from_descriptor(
+            &format!("wpkh([a7fd5114/84'/1'/0']{}/0/*)", account_xprv),
+            None,
+            Network::Testnet,
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        // Private key is moved to the change keychain along with the public one
+        assert!(!imported_account
+            .get_wallet()
+            .await
+            .get_signers(KeychainKind::Internal)
+            .signers()
+            .is_empty());
+        assert_eq!(
+            imported_account.public_descriptor(KeychainKind::Internal).unwrap(),
+            account.public_descriptor(KeychainKind::Internal).unwrap()
+        );
+        assert_eq!(
+            imported_account
+                .get_address_for_keychain(KeychainKind::Internal, Some(0))
+                .await
+                .address,
+            account
+                .get_address_for_keychain(KeychainKind::Internal, Some(0))
+                .await
+                .address
+        );
+    }
+
+    #[test]
+    fn test_account_from_descriptor_rejects_other_network_keys() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let result = Account::<MemoryPersisted, MemoryPersisted>::from_descriptor(
+            &account.public_descriptor(KeychainKind::External).unwrap(),
+            None,
+            Network::Bitcoin,
+            MemoryPersisted {},
+        );
+        assert!(matches!(result, Err(Error::InvalidDescriptor(_))));
+    }
+
+    #[test]
+    fn test_account_from_descriptor_requires_keychain_level_key() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let account_xpub = account.get_account_xpub();
+
+        // Key path would be lost in the account's descriptors
+        let result = Account::<MemoryPersisted, MemoryPersisted>::from_descriptor(
+            &format!("wpkh([a7fd5114/84'/1'/0']{}/7/0/*)", account_xpub),
+            None,
+            Network::Testnet,
+            MemoryPersisted {},
+        );
+        assert!(matches!(result, Err(Error::InvalidDescriptor(_))));
+    }
+
+    #[tokio::test]
+    async fn test_account_from_watch_only_descriptor() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let watch_only_account = Account::<MemoryPersisted, MemoryPersisted>::new_watch_only(
+            account.get_account_xpub(),
+            Network::Testnet,
+            ScriptType::NativeSegwit,
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        // Watch-only accounts export keys without origin
+        let external_descriptor = watch_only_account.public_descriptor(KeychainKind::External).unwrap();
+        assert!(external_descriptor.starts_with(&format!("wpkh({}/0/*)", watch_only_account.export_account_xpub())));
+
+        let imported_account = Account::<MemoryPersisted, MemoryPersisted>::from_descriptor(
+            &external_descriptor,
+            None,
+            Network::Testnet,
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        assert_eq!(
+            imported_account.get_derivation_path(),
+            watch_only_account.get_derivation_path()
+        );
+        for keychain in [KeychainKind::External, KeychainKind::Internal] {
+            assert_eq!(
+                imported_account.public_descriptor(keychain).unwrap(),
+                watch_only_account.public_descriptor(keychain).unwrap()
+            );
+        }
+        assert_eq!(
+            imported_account.peek_receive_address(0).await.unwrap().address,
+            account.peek_receive_address(0).await.unwrap().address
+        );
+    }
+
+    #[test]
+    fn test_account_from_invalid_descriptor() {
+        let result = Account::<MemoryPersisted, MemoryPersisted>::from_descriptor(
+            "wpkh(invalid)",
+            None,
+            Network::Testnet,
+            MemoryPersisted {},
+        );
+        assert!(matches!(result, Err(Error::InvalidDescriptor(_))));
+    }
+
+    #[tokio::test]
+    async fn test_multisig_account_partially_signs() {
+        let secp = Secp256k1::new();
+        let mnemonic = Mnemonic::from_string(
+            "onion ancient develop team busy purchase salmon robust danger wheat rich empower".to_string(),
         )
         .unwrap();
-        let master_secret_key = Xpriv::new_master(network, &mnemonic.inner().to_seed("")).unwrap();
+        let master_secret_key = Xpriv::new_master(NetworkKind::Test, &mnemonic.inner().to_seed("")).unwrap();
+        let fingerprint = master_secret_key.fingerprint(&secp);
+
+        // Cosigners keys are derived from the same master key for the sake of the test
+        let cosigners = (0..3)
+            .map(|index| {
+                let derivation_path = DerivationPath::from_str(&format!("m/48'/1'/{index}'/2'")).unwrap();
+                let account_xprv = master_secret_key.derive_priv(&secp, &derivation_path).unwrap();
+                (derivation_path, account_xprv)
+            })
+            .collect::<Vec<_>>();
+        let keys = cosigners
+            .iter()
+            .map(|(derivation_path, account_xprv)| {
+                format!(
+                    "[{fingerprint}/{derivation_path}]{}/<0;1>/*",
+                    Xpub::from_priv(&secp, account_xprv)
+                )
+            })
+            .collect::<Vec<_>>();
+        let descriptor = format!("wsh(multi(2,{}))", keys.join(","));
+
+        let (derivation_path, account_xprv) = &cosigners[1];
+        let our_signer =
+            DescriptorSecretKey::from_str(&format!("[{fingerprint}/{derivation_path}]{account_xprv}")).unwrap();
+
+        let account = Arc::new(
+            Account::<MemoryPersisted, MemoryPersisted>::new_multisig(
+                &descriptor,
+                Some(our_signer),
+                Network::Regtest,
+                MemoryPersisted {},
+            )
+            .unwrap(),
+        );
+        assert!(!account.is_watch_only().await);
+        assert_eq!(account.get_derivation_path(), *derivation_path);
+
+        let address = account.get_next_receive_address().await.unwrap().address;
+        assert_eq!(address.address_type(), Some(AddressType::P2wsh));
+
+        let watch_only_account = Account::<MemoryPersisted, MemoryPersisted>::new_multisig(
+            &descriptor,
+            None,
+            Network::Regtest,
+            MemoryPersisted {},
+        )
+        .unwrap();
+        assert!(watch_only_account.is_watch_only().await);
+        assert_eq!(
+            watch_only_account.peek_receive_address(0).await.unwrap().address,
+            address
+        );
+
+        receive_unconfirmed_tx(
+            &account,
+            vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        )
+        .await;
+        assert_eq!(account.get_balance().await.total(), Amount::from_sat(10_000));
+
+        let mut psbt = TxBuilder::<MemoryPersisted>::new()
+            .set_account(account.clone())
+            .update_recipient(
+                0,
+                (
+                    Some("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string()),
+                    Some(5_000),
+                ),
+            )
+            .unwrap()
+            .create_psbt(false, false)
+            .await
+            .unwrap()
+            .inner();
+
+        account.sign(&mut psbt, None).await.unwrap();
+
+        // Only our signature is added, another cosigner still has to sign
+        assert_eq!(psbt.inputs[0].partial_sigs.len(), 1);
+        assert!(psbt.inputs[0].final_script_witness.is_none());
+    }
+
+    #[test]
+    fn test_multisig_account_from_invalid_descriptor() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let other_account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/1'");
+        let descriptor = format!(
+            "wsh(multi(1,{}/0/*,{}/0/*))",
+            account.export_account_xpub(),
+            other_account.export_account_xpub()
+        );
+
+        // Signer isn't one of the cosigners
+        let foreign_signer =
+            DescriptorSecretKey::from_str(&Xpriv::new_master(NetworkKind::Test, &[42u8; 32]).unwrap().to_string())
+                .unwrap();
+        let result = Account::<MemoryPersisted, MemoryPersisted>::new_multisig(
+            &descriptor,
+            Some(foreign_signer),
+            Network::Testnet,
+            MemoryPersisted {},
+        );
+        assert!(matches!(result, Err(Error::InvalidDescriptor(_))));
+
+        // Single key descriptor
+        let result = Account::<MemoryPersisted, MemoryPersisted>::new_multisig(
+            &format!("wpkh({}/0/*)", account.export_account_xpub()),
+            None,
+            Network::Testnet,
+            MemoryPersisted {},
+        );
+        assert!(matches!(result, Err(Error::InvalidDescriptor(_))));
+    }
+
+    fn set_test_watch_only_account(
+        script_type: ScriptType,
+        derivation_path: &str,
+    ) -> Account<MemoryPersisted, MemoryPersisted> {
+        let mnemonic = Mnemonic::from_string("category law logic swear involve banner pink room diesel fragile sunset remove whale lounge captain code hobby lesson material current moment funny vast fade".to_string()).unwrap();
+        let master_secret_key = Xpriv::new_master(NetworkKind::Test, &mnemonic.inner().to_seed("")).unwrap();
+
+        let secp = Secp256k1::new();
+        let account_xprv = master_secret_key
+            .derive_priv(&secp, &DerivationPath::from_str(derivation_path).unwrap())
+            .unwrap();
+
+        Account::new_watch_only(
+            Xpub::from_priv(&secp, &account_xprv),
+            Network::Testnet,
+            script_type,
+            MemoryPersisted {},
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_watch_only_account_addresses() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let watch_only_account = set_test_watch_only_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        assert_eq!(
+            watch_only_account.get_derivation_path(),
+            DerivationPath::from_str("m/84'/1'/0'").unwrap()
+        );
+        assert!(watch_only_account.is_watch_only().await);
+        assert!(!account.is_watch_only().await);
+
+        for index in 0..3 {
+            assert_eq!(
+                watch_only_account.peek_receive_address(index).await.unwrap().address,
+                account.peek_receive_address(index).await.unwrap().address
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_only_account_cannot_sign() {
+        let watch_only_account = set_test_watch_only_account(ScriptType::Taproot, "m/86'/1'/0'");
+
+        let mut psbt = Psbt::from_unsigned_tx(Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![],
+        })
+        .unwrap();
+
+        let result = watch_only_account.sign(&mut psbt, None).await;
+        assert!(matches!(result, Err(Error::WatchOnlyCannotSign)));
+    }
+
+    #[test]
+    fn test_watch_only_account_rejects_non_account_xpub() {
+        let mnemonic = Mnemonic::from_string("category law logic swear involve banner pink room diesel fragile sunset remove whale lounge captain code hobby lesson material current moment funny vast fade".to_string()).unwrap();
+        let master_secret_key = Xpriv::new_master(NetworkKind::Test, &mnemonic.inner().to_seed("")).unwrap();
+
+        let secp = Secp256k1::new();
+        let new_watch_only = |derivation_path: &str, network: Network| {
+            let account_xprv = master_secret_key
+                .derive_priv(&secp, &DerivationPath::from_str(derivation_path).unwrap())
+                .unwrap();
+
+            Account::<MemoryPersisted, MemoryPersisted>::new_watch_only(
+                Xpub::from_priv(&secp, &account_xprv),
+                network,
+                ScriptType::NativeSegwit,
+                MemoryPersisted {},
+            )
+        };
+
+        // Keys above or below account level
+        for derivation_path in ["m/84'/1'", "m/84'/1'/0'/0"] {
+            assert!(matches!(
+                new_watch_only(derivation_path, Network::Testnet),
+                Err(Error::InvalidAccountXpub(_))
+            ));
+        }
+
+        // Unhardened account index
+        assert!(matches!(
+            new_watch_only("m/84'/1'/0", Network::Testnet),
+            Err(Error::InvalidAccountXpub(_))
+        ));
+
+        // Testnet key for a mainnet account
+        assert!(matches!(
+            new_watch_only("m/84'/1'/0'", Network::Bitcoin),
+            Err(Error::InvalidAccountXpub(_))
+        ));
+
+        assert!(new_watch_only("m/84'/1'/0'", Network::Testnet).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_apply_update_reports_new_txs() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let mock_server = get_mock_server_for_regtest().await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = BlockchainClient::new(api_client.clone());
+
+        let update = client.full_sync(&account, None, None).await.unwrap();
+        let report = account.apply_update(update).await.unwrap();
+
+        let txid = Txid::from_str("6b62ad31e219c9dab4d7e24a0803b02bbc5d86ba53f6f02aa6de0f301b718e88").unwrap();
+        assert_eq!(report.new_txs, vec![txid]);
+        assert!(report.confirmed_txs.is_empty());
+        assert!(report.reorged_txs.is_empty());
+        assert_eq!(report.tip_height, 3599);
+
+        // Syncing again shouldn't report already known transactions
+        let update = client.full_sync(&account, None, None).await.unwrap();
+        let report = account.apply_update(update).await.unwrap();
+
+        assert!(report.new_txs.is_empty());
+        assert_eq!(report.tip_height, 3599);
+    }
+
+    #[tokio::test]
+    async fn test_apply_update_handles_reorged_txs() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let (genesis, tx) = {
+            let wallet_lock = account.get_wallet().await;
+            let address = wallet_lock.peek_address(KeychainKind::External, 0).address;
+
+            let tx = funding_tx(vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            }]);
+
+            (wallet_lock.latest_checkpoint(), tx)
+        };
+        let txid = tx.compute_txid();
+
+        // First update confirms the transaction in block A at height 1
+        let block_a = BlockId {
+            height: 1,
+            hash: BlockHash::from_str(&"aa".repeat(32)).unwrap(),
+        };
+        let mut tx_update = TxUpdate::default();
+        tx_update.txs.push(Arc::new(tx));
+        tx_update.anchors.insert((
+            ConfirmationBlockTime {
+                block_id: block_a,
+                confirmation_time: 1733473267,
+            },
+            txid,
+        ));
+        let update = Update {
+            chain: Some(genesis.clone().push(block_a).unwrap()),
+            tx_update,
+            ..Default::default()
+        };
+
+        let report = account.apply_update(update).await.unwrap();
+        assert_eq!(report.new_txs, vec![txid]);
+        assert!(account
+            .get_wallet()
+            .await
+            .get_tx(txid)
+            .unwrap()
+            .chain_position
+            .is_confirmed());
+
+        // Second update replaces block A with block B, which doesn't contain the
+        // transaction
+        let block_b = BlockId {
+            height: 1,
+            hash: BlockHash::from_str(&"bb".repeat(32)).unwrap(),
+        };
+        let update = Update {
+            chain: Some(genesis.push(block_b).unwrap()),
+            ..Default::default()
+        };
+
+        let report = account.apply_update(update).await.unwrap();
+        assert!(report.new_txs.is_empty());
+        assert_eq!(report.reorged_txs, vec![txid]);
+        assert_eq!(report.tip_height, 1);
+
+        let wallet_lock = account.get_wallet().await;
+        let wallet_tx = wallet_lock.get_tx(txid).unwrap();
+        assert!(matches!(
+            wallet_tx.chain_position,
+            ChainPosition::Unconfirmed { last_seen: Some(_) }
+        ));
+        assert_eq!(wallet_lock.balance().untrusted_pending.to_sat(), 10_000);
+    }
+
+    #[tokio::test]
+    async fn test_psbt_inputs_and_outputs() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let mock_server = get_mock_server_for_regtest().await;
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = BlockchainClient::new(api_client.clone());
+
+        let update = client.full_sync(&account, None, None).await.unwrap();
+        account.apply_update(update).await.unwrap();
 
-        let derivation_path = DerivationPath::from_str(derivation_path).unwrap();
+        let mut wallet_lock = account.get_mutable_wallet().await;
+        let recipient = Address::from_str("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j")
+            .unwrap()
+            .assume_checked();
 
-        Account::new(
-            master_secret_key,
-            Network::Bitcoin,
-            script_type,
-            derivation_path,
-            MemoryPersisted {},
-        )
-        .unwrap()
+        let mut tx_builder = wallet_lock.build_tx();
+        tx_builder.add_recipient(recipient.script_pubkey(), Amount::from_sat(1000));
+        let psbt = crate::psbt::Psbt::new(tx_builder.finish().unwrap());
+
+        let inputs = psbt.inputs(&wallet_lock);
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(inputs[0].value, Some(8781));
+        assert!(inputs[0].is_mine);
+        assert!(inputs[0].is_witness);
+
+        let outputs = psbt.outputs(&wallet_lock);
+        assert_eq!(outputs.len(), 2);
+
+        let recipient_output = outputs
+            .iter()
+            .find(|output| output.address == Some(recipient.clone()))
+            .unwrap();
+        assert_eq!(recipient_output.value, 1000);
+        assert!(!recipient_output.is_change);
+
+        let change_output = outputs
+            .iter()
+            .find(|output| output.address != Some(recipient.clone()))
+            .unwrap();
+        assert!(change_output.is_change);
     }
 
-    async fn get_mock_server_for_mainnet() -> MockServer {
-        let mock_server = MockServer::start().await;
+    #[tokio::test]
+    async fn test_sync_scripts() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let address = account.get_next_receive_address().await.unwrap().address;
+        let spk = address.script_pubkey();
 
-        let req_path_blocks: String = format!("{}/blocks", BASE_WALLET_API_V1);
+        let mock_server = MockServer::start().await;
 
-        let response_contents = read_mock_file!("get_blocks_body_mainnet");
-        let response = ResponseTemplate::new(200).set_body_string(response_contents);
         Mock::given(method("GET"))
-            .and(path(req_path_blocks.clone()))
-            .respond_with(response)
+            .and(path(format!("{}/blocks", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_string(read_mock_file!("get_blocks_body")))
             .mount(&mock_server)
             .await;
-
-        let req_path: String = format!("{}/addresses/scripthashes/transactions", BASE_WALLET_API_V1);
-
-        let response_contents1 = read_mock_file!("get_scripthashes_transactions_body_mainnet_1");
-        let response1 = ResponseTemplate::new(200).set_body_string(response_contents1);
-        Mock::given(method("POST"))
-            .and(path(req_path.clone()))
-            .and(body_string_contains(
-                "5433431edc5b618c67ddc237ea86a996f32004022daa49bd40dc607052e892e0",
-            ))
-            .respond_with(response1)
+        Mock::given(method("GET"))
+            .and(path_regex(".*/height/.*"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(read_mock_file!("get_block_hash_body")))
             .mount(&mock_server)
             .await;
 
-        let response_contents2 = read_mock_file!("get_scripthashes_transactions_body_mainnet_2");
-        let response2 = ResponseTemplate::new(200).set_body_string(response_contents2);
-
+        // Only the revealed address' scripthash should be queried, once
+        let scripthash = scripthash_for_script(&spk);
         Mock::given(method("POST"))
-            .and(path(req_path.clone()))
-            .and(body_string_contains(
-                "364923b99c5e330ddf19692077b8d69700e71083542ef0a6ebc9e657d5e1ef56",
-            ))
-            .respond_with(response2)
+            .and(path(format!(
+                "{}/addresses/scripthashes/transactions",
+                BASE_WALLET_API_V1
+            )))
+            .and(body_string_contains(scripthash.clone()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Code": 1000,
+                "Transactions": {
+                    scripthash: [{
+                        "TransactionID": "871fc3f9738818328112cecb6893634190c20e089584d848661c566da2c9d469",
+                        "Version": 2,
+                        "Locktime": 0,
+                        "Vin": null,
+                        "Vout": [{
+                            "ScriptPubKey": spk.to_hex_string(),
+                            "ScriptPubKeyAsm": "",
+                            "ScriptPubKeyType": "v0_p2wpkh",
+                            "ScriptPubKeyAddress": address.to_string(),
+                            "Value": 5000
+                        }],
+                        "Size": 222,
+                        "Weight": 561,
+                        "Fee": 141,
+                        "TransactionStatus": {
+                            "IsConfirmed": 1,
+                            "BlockHeight": 3595,
+                            "BlockHash": "4eddaa524a567d5891853d651f932d8cf26d39397ad087cda2a640f560dea51b",
+                            "BlockTime": 1733468825
+                        }
+                    }]
+                }
+            })))
+            .expect(1)
             .mount(&mock_server)
             .await;
 
-        let response_contents3 = read_mock_file!("get_scripthashes_transactions_body_mainnet_3");
-        let response3 = ResponseTemplate::new(200).set_body_string(response_contents3);
+        let client = Arc::new(BlockchainClient::new(setup_test_connection(mock_server.uri())));
+        let report = account.sync_scripts(client, vec![spk]).await.unwrap();
 
-        Mock::given(method("POST"))
-            .and(path(req_path.clone()))
-            .and(body_string_contains(
-                "032f964badcda0acf21c0b7642fd7a94056e3cfa49a38ed92d9c2f46acba4417",
-            ))
-            .respond_with(response3)
-            .mount(&mock_server)
-            .await;
+        assert_eq!(report.new_txs.len(), 1);
+        assert_eq!(account.get_balance().await.total().to_sat(), 5000);
+    }
 
-        let response_contents4 = read_mock_file!("get_scripthashes_transactions_body_mainnet_4");
-        let response4 = ResponseTemplate::new(200).set_body_string(response_contents4);
+    #[tokio::test]
+    async fn test_generate_address_pool() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
 
-        Mock::given(method("POST"))
-            .and(path(req_path.clone()))
-            .and(body_string_contains(
-                "278a61a1f06888fab709301b570b9c3e4b9efdc54a1f26b9bfbad34b508e8433",
-            ))
-            .respond_with(response4)
-            .mount(&mock_server)
-            .await;
+        let pool = account
+            .generate_address_pool(5, Some(2), |address| Ok(format!("signature-{}", address.address)))
+            .await
+            .unwrap();
 
-        let block_hash_data = vec![
-            (0, "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f"),
-            (
-                869089,
-                "000000000000000000015c4d376f65d85378b3b6f5eaae656078dc319dd7fcdf",
-            ),
-            (
-                869090,
-                "000000000000000000002067d2cd097be40e1d7d1876d38b8025eb1bb2f28fc4",
-            ),
-            (
-                869434,
-                "00000000000000000000556ef62a310d7230ec72afc2a577b953072320f03c5b",
-            ),
-            (
-                871611,
-                "00000000000000000001f9a7c7c50359f190a71fcd658f5efe02808adfa8027b",
-            ),
-            (
-                872365,
-                "000000000000000000001d74687b6170827dad9bf59c4d612db3d7cd73009a5b",
-            ),
-            (
-                872366,
-                "000000000000000000018700dd168b5672808addc35d6aa015eb817e80a00f2d",
-            ),
-        ];
-        for (block_height, block_hash) in block_hash_data {
-            Mock::given(method("GET"))
-                .and(path_regex(format!(".*blocks/height/{}/hash", block_height)))
-                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
-                    "Code": 1000,
-                    "BlockHash": block_hash,
-                })))
-                .mount(&mock_server)
-                .await;
+        assert_eq!(
+            pool.iter()
+                .map(|payload| payload.BitcoinAddressIndex)
+                .collect::<Vec<_>>(),
+            vec![3, 4, 5, 6, 7]
+        );
+        for payload in pool.iter() {
+            let expected_address = account
+                .peek_receive_address(payload.BitcoinAddressIndex as u32)
+                .await
+                .unwrap()
+                .address
+                .to_string();
+            assert_eq!(payload.BitcoinAddress, expected_address);
+            assert_eq!(
+                payload.BitcoinAddressSignature,
+                format!("signature-{}", expected_address)
+            );
         }
-        return mock_server;
+
+        // Next pool should start right after the previous one
+        let pool = account
+            .generate_address_pool(2, Some(2), |address| Ok(address.address.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(pool[0].BitcoinAddressIndex, 8);
+        assert_eq!(pool[1].BitcoinAddressIndex, 9);
     }
 
     #[tokio::test]
-    async fn test_get_wallet() {
-        let account = set_test_account(ScriptType::Legacy, "m/44'/1'/0'");
-        let wallet = account.get_wallet().await;
-        assert!(wallet.balance().total().to_sat() == 0);
+    async fn test_generate_address_pool_marks_addresses_once_signed() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        // Second address can't be signed
+        let failing_signer = |address: &AddressInfo| match address.index {
+            1 => Err(Error::WatchOnlyCannotSign),
+            _ => Ok(address.address.to_string()),
+        };
+        let result = account.generate_address_pool(3, None, failing_signer).await;
+        assert!(matches!(result, Err(Error::WatchOnlyCannotSign)));
+        assert_eq!(
+            account
+                .get_address_for_keychain(KeychainKind::External, None)
+                .await
+                .index,
+            0
+        );
+
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        account
+            .generate_address_pool(3, None, |address| Ok(address.address.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(
+            account
+                .get_address_for_keychain(KeychainKind::External, None)
+                .await
+                .index,
+            3
+        );
     }
 
     #[tokio::test]
-    #[ignore]
-    async fn test_get_address_sync_true() {
-        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
-        let network = Network::Regtest;
-        let address_str = "bcrt1q4zpmdp77e9ff4ls8ajgqapdhgqutrkcpqpzcqw".to_string();
+    async fn test_generate_address_pool_rejects_out_of_range_indexes() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let signer = |address: &AddressInfo| Ok(address.address.to_string());
 
-        let api_client = common_api_client().await;
-        let client = BlockchainClient::new(api_client.as_ref().clone());
-        let address_detail = account
-            .get_address(network, address_str.clone(), Arc::new(client.clone()), true)
+        for highest_used_index in [u64::MAX, u32::MAX as u64, (1 << 31) - 2] {
+            assert!(matches!(
+                account.generate_address_pool(2, Some(highest_used_index), signer).await,
+                Err(Error::AddressIndexOutOfRange)
+            ));
+        }
+
+        // Last unhardened index can still be handed out
+        let pool = account
+            .generate_address_pool(1, Some((1 << 31) - 2), signer)
             .await
             .unwrap();
-        assert!(address_detail.is_some());
-        assert!(address_detail.unwrap().balance.confirmed.to_sat() == 0);
+        assert_eq!(pool[0].BitcoinAddressIndex, (1 << 31) - 1);
     }
 
     #[tokio::test]
-    #[ignore]
-    async fn test_get_address_sync_false() {
+    async fn test_reconcile_address_pool() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let pool = account
+            .generate_address_pool(3, None, |address| Ok(address.address.to_string()))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|payload| ApiWalletBitcoinAddress {
+                ID: format!("pool-address-{}", payload.BitcoinAddressIndex),
+                WalletID: "wallet".to_string(),
+                WalletAccountID: "account".to_string(),
+                Fetched: 1,
+                Used: 0,
+                BitcoinAddress: Some(payload.BitcoinAddress),
+                BitcoinAddressSignature: Some(payload.BitcoinAddressSignature),
+                BitcoinAddressIndex: Some(payload.BitcoinAddressIndex),
+            })
+            .chain([ApiWalletBitcoinAddress {
+                ID: "pool-address-out-of-range".to_string(),
+                WalletID: "wallet".to_string(),
+                WalletAccountID: "account".to_string(),
+                Fetched: 1,
+                Used: 0,
+                BitcoinAddress: None,
+                BitcoinAddressSignature: None,
+                // Would be the second pool address' index if truncated
+                BitcoinAddressIndex: Some(u64::from(u32::MAX) + 2),
+            }])
+            .collect::<Vec<_>>();
+
+        // Second pool address receives funds
+        let address = account.peek_receive_address(1).await.unwrap().address;
+        receive_unconfirmed_tx(
+            &account,
+            vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        )
+        .await;
+
+        let used = account
+            .reconcile_address_pool(&pool, Some(2), |address| Ok(address.address.to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(used.len(), 1);
+        assert_eq!(used[0].id, "pool-address-1");
+        assert_eq!(used[0].index, 1);
+        assert_eq!(used[0].replacement.BitcoinAddressIndex, 3);
+        assert_ne!(used[0].replacement.BitcoinAddress, address.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_sign_bitcoin_address() {
+        let message = "proton wallet bitcoin address";
+
+        // BIP137 header flags the address type
+        for (script_type, derivation_path, headers) in [
+            (ScriptType::Legacy, "m/44'/1'/0'", 31..=34),
+            (ScriptType::NestedSegwit, "m/49'/1'/0'", 35..=38),
+            (ScriptType::NativeSegwit, "m/84'/1'/0'", 39..=42),
+        ] {
+            let account = set_test_account(script_type, derivation_path);
+            let address = account.peek_receive_address(3).await.unwrap().address;
+
+            let signature = account
+                .sign_bitcoin_address(&address.to_string(), message)
+                .await
+                .unwrap();
+
+            let signature = STANDARD.decode(signature).unwrap();
+            assert!(headers.contains(&signature[0]));
+
+            let recovery_id = RecoveryId::from_i32(((signature[0] - 27) & 0x03) as i32).unwrap();
+            let signature = MessageSignature::new(
+                RecoverableSignature::from_compact(&signature[1..], recovery_id).unwrap(),
+                true,
+            );
+            let pubkey = signature
+                .recover_pubkey(&Secp256k1::verification_only(), signed_msg_hash(message))
+                .unwrap();
+            assert!(address.is_related_to_pubkey(&pubkey));
+
+            // Signature shouldn't verify against another address of the account
+            let other_address = account.peek_receive_address(4).await.unwrap().address;
+            assert!(!other_address.is_related_to_pubkey(&pubkey));
+        }
+
+        // Taproot addresses are signed following BIP-322
+        let account = set_test_account(ScriptType::Taproot, "m/86'/1'/0'");
+        let address = account.peek_receive_address(3).await.unwrap().address.to_string();
+        let signature = account.sign_bitcoin_address(&address, message).await.unwrap();
+        assert!(verify_message(message, &signature, &address).unwrap());
+
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let not_owned_address = Address::from_script(
+            &ScriptBuf::from_hex("0014bc67f585a4e23ed0895782b55b164e210b089691").unwrap(),
+            bitcoin::Network::Testnet,
+        )
+        .unwrap();
+        let not_owned = account
+            .sign_bitcoin_address(&not_owned_address.to_string(), message)
+            .await;
+        assert!(matches!(not_owned, Err(Error::AddressNotOwned(_))));
+    }
+
+    #[tokio::test]
+    async fn test_sign_bitcoin_address_with_account_key() {
+        let account = set_test_account(ScriptType::Taproot, "m/86'/1'/0'");
+        let leaf_account = set_test_account(ScriptType::Taproot, "m/86'/1'/1'");
+
+        // Descriptor has signers for both its internal and leaf keys
+        let mnemonic = Mnemonic::from_string("category law logic swear involve banner pink room diesel fragile sunset remove whale lounge captain code hobby lesson material current moment funny vast fade".to_string()).unwrap();
+        let master_secret_key = Xpriv::new_master(NetworkKind::Test, &mnemonic.inner().to_seed("")).unwrap();
+        let secp = Secp256k1::new();
+        let [account_xprv, leaf_xprv] = ["m/86'/1'/0'", "m/86'/1'/1'"].map(|derivation_path| {
+            master_secret_key
+                .derive_priv(&secp, &DerivationPath::from_str(derivation_path).unwrap())
+                .unwrap()
+        });
+        let fingerprint = master_secret_key.fingerprint(&secp);
+
+        let imported_account = Account::<MemoryPersisted, MemoryPersisted>::from_descriptor(
+            &format!("tr([{fingerprint}/86'/1'/0']{account_xprv}/0/*,pk([{fingerprint}/86'/1'/1']{leaf_xprv}/0/*))"),
+            None,
+            Network::Testnet,
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        // Address is signed with the internal key, not the first signer's one
+        let address = imported_account.peek_receive_address(0).await.unwrap().address;
+        let (_, private_key, _) = imported_account
+            .get_address_private_key(&address.to_string())
+            .await
+            .unwrap();
+
+        let expected_key = |account: &Account<MemoryPersisted, MemoryPersisted>| {
+            account
+                .get_account_xpub()
+                .derive_pub(&secp, &DerivationPath::from_str("m/0/0").unwrap())
+                .unwrap()
+                .public_key
+        };
+        assert_eq!(private_key.public_key(&secp), expected_key(&account));
+        assert_ne!(private_key.public_key(&secp), expected_key(&leaf_account));
+
+        // Signature commits to the address script tree
+        let message = "proof of ownership";
+        let signature = imported_account
+            .sign_bitcoin_address(&address.to_string(), message)
+            .await
+            .unwrap();
+        assert!(verify_message(message, &signature, &address.to_string()).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_sign_message() {
+        let message = "proof of ownership";
+
+        for (script_type, derivation_path) in [
+            (ScriptType::NativeSegwit, "m/84'/1'/0'"),
+            (ScriptType::Taproot, "m/86'/1'/0'"),
+        ] {
+            let account = set_test_account(script_type, derivation_path);
+            let address = account.peek_receive_address(3).await.unwrap().address.to_string();
+
+            let signature = account.sign_message(message, &address).await.unwrap();
+
+            assert!(verify_message(message, &signature, &address).unwrap());
+            assert!(!verify_message("proof of 0wnership", &signature, &address).unwrap());
+
+            let other_address = account.peek_receive_address(4).await.unwrap().address.to_string();
+            assert!(!verify_message(message, &signature, &other_address).unwrap());
+        }
+
+        let account = set_test_account(ScriptType::Legacy, "m/44'/1'/0'");
+        let address = account.peek_receive_address(0).await.unwrap().address.to_string();
+        assert!(matches!(
+            account.sign_message(message, &address).await,
+            Err(Error::MessageSigningNotSupported)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_message_with_script_tree_account() {
+        let message = "proof of ownership";
+
+        let mnemonic = Mnemonic::from_string("category law logic swear involve banner pink room diesel fragile sunset remove whale lounge captain code hobby lesson material current moment funny vast fade".to_string()).unwrap();
+        let master_secret_key = Xpriv::new_master(NetworkKind::Test, &mnemonic.inner().to_seed("")).unwrap();
+        let secp = Secp256k1::new();
+        let [account_xprv, leaf_xprv] = ["m/86'/1'/0'", "m/86'/1'/1'"].map(|derivation_path| {
+            master_secret_key
+                .derive_priv(&secp, &DerivationPath::from_str(derivation_path).unwrap())
+                .unwrap()
+        });
+        let fingerprint = master_secret_key.fingerprint(&secp);
+
+        let account = Account::<MemoryPersisted, MemoryPersisted>::from_descriptor(
+            &format!("tr([{fingerprint}/86'/1'/0']{account_xprv}/0/*,pk([{fingerprint}/86'/1'/1']{leaf_xprv}/0/*))"),
+            None,
+            Network::Testnet,
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        // Output key is tweaked with the script tree, which the signature must commit to
+        let address = account.peek_receive_address(2).await.unwrap().address.to_string();
+        let signature = account.sign_message(message, &address).await.unwrap();
+
+        assert!(verify_message(message, &signature, &address).unwrap());
+        assert!(!verify_message("proof of 0wnership", &signature, &address).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_get_received_on_address() {
         let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
-        let network = Network::Regtest;
-        let address_str = "bcrt1q4zpmdp77e9ff4ls8ajgqapdhgqutrkcpqpzcqw".to_string();
+        let address = account.peek_receive_address(0).await.unwrap().address;
+        let spk = address.script_pubkey();
+
+        let api_tx = |txid: &str, value: u64| {
+            serde_json::json!({
+                "TransactionID": txid,
+                "Version": 2,
+                "Locktime": 0,
+                "Vin": null,
+                "Vout": [
+                    {
+                        "ScriptPubKey": spk.to_hex_string(),
+                        "ScriptPubKeyAsm": "",
+                        "ScriptPubKeyType": "v0_p2wpkh",
+                        "ScriptPubKeyAddress": address.to_string(),
+                        "Value": value
+                    },
+                    {
+                        "ScriptPubKey": "0014bc67f585a4e23ed0895782b55b164e210b089691",
+                        "ScriptPubKeyAsm": "",
+                        "ScriptPubKeyType": "v0_p2wpkh",
+                        "ScriptPubKeyAddress": "bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j",
+                        "Value": 10000
+                    }
+                ],
+                "Size": 222,
+                "Weight": 561,
+                "Fee": 141,
+                "TransactionStatus": {
+                    "IsConfirmed": 1,
+                    "BlockHeight": 3595,
+                    "BlockHash": "4eddaa524a567d5891853d651f932d8cf26d39397ad087cda2a640f560dea51b",
+                    "BlockTime": 1733468825
+                }
+            })
+        };
 
-        let api_client = common_api_client().await;
-        let client = BlockchainClient::new(api_client.as_ref().clone());
-        let address_detail = account
-            .get_address(network, address_str.clone(), Arc::new(client.clone()), false)
+        let mock_server = MockServer::start().await;
+        let req_path: String = format!(
+            "{}/addresses/scripthash/{}/transactions",
+            BASE_WALLET_API_V1,
+            scripthash_for_script(&spk)
+        );
+        Mock::given(method("GET"))
+            .and(path(req_path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Code": 1000,
+                "Transactions": [
+                    api_tx("6b62ad31e219c9dab4d7e24a0803b02bbc5d86ba53f6f02aa6de0f301b718e88", 3000),
+                    api_tx("871fc3f9738818328112cecb6893634190c20e089584d848661c566da2c9d469", 5000),
+                ],
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = Arc::new(BlockchainClient::new(api_client));
+
+        let received = account
+            .get_received_on_address(Network::Regtest, address.to_string(), client.clone())
             .await
             .unwrap();
-        assert!(address_detail.is_some());
-        assert!(address_detail.unwrap().balance.confirmed.to_sat() == 0);
+        assert_eq!(received, 8000);
+
+        let not_owned = account
+            .get_received_on_address(
+                Network::Regtest,
+                "bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string(),
+                client,
+            )
+            .await;
+        assert!(matches!(not_owned, Err(Error::AddressNotOwned(_))));
+    }
+
+    #[tokio::test]
+    async fn test_bump_transactions_fees_error() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let result = account
+            .bump_transactions_fees(
+                "6b62ad31e219c9dab4d7e24a0803b02bbc5d86ba53f6f02aa6de0f301b718e88".to_string(),
+                1000,
+            )
+            .await;
+        assert!(result.is_err());
     }
 
     #[tokio::test]
-    #[ignore]
-    async fn test_get_addresses_sync_false() {
+    async fn test_get_derivation_path() {
         let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
-
-        let api_client = common_api_client().await;
-        let client = BlockchainClient::new(api_client.as_ref().clone());
-        let pagination = Pagination::new(0, 10);
-        let addresses = account
-            .get_addresses(
-                pagination,
-                Arc::new(client.clone()),
-                bdk_wallet::KeychainKind::Internal,
-                false,
-            )
-            .await
-            .unwrap();
-        assert!(addresses.len() == 9);
+        let derivation_path = account.get_derivation_path();
+        assert_eq!(derivation_path.to_string(), "84'/1'/0'");
     }
 
     #[tokio::test]
-    async fn test_get_addresses() {
+    async fn test_get_balance() {
         let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let balance = account.get_balance().await;
+        assert_eq!(balance.total().to_sat(), 0);
 
         let mock_server = MockServer::start().await;
 
@@ -934,17 +4068,41 @@ mod tests {
 
         let req_path: String = format!("{}/addresses/scripthashes/transactions", BASE_WALLET_API_V1);
 
-        let response_contents1 = read_mock_file!("get_addresses_body_1");
+        let response_contents1 = read_mock_file!("get_scripthashes_transactions_body_1");
         let response1 = ResponseTemplate::new(200).set_body_string(response_contents1);
         Mock::given(method("POST"))
             .and(path(req_path.clone()))
             .and(body_string_contains(
-                "3979339df938c20edd4f7505ba17ded9c999c4901091660072628aa0b69fe004",
+                "89a10f34b9e0ad8b770c381d5bbb1f566124d3164781f41fb98218d1362069ec",
             ))
             .respond_with(response1)
             .mount(&mock_server)
             .await;
 
+        let response_contents2 = read_mock_file!("get_scripthashes_transactions_body_2");
+        let response2 = ResponseTemplate::new(200).set_body_string(response_contents2);
+
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .and(body_string_contains(
+                "b6c3616a787f87ed96b70770d84d45acf637ed3ad6f2706b2dfc282cc3ba4c05",
+            ))
+            .respond_with(response2)
+            .mount(&mock_server)
+            .await;
+
+        let response_contents3 = read_mock_file!("get_scripthashes_transactions_body_3");
+        let response3 = ResponseTemplate::new(200).set_body_string(response_contents3);
+
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .and(body_string_contains(
+                "5eac955f250ff14fd8c61e29e9531bc3e49d69038981a1344e88b985bd200a29",
+            ))
+            .respond_with(response3)
+            .mount(&mock_server)
+            .await;
+
         let response_contents_block_hash = read_mock_file!("get_block_hash_body");
         let response_block_hash = ResponseTemplate::new(200).set_body_string(response_contents_block_hash);
 
@@ -956,60 +4114,23 @@ mod tests {
 
         let api_client = setup_test_connection(mock_server.uri());
         let client = BlockchainClient::new(api_client.clone());
-        let pagination = Pagination::new(0, 10);
-        let addresses = account
-            .get_addresses(
-                pagination,
-                Arc::new(client.clone()),
-                bdk_wallet::KeychainKind::Internal,
-                false,
-            )
-            .await
-            .unwrap();
-        assert!(addresses.len() == 9);
-    }
-
-    #[tokio::test]
-    async fn test_get_address() {
-        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
-        let network = Network::Regtest;
-        let address_str = "bcrt1q4zpmdp77e9ff4ls8ajgqapdhgqutrkcpqpzcqw".to_string();
 
-        let mock_server = MockServer::start().await;
-        let api_client = setup_test_connection(mock_server.uri());
-        let client = BlockchainClient::new(api_client.clone());
-        let address_detail = account
-            .get_address(network, address_str.clone(), Arc::new(client.clone()), false)
+        // do full sync
+        let update = client.full_sync(&account, None, None).await.unwrap();
+        account
+            .apply_update(update)
             .await
+            .map_err(|_e| "ERROR: could not apply sync update")
             .unwrap();
-        assert!(address_detail.is_some());
-        assert!(address_detail.unwrap().balance.confirmed.to_sat() == 0);
-    }
-
-    #[tokio::test]
-    async fn test_bump_transactions_fees_error() {
-        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
-        let result = account
-            .bump_transactions_fees(
-                "6b62ad31e219c9dab4d7e24a0803b02bbc5d86ba53f6f02aa6de0f301b718e88".to_string(),
-                1000,
-            )
-            .await;
-        assert!(result.is_err());
-    }
-
-    #[tokio::test]
-    async fn test_get_derivation_path() {
-        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
-        let derivation_path = account.get_derivation_path();
-        assert_eq!(derivation_path.to_string(), "84'/1'/0'");
+        let balance = account.get_balance().await;
+        assert_eq!(balance.total().to_sat(), 8781);
     }
 
     #[tokio::test]
-    async fn test_get_balance() {
+    async fn test_get_utxo() {
         let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
-        let balance = account.get_balance().await;
-        assert_eq!(balance.total().to_sat(), 0);
+        let (utxos, _) = account.get_utxos(Pagination::default(), None).await;
+        assert_eq!(utxos.len(), 0);
 
         let mock_server = MockServer::start().await;
 
@@ -1063,109 +4184,687 @@ mod tests {
         let response_contents_block_hash = read_mock_file!("get_block_hash_body");
         let response_block_hash = ResponseTemplate::new(200).set_body_string(response_contents_block_hash);
 
-        Mock::given(method("GET"))
-            .and(path_regex(".*/height/.*"))
-            .respond_with(response_block_hash)
-            .mount(&mock_server)
-            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(".*/height/.*"))
+            .respond_with(response_block_hash)
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = BlockchainClient::new(api_client.clone());
+
+        // do full sync
+        let update = client.full_sync(&account, None, None).await.unwrap();
+        account
+            .apply_update(update)
+            .await
+            .map_err(|_e| "ERROR: could not apply sync update")
+            .unwrap();
+        let (utxos, total) = account.get_utxos(Pagination::default(), None).await;
+
+        assert_eq!(utxos.len(), 1);
+        assert_eq!(total, 1);
+        assert_eq!(utxos[0].chain_position.is_confirmed(), true);
+        assert_eq!(utxos[0].txout.value.to_sat(), 8781);
+    }
+
+    #[tokio::test]
+    async fn test_get_utxos_sorted_and_paginated() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let mut output = Vec::new();
+        for value in [3_000, 9_000, 1_000, 7_000, 5_000] {
+            let address = account.get_next_receive_address().await.unwrap().address;
+            output.push(TxOut {
+                value: Amount::from_sat(value),
+                script_pubkey: address.script_pubkey(),
+            });
+        }
+        receive_unconfirmed_tx(&account, output).await;
+
+        let (utxos, total) = account
+            .get_utxos(Pagination::new(0, 2), Some(UtxoSort::ValueDesc))
+            .await;
+        assert_eq!(total, 5);
+        assert_eq!(
+            utxos.iter().map(|utxo| utxo.txout.value.to_sat()).collect::<Vec<_>>(),
+            vec![9_000, 7_000]
+        );
+
+        let (utxos, _) = account
+            .get_utxos(Pagination::new(1, 10), Some(UtxoSort::ValueAsc))
+            .await;
+        assert_eq!(
+            utxos.iter().map(|utxo| utxo.txout.value.to_sat()).collect::<Vec<_>>(),
+            vec![3_000, 5_000, 7_000, 9_000]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_raw_hex() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let address = account.get_next_receive_address().await.unwrap().address;
+        let txid = receive_unconfirmed_tx(
+            &account,
+            vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        )
+        .await
+        .compute_txid();
+
+        let details = account.get_transaction(txid.to_string(), None).await.unwrap();
+        let parsed = deserialize_hex::<Transaction>(&details.raw_hex).unwrap();
+
+        assert_eq!(parsed.compute_txid(), txid);
+        assert_eq!(parsed.compute_txid(), details.txid);
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_size() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        // Single-input single-output P2WPKH transaction, with a 72 bytes signature
+        let address = account.get_next_receive_address().await.unwrap().address;
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: foreign_outpoint(0),
+                witness: Witness::from_slice(&[vec![0u8; 72], vec![2u8; 33]]),
+                ..Default::default()
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        };
+        let txid = tx.compute_txid();
+        apply_unconfirmed_txs(&account, [tx]).await;
+
+        let details = account.get_transaction(txid.to_string(), None).await.unwrap();
+        assert_eq!(details.weight, 438);
+        assert_eq!(details.vbytes_size, 110);
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_falls_back_to_blockchain() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let address = account.get_next_receive_address().await.unwrap().address;
+        let previous_txid = "6b62ad31e219c9dab4d7e24a0803b02bbc5d86ba53f6f02aa6de0f301b718e88";
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(Txid::from_str(previous_txid).unwrap(), 0),
+                ..Default::default()
+            }],
+            output: vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        };
+        let txid = tx.compute_txid();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/transactions/{}/info", BASE_WALLET_API_V1, txid)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Code": 1000,
+                "Transaction": {
+                    "TransactionID": txid.to_string(),
+                    "Version": 2,
+                    "Locktime": 0,
+                    "Vin": [{
+                        "TransactionID": previous_txid,
+                        "Vout": 0,
+                        "Prevout": null,
+                        "ScriptSig": "",
+                        "ScriptSigAsm": "",
+                        "Witness": [],
+                        "InnerWitnessScriptAsm": null,
+                        "IsCoinbase": 0,
+                        "Sequence": 4294967295u32,
+                        "InnerRedeemScriptAsm": null
+                    }],
+                    "Vout": [{
+                        "ScriptPubKey": address.script_pubkey().to_hex_string(),
+                        "ScriptPubKeyAsm": "",
+                        "ScriptPubKeyType": "v0_p2wpkh",
+                        "ScriptPubKeyAddress": address.to_string(),
+                        "Value": 10_000
+                    }],
+                    "Size": 82,
+                    "Weight": 328,
+                    "Fee": 500,
+                    "TransactionStatus": {
+                        "IsConfirmed": 0,
+                        "BlockHeight": null,
+                        "BlockHash": null,
+                        "BlockTime": null
+                    }
+                }
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/blocks/tip/height", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Code": 1000,
+                "Height": 100,
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let client = Arc::new(BlockchainClient::new(setup_test_connection(mock_server.uri())));
+
+        // The transaction was never synced
+        assert!(matches!(
+            account.get_transaction(txid.to_string(), None).await,
+            Err(Error::TransactionNotFound)
+        ));
+
+        let details = account
+            .get_transaction(txid.to_string(), Some(client.clone()))
+            .await
+            .unwrap();
+        assert_eq!(details.txid, txid);
+        assert_eq!(details.received, 10_000);
+        assert_eq!(details.fees, Some(500));
+
+        // Unconfirmed transaction is now cached in the wallet
+        let cached = account.get_transaction(txid.to_string(), Some(client)).await.unwrap();
+        assert_eq!(cached.txid, txid);
+        assert_eq!(cached.received, 10_000);
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_flags_change_output() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let address = account.get_next_receive_address().await.unwrap().address;
+        let receive_tx = funding_tx(vec![TxOut {
+            value: Amount::from_sat(10_000),
+            script_pubkey: address.script_pubkey(),
+        }]);
+
+        let change_address = account
+            .get_address_for_keychain(KeychainKind::Internal, None)
+            .await
+            .address;
+        // Foreign P2WPKH script
+        let recipient = ScriptBuf::from_hex("0014ef651e82d6717ee9f1c74e8acd5bd1bf639b94a1").unwrap();
+        let spend_tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::new(receive_tx.compute_txid(), 0),
+                ..Default::default()
+            }],
+            output: vec![
+                TxOut {
+                    value: Amount::from_sat(6_000),
+                    script_pubkey: recipient,
+                },
+                TxOut {
+                    value: Amount::from_sat(3_500),
+                    script_pubkey: change_address.script_pubkey(),
+                },
+            ],
+        };
+        let txid = spend_tx.compute_txid();
+        apply_unconfirmed_txs(&account, [receive_tx, spend_tx]).await;
+
+        let details = account.get_transaction(txid.to_string(), None).await.unwrap();
+
+        assert!(!details.outputs[0].is_mine);
+        assert!(!details.outputs[0].is_change);
+        assert!(details.outputs[1].is_mine);
+        assert!(details.outputs[1].is_change);
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_reports_received_output_derivation_index() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let address = account
+            .get_mutable_wallet()
+            .await
+            .reveal_addresses_to(KeychainKind::External, 3)
+            .last()
+            .unwrap()
+            .address;
+        // Foreign P2WPKH script
+        let foreign = ScriptBuf::from_hex("0014ef651e82d6717ee9f1c74e8acd5bd1bf639b94a1").unwrap();
+        let payment_tx = funding_tx(vec![
+            TxOut {
+                value: Amount::from_sat(4_000),
+                script_pubkey: foreign,
+            },
+            TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            },
+        ]);
+        let txid = payment_tx.compute_txid();
+        apply_unconfirmed_txs(&account, [payment_tx]).await;
+
+        let details = account.get_transaction(txid.to_string(), None).await.unwrap();
+
+        assert_eq!(details.outputs[0].derivation_index, None);
+        assert!(details.outputs[1].is_mine);
+        assert!(!details.outputs[1].is_change);
+        assert_eq!(details.outputs[1].derivation_index, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_next_unused_index() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        assert_eq!(account.next_unused_index(KeychainKind::External).await, 0);
+        assert_eq!(account.addresses_used_count(KeychainKind::External).await, 0);
+
+        for _ in 0..3 {
+            account.get_next_receive_address().await.unwrap();
+        }
+        assert_eq!(account.next_unused_index(KeychainKind::External).await, 3);
+        assert_eq!(account.addresses_used_count(KeychainKind::External).await, 3);
+
+        // Revealing an address doesn't mark it as used
+        let address = account.get_address_for_keychain(KeychainKind::External, None).await;
+        assert_eq!(address.index, 3);
+        assert_eq!(account.next_unused_index(KeychainKind::External).await, 3);
+        assert_eq!(account.addresses_used_count(KeychainKind::External).await, 3);
+
+        assert_eq!(account.next_unused_index(KeychainKind::Internal).await, 0);
+        assert_eq!(account.addresses_used_count(KeychainKind::Internal).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_transactions_flags_replaceable() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let address = account.get_next_receive_address().await.unwrap().address;
+        let [rbf_tx, final_tx] =
+            [(0, Sequence::ENABLE_RBF_NO_LOCKTIME), (1, Sequence::MAX)].map(|(vout, sequence)| Transaction {
+                version: Version::TWO,
+                lock_time: LockTime::ZERO,
+                input: vec![TxIn {
+                    previous_output: foreign_outpoint(vout),
+                    sequence,
+                    ..Default::default()
+                }],
+                output: vec![TxOut {
+                    value: Amount::from_sat(10_000),
+                    script_pubkey: address.script_pubkey(),
+                }],
+            });
+        let (rbf_txid, final_txid) = (rbf_tx.compute_txid(), final_tx.compute_txid());
+        apply_unconfirmed_txs(&account, [rbf_tx, final_tx]).await;
+
+        let transactions = account.get_transactions(Pagination::default(), None).await.unwrap();
+        assert_eq!(transactions.len(), 2);
+
+        let replaceable = |txid| {
+            transactions
+                .iter()
+                .find(|tx| tx.txid == txid)
+                .map(|tx| tx.replaceable)
+                .unwrap()
+        };
+        assert!(replaceable(rbf_txid));
+        assert!(!replaceable(final_txid));
+    }
+
+    #[tokio::test]
+    async fn test_transaction_details_from_psbt_fees() {
+        let account = Arc::new(set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'"));
+
+        let address = account.get_next_receive_address().await.unwrap().address;
+        let change_address = account
+            .get_address_for_keychain(KeychainKind::Internal, None)
+            .await
+            .address;
+        let spent_outputs = [12_000, 8_000].map(|value| TxOut {
+            value: Amount::from_sat(value),
+            script_pubkey: address.script_pubkey(),
+        });
+        let unsigned_tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: [0, 1]
+                .map(|vout| TxIn {
+                    previous_output: foreign_outpoint(vout),
+                    ..Default::default()
+                })
+                .to_vec(),
+            output: vec![TxOut {
+                value: Amount::from_sat(19_000),
+                script_pubkey: change_address.script_pubkey(),
+            }],
+        };
+
+        let mut psbt = Psbt::from_unsigned_tx(unsigned_tx).unwrap();
+        for (input, spent_output) in psbt.inputs.iter_mut().zip(spent_outputs) {
+            input.witness_utxo = Some(spent_output);
+        }
+
+        let details = TransactionDetails::from_psbt(&psbt.clone().into(), account.clone())
+            .await
+            .unwrap();
+        assert_eq!(details.fees, Some(1_000));
+
+        // Value of the second input is unknown
+        psbt.inputs[1].witness_utxo = None;
+        let details = TransactionDetails::from_psbt(&psbt.into(), account).await.unwrap();
+        assert_eq!(details.fees, None);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_details_from_hex() {
+        let account = Arc::new(set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'"));
+
+        // Genesis block coinbase transaction
+        let genesis_tx_hex = "01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000";
+
+        let details = TransactionDetails::from_hex(genesis_tx_hex, account.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            details.txid.to_string(),
+            "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"
+        );
+        assert_eq!(details.outputs.len(), 1);
+        assert!(!details.outputs[0].is_mine);
+        assert_eq!(details.received, 0);
+        assert_eq!(details.raw_hex, genesis_tx_hex);
+
+        assert!(matches!(
+            TransactionDetails::from_hex("not a transaction", account).await,
+            Err(Error::InvalidTransactionHex(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_balance_with_confirmations() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let address = account.get_next_receive_address().await.unwrap().address;
+        let tx = funding_tx(vec![TxOut {
+            value: Amount::from_sat(10_000),
+            script_pubkey: address.script_pubkey(),
+        }]);
+
+        // Transaction is mined in block 1, block 2 gives it a second confirmation
+        let genesis = account.get_wallet().await.latest_checkpoint();
+        let [block_1, block_2] = [1, 2].map(|height| BlockId {
+            height,
+            hash: BlockHash::from_str(&format!("{height:02x}").repeat(32)).unwrap(),
+        });
+        let mut tx_update = TxUpdate::default();
+        tx_update.anchors.insert((
+            ConfirmationBlockTime {
+                block_id: block_1,
+                confirmation_time: 1733473267,
+            },
+            tx.compute_txid(),
+        ));
+        tx_update.txs.push(Arc::new(tx));
+        account
+            .apply_update(Update {
+                chain: Some(genesis.push(block_1).unwrap().push(block_2).unwrap()),
+                tx_update,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let balance = account.get_balance_with_confirmations(1, None).await;
+        assert_eq!(balance.confirmed, Amount::from_sat(10_000));
+        assert_eq!(balance.untrusted_pending, Amount::ZERO);
+
+        let balance = account.get_balance_with_confirmations(6, None).await;
+        assert_eq!(balance.confirmed, Amount::ZERO);
+        assert_eq!(balance.untrusted_pending, Amount::from_sat(10_000));
+
+        // Confirmations can be counted up to a more recent tip
+        let balance = account.get_balance_with_confirmations(6, Some(6)).await;
+        assert_eq!(balance.confirmed, Amount::from_sat(10_000));
+    }
+
+    #[tokio::test]
+    async fn test_get_spendable_balance_excludes_frozen_utxos() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let mut output = Vec::new();
+        for value in [3_000, 5_000] {
+            let address = account.get_next_receive_address().await.unwrap().address;
+            output.push(TxOut {
+                value: Amount::from_sat(value),
+                script_pubkey: address.script_pubkey(),
+            });
+        }
+        let txid = receive_unconfirmed_tx(&account, output).await.compute_txid();
+
+        assert_eq!(
+            account.get_spendable_balance(SpendPolicy::default()).await,
+            Amount::from_sat(8_000)
+        );
+
+        account.freeze_utxo(OutPoint::new(txid, 0)).await;
+        assert_eq!(
+            account.get_spendable_balance(SpendPolicy::default()).await,
+            Amount::from_sat(5_000)
+        );
 
-        let api_client = setup_test_connection(mock_server.uri());
-        let client = BlockchainClient::new(api_client.clone());
+        // Received UTXOs are still unconfirmed
+        let policy = SpendPolicy {
+            include_untrusted_pending: false,
+            ..Default::default()
+        };
+        assert_eq!(account.get_spendable_balance(policy).await, Amount::ZERO);
 
-        // do full sync
-        let update = client.full_sync(&account, None).await.unwrap();
-        account
-            .apply_update(update)
-            .await
-            .map_err(|_e| "ERROR: could not apply sync update")
-            .unwrap();
-        let balance = account.get_balance().await;
-        assert_eq!(balance.total().to_sat(), 8781);
+        account.unfreeze_utxo(&OutPoint::new(txid, 0)).await;
+        assert_eq!(
+            account.get_spendable_balance(SpendPolicy::default()).await,
+            Amount::from_sat(8_000)
+        );
     }
 
     #[tokio::test]
-    async fn test_get_utxo() {
+    async fn test_dust_utxos() {
         let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
-        let utxos = account.get_utxos().await;
-        assert_eq!(utxos.len(), 0);
 
-        let mock_server = MockServer::start().await;
+        let mut output = Vec::new();
+        for value in [500, 50_000] {
+            let address = account.get_next_receive_address().await.unwrap().address;
+            output.push(TxOut {
+                value: Amount::from_sat(value),
+                script_pubkey: address.script_pubkey(),
+            });
+        }
+        receive_unconfirmed_tx(&account, output).await;
 
-        let req_path_blocks: String = format!("{}/blocks", BASE_WALLET_API_V1);
+        // Spending a P2WPKH input costs ~68 vB, i.e. ~1360 sats at 20 sat/vB
+        let dust_utxos = account.dust_utxos(20).await.unwrap();
+        assert_eq!(dust_utxos.len(), 1);
+        assert_eq!(dust_utxos[0].txout.value, Amount::from_sat(500));
 
-        let response_contents = read_mock_file!("get_blocks_body");
-        let response = ResponseTemplate::new(200).set_body_string(response_contents);
-        Mock::given(method("GET"))
-            .and(path(req_path_blocks.clone()))
-            .respond_with(response)
-            .mount(&mock_server)
-            .await;
+        assert!(account.dust_utxos(1).await.unwrap().is_empty());
+    }
 
-        let req_path: String = format!("{}/addresses/scripthashes/transactions", BASE_WALLET_API_V1);
+    #[tokio::test]
+    async fn test_build_proof_of_reserves() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
 
-        let response_contents1 = read_mock_file!("get_scripthashes_transactions_body_1");
-        let response1 = ResponseTemplate::new(200).set_body_string(response_contents1);
-        Mock::given(method("POST"))
-            .and(path(req_path.clone()))
-            .and(body_string_contains(
-                "89a10f34b9e0ad8b770c381d5bbb1f566124d3164781f41fb98218d1362069ec",
-            ))
-            .respond_with(response1)
-            .mount(&mock_server)
-            .await;
+        let mut output = Vec::new();
+        for value in [3_000, 4_000, 5_000] {
+            let address = account.get_next_receive_address().await.unwrap().address;
+            output.push(TxOut {
+                value: Amount::from_sat(value),
+                script_pubkey: address.script_pubkey(),
+            });
+        }
+        receive_unconfirmed_tx(&account, output).await;
 
-        let response_contents2 = read_mock_file!("get_scripthashes_transactions_body_2");
-        let response2 = ResponseTemplate::new(200).set_body_string(response_contents2);
+        let message = "reserves as of today";
+        let proof = account.build_proof_of_reserves(message).await.unwrap();
 
-        Mock::given(method("POST"))
-            .and(path(req_path.clone()))
-            .and(body_string_contains(
-                "b6c3616a787f87ed96b70770d84d45acf637ed3ad6f2706b2dfc282cc3ba4c05",
-            ))
-            .respond_with(response2)
-            .mount(&mock_server)
-            .await;
+        assert_eq!(proof.inner().unsigned_tx.input.len(), 4);
+        assert_eq!(
+            verify_proof_of_reserves(&proof, message).unwrap(),
+            account.get_balance().await.total().to_sat()
+        );
+        assert!(matches!(
+            verify_proof_of_reserves(&proof, "another message"),
+            Err(Error::InvalidProofOfReserves(_))
+        ));
+    }
 
-        let response_contents3 = read_mock_file!("get_scripthashes_transactions_body_3");
-        let response3 = ResponseTemplate::new(200).set_body_string(response_contents3);
+    #[tokio::test]
+    async fn test_build_consolidation() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
 
-        Mock::given(method("POST"))
-            .and(path(req_path.clone()))
-            .and(body_string_contains(
-                "5eac955f250ff14fd8c61e29e9531bc3e49d69038981a1344e88b985bd200a29",
-            ))
-            .respond_with(response3)
-            .mount(&mock_server)
-            .await;
+        let mut output = Vec::new();
+        for value in [3_000, 4_000, 5_000] {
+            let address = account.get_next_receive_address().await.unwrap().address;
+            output.push(TxOut {
+                value: Amount::from_sat(value),
+                script_pubkey: address.script_pubkey(),
+            });
+        }
+        receive_unconfirmed_tx(&account, output).await;
 
-        let response_contents_block_hash = read_mock_file!("get_block_hash_body");
-        let response_block_hash = ResponseTemplate::new(200).set_body_string(response_contents_block_hash);
+        let account = Arc::new(account);
+        let psbt = account
+            .clone()
+            .build_consolidation(1, None)
+            .await
+            .unwrap()
+            .create_draft_psbt(false)
+            .await
+            .unwrap()
+            .inner();
 
-        Mock::given(method("GET"))
-            .and(path_regex(".*/height/.*"))
-            .respond_with(response_block_hash)
-            .mount(&mock_server)
-            .await;
+        assert_eq!(psbt.unsigned_tx.input.len(), 3);
+        assert_eq!(psbt.unsigned_tx.output.len(), 1);
+        assert!(matches!(
+            account
+                .get_wallet()
+                .await
+                .derivation_of_spk(psbt.unsigned_tx.output[0].script_pubkey.clone()),
+            Some((KeychainKind::Internal, _))
+        ));
+        assert!(psbt.unsigned_tx.output[0].value > Amount::from_sat(11_000));
+
+        // Inputs count can be capped
+        let psbt = account
+            .clone()
+            .build_consolidation(1, Some(2))
+            .await
+            .unwrap()
+            .create_draft_psbt(false)
+            .await
+            .unwrap()
+            .inner();
+        assert_eq!(psbt.unsigned_tx.input.len(), 2);
+
+        // Invalid fee rates don't use up a change address
+        let next_change_index = account.next_unused_index(KeychainKind::Internal).await;
+        assert!(matches!(
+            account.clone().build_consolidation(0, None).await,
+            Err(Error::InvalidFeeRate(_))
+        ));
+        assert_eq!(
+            account.next_unused_index(KeychainKind::Internal).await,
+            next_change_index
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bump_transactions_fees_success() {}
+
+    #[tokio::test]
+    async fn test_sign_with_trust_witness_utxo() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
 
+        let mock_server = get_mock_server_for_regtest().await;
         let api_client = setup_test_connection(mock_server.uri());
         let client = BlockchainClient::new(api_client.clone());
 
         // do full sync
-        let update = client.full_sync(&account, None).await.unwrap();
+        let update = client.full_sync(&account, None, None).await.unwrap();
         account
             .apply_update(update)
             .await
             .map_err(|_e| "ERROR: could not apply sync update")
             .unwrap();
-        let utxos = account.get_utxos().await;
 
-        assert_eq!(utxos.len(), 1);
-        assert_eq!(utxos[0].chain_position.is_confirmed(), true);
-        assert_eq!(utxos[0].txout.value.to_sat(), 8781);
+        let mut psbt = {
+            let mut wallet_lock = account.get_mutable_wallet().await;
+            let recipient = Address::from_str("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j")
+                .unwrap()
+                .assume_checked();
+
+            let mut tx_builder = wallet_lock.build_tx();
+            tx_builder.add_recipient(recipient.script_pubkey(), Amount::from_sat(1000));
+            tx_builder.finish().unwrap()
+        };
+
+        // Watch-only flows usually only provide witness utxos
+        psbt.inputs.iter_mut().for_each(|input| input.non_witness_utxo = None);
+
+        // BDK refuses to sign without non-witness utxos by default
+        assert!(account.sign(&mut psbt.clone(), None).await.is_err());
+
+        let sign_options = SignOptions {
+            trust_witness_utxo: true,
+            ..Default::default()
+        };
+        account.sign(&mut psbt, Some(sign_options)).await.unwrap();
+
+        assert!(psbt.inputs.iter().all(|input| input.final_script_witness.is_some()));
     }
 
     #[tokio::test]
-    async fn test_bump_transactions_fees_success() {}
+    async fn test_sign_taproot_key_path_spend() {
+        let account = set_test_account_regtest(ScriptType::Taproot, "m/86'/1'/0'");
+
+        let address = account.get_next_receive_address().await.unwrap().address;
+        receive_unconfirmed_tx(
+            &account,
+            vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        )
+        .await;
+
+        let mut psbt = {
+            let mut wallet_lock = account.get_mutable_wallet().await;
+            let recipient = Address::from_str("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j")
+                .unwrap()
+                .assume_checked();
+
+            let mut tx_builder = wallet_lock.build_tx();
+            tx_builder.add_recipient(recipient.script_pubkey(), Amount::from_sat(1000));
+            tx_builder.finish().unwrap()
+        };
+        assert!(psbt.inputs.iter().all(|input| input.tap_internal_key.is_some()));
+
+        account.sign(&mut psbt, None).await.unwrap();
+
+        // Key path spends only have a schnorr signature in their witness
+        assert!(psbt.inputs.iter().all(|input| input
+            .final_script_witness
+            .as_ref()
+            .is_some_and(|witness| witness.len() == 1)));
+        assert!(psbt.extract_tx().is_ok());
+    }
 
     #[tokio::test]
     async fn test_has_sync_data() {
@@ -1236,7 +4935,7 @@ mod tests {
         assert!(!has_synced);
 
         // do full sync
-        let update = client.full_sync(&account, None).await.unwrap();
+        let update = client.full_sync(&account, None, None).await.unwrap();
         account
             .apply_update(update)
             .await
@@ -1312,7 +5011,7 @@ mod tests {
         let api_client = setup_test_connection(mock_server.uri());
         let client = BlockchainClient::new(api_client.clone());
         // do full sync
-        let update = client.full_sync(&account, None).await.unwrap();
+        let update = client.full_sync(&account, None, None).await.unwrap();
         account
             .apply_update(update)
             .await
@@ -1321,9 +5020,11 @@ mod tests {
 
         // get single transaction
         let txid = "6b62ad31e219c9dab4d7e24a0803b02bbc5d86ba53f6f02aa6de0f301b718e88".to_string();
-        let transaction_details = account.get_transaction(txid).await.unwrap();
+        let transaction_details = account.get_transaction(txid, None).await.unwrap();
         assert_eq!(transaction_details.fees.unwrap(), 141);
         assert_eq!(transaction_details.received, 8781);
+        // tx was mined at height 3595 and mocked tip is at 3599
+        assert_eq!(transaction_details.confirmations, Some(5));
 
         // get transactions
         let pagination = Pagination::new(0, 10);
@@ -1337,6 +5038,261 @@ mod tests {
         assert_eq!(transactions[0].received, 8781);
     }
 
+    #[tokio::test]
+    async fn test_account_snapshot_round_trip() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let (genesis, txs) = {
+            let wallet_lock = account.get_wallet().await;
+
+            let txs = (0..3)
+                .map(|index| Transaction {
+                    version: Version::TWO,
+                    lock_time: LockTime::ZERO,
+                    input: vec![TxIn {
+                        previous_output: foreign_outpoint(index),
+                        ..Default::default()
+                    }],
+                    output: vec![TxOut {
+                        value: Amount::from_sat(10_000),
+                        script_pubkey: wallet_lock
+                            .peek_address(KeychainKind::External, index)
+                            .address
+                            .script_pubkey(),
+                    }],
+                })
+                .collect::<Vec<_>>();
+
+            (wallet_lock.latest_checkpoint(), txs)
+        };
+
+        // Two transactions are confirmed, the last one stays unconfirmed
+        let block = BlockId {
+            height: 1,
+            hash: BlockHash::from_str(&"aa".repeat(32)).unwrap(),
+        };
+        let mut tx_update = TxUpdate::default();
+        for tx in &txs[..2] {
+            tx_update.anchors.insert((
+                ConfirmationBlockTime {
+                    block_id: block,
+                    confirmation_time: 1733473267,
+                },
+                tx.compute_txid(),
+            ));
+        }
+        tx_update.txs = txs.into_iter().map(Arc::new).collect();
+        tx_update.seen_ats.insert((tx_update.txs[2].compute_txid(), 1733480000));
+
+        account
+            .apply_update(Update {
+                chain: Some(genesis.push(block).unwrap()),
+                tx_update,
+                last_active_indices: [(KeychainKind::External, 2)].into(),
+            })
+            .await
+            .unwrap();
+
+        let snapshot = account.export_snapshot().await.unwrap();
+
+        let restored_account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let report = restored_account.import_snapshot(&snapshot).await.unwrap();
+        assert_eq!(report.new_txs.len(), 3);
+        assert_eq!(report.tip_height, 1);
+
+        assert_eq!(restored_account.get_balance().await, account.get_balance().await);
+        assert_eq!(restored_account.get_balance().await.confirmed.to_sat(), 20_000);
+
+        let txids = |transactions: Vec<TransactionDetails>| {
+            transactions
+                .into_iter()
+                .map(|tx| (tx.txid, tx.time))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            txids(
+                restored_account
+                    .get_transactions(Pagination::default(), Some(SortOrder::Asc))
+                    .await
+                    .unwrap()
+            ),
+            txids(
+                account
+                    .get_transactions(Pagination::default(), Some(SortOrder::Asc))
+                    .await
+                    .unwrap()
+            )
+        );
+
+        assert_eq!(
+            restored_account.get_next_receive_address().await.unwrap().index,
+            account.get_next_receive_address().await.unwrap().index
+        );
+
+        // Snapshots can't be restored into another account
+        let other_account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/1'");
+        assert!(matches!(
+            other_account.import_snapshot(&snapshot).await,
+            Err(Error::InvalidSnapshot(_))
+        ));
+
+        // Nor without any chain to anchor transactions to
+        let mut chainless_snapshot = serde_json::from_slice::<serde_json::Value>(&snapshot).unwrap();
+        chainless_snapshot["local_chain"]["blocks"] = serde_json::json!({});
+        let chainless_account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        assert!(matches!(
+            chainless_account
+                .import_snapshot(&serde_json::to_vec(&chainless_snapshot).unwrap())
+                .await,
+            Err(Error::InvalidSnapshot(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_restore_checkpoint_then_partial_sync() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        {
+            let mut wallet_lock = account.get_mutable_wallet().await;
+            let _ = wallet_lock.reveal_addresses_to(KeychainKind::External, 2);
+            let _ = wallet_lock.reveal_addresses_to(KeychainKind::Internal, 0);
+        }
+
+        let genesis = account.get_wallet().await.latest_checkpoint();
+        let tip = BlockId {
+            height: 3599,
+            hash: BlockHash::from_str("5ea2caa95b7f7348edca43749c591bcca570e07f70a081076ffe1caf099129e0").unwrap(),
+        };
+        account
+            .apply_update(Update {
+                chain: Some(genesis.push(tip).unwrap()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let checkpoint = account.checkpoint().await;
+        assert_eq!(checkpoint.tip_height, 3599);
+        assert_eq!(checkpoint.external_index, Some(2));
+        assert_eq!(checkpoint.internal_index, Some(0));
+
+        let serialized = serde_json::to_string(&checkpoint).unwrap();
+        let checkpoint: Checkpoint = serde_json::from_str(&serialized).unwrap();
+
+        let restored_account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        restored_account.restore_checkpoint(checkpoint).await.unwrap();
+        assert_eq!(restored_account.checkpoint().await, checkpoint);
+
+        let mock_server = get_mock_server_with_empty_history().await;
+
+        let client = BlockchainClient::new(setup_test_connection(mock_server.uri()));
+        let update = client
+            .partial_sync(restored_account.get_wallet().await, None)
+            .await
+            .unwrap();
+
+        // Only the 3 external and 1 internal revealed scripts are queried,
+        // without any stop gap lookup
+        let queried_scripts_count = mock_server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|request| request.method.as_str() == "POST")
+            .map(|request| {
+                serde_json::from_slice::<serde_json::Value>(&request.body).unwrap()["ScriptHashes"]
+                    .as_array()
+                    .unwrap()
+                    .len()
+            })
+            .sum::<usize>();
+        assert_eq!(queried_scripts_count, 4);
+
+        // The sync resumes from the checkpoint's tip
+        assert_eq!(update.chain_update.unwrap().get(3599).unwrap().hash(), tip.hash);
+    }
+
+    #[tokio::test]
+    async fn test_transactions_iter_matches_sorted_list() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let (genesis, txs) = {
+            let wallet_lock = account.get_wallet().await;
+
+            let txs = (0..5)
+                .map(|index| Transaction {
+                    version: Version::TWO,
+                    lock_time: LockTime::ZERO,
+                    input: vec![TxIn {
+                        previous_output: foreign_outpoint(index),
+                        ..Default::default()
+                    }],
+                    output: vec![TxOut {
+                        value: Amount::from_sat(10_000),
+                        script_pubkey: wallet_lock
+                            .peek_address(KeychainKind::External, index)
+                            .address
+                            .script_pubkey(),
+                    }],
+                })
+                .collect::<Vec<_>>();
+
+            (wallet_lock.latest_checkpoint(), txs)
+        };
+
+        // Confirmation times don't follow insertion order, and the last
+        // transaction stays unconfirmed
+        let block = BlockId {
+            height: 1,
+            hash: BlockHash::from_str(&"aa".repeat(32)).unwrap(),
+        };
+        let mut tx_update = TxUpdate::default();
+        for (tx, confirmation_time) in txs.iter().zip([1733473267, 1733470000, 1733479999, 1733471111]) {
+            tx_update.anchors.insert((
+                ConfirmationBlockTime {
+                    block_id: block,
+                    confirmation_time,
+                },
+                tx.compute_txid(),
+            ));
+        }
+        tx_update.txs = txs.into_iter().map(Arc::new).collect();
+        tx_update.seen_ats.insert((tx_update.txs[4].compute_txid(), 1733480000));
+
+        account
+            .apply_update(Update {
+                chain: Some(genesis.push(block).unwrap()),
+                tx_update,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        for sort in [Some(SortOrder::Asc), Some(SortOrder::Desc), None] {
+            let expected_txids = account
+                .get_transactions(Pagination::default(), sort)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|tx| tx.txid)
+                .collect::<Vec<_>>();
+            assert_eq!(expected_txids.len(), 5);
+
+            let mut cursor = account.transactions_iter(sort).await;
+            let mut txids = Vec::new();
+            loop {
+                let page = cursor.next_page(2).await.unwrap();
+                if page.is_empty() {
+                    break;
+                }
+                assert!(page.len() <= 2);
+                txids.extend(page.into_iter().map(|tx| tx.txid));
+            }
+
+            assert_eq!(cursor.remaining(), 0);
+            assert_eq!(txids, expected_txids);
+        }
+    }
+
     #[tokio::test]
     async fn test_get_highest_used_address_index_in_output() {
         let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
@@ -1410,7 +5366,7 @@ mod tests {
         let client = BlockchainClient::new(api_client.clone());
 
         // do full sync
-        let update = client.full_sync(&account, None).await.unwrap();
+        let update = client.full_sync(&account, None, None).await.unwrap();
         account
             .apply_update(update)
             .await
@@ -1436,7 +5392,7 @@ mod tests {
         let client = BlockchainClient::new(api_client.as_ref().clone());
 
         // do full sync
-        let update = client.full_sync(&account, Some(200)).await.unwrap();
+        let update = client.full_sync(&account, Some(200), None).await.unwrap();
         account
             .apply_update(update)
             .await
@@ -1462,7 +5418,7 @@ mod tests {
         let client = BlockchainClient::new(api_client.as_ref().clone());
 
         // do full sync
-        let update = client.full_sync(&account, Some(500)).await.unwrap();
+        let update = client.full_sync(&account, Some(500), None).await.unwrap();
         account
             .apply_update(update)
             .await
@@ -1518,7 +5474,7 @@ mod tests {
         let client = BlockchainClient::new(api_client.clone());
 
         // do full sync
-        let update = client.full_sync(&account, Some(20)).await.unwrap();
+        let update = client.full_sync(&account, Some(20), None).await.unwrap();
         account
             .apply_update(update)
             .await
@@ -1532,6 +5488,173 @@ mod tests {
         assert_eq!(highest.unwrap(), 7);
     }
 
+    #[tokio::test]
+    async fn test_full_sync_with_progress() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let mock_server = get_mock_server_with_empty_history().await;
+
+        let client = BlockchainClient::new(setup_test_connection(mock_server.uri()));
+
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let progress_clone = progress.clone();
+        client
+            .full_sync_with_progress(
+                &account,
+                Some(10),
+                move |sync_progress| progress_clone.lock().unwrap().push(sync_progress),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let queried_scripts = mock_server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|request| request.url.path().ends_with("/addresses/scripthashes/transactions"))
+            .map(|request| {
+                request.body_json::<serde_json::Value>().unwrap()["ScriptHashes"]
+                    .as_array()
+                    .unwrap()
+                    .len()
+            })
+            .sum::<usize>();
+
+        let progress = progress.lock().unwrap();
+        assert!(!progress.is_empty());
+        // Stop gap of 10 on both keychains
+        assert_eq!(queried_scripts, 20);
+        assert_eq!(progress.last().unwrap().scanned_scripts, queried_scripts);
+    }
+
+    #[tokio::test]
+    async fn test_full_sync_uses_account_gap_limit() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        assert_eq!(account.get_gap_limit(), 20);
+
+        let account = account.with_gap_limit(15);
+        assert_eq!(account.get_gap_limit(), 15);
+
+        let mock_server = get_mock_server_with_empty_history().await;
+
+        let client = BlockchainClient::new(setup_test_connection(mock_server.uri()));
+        client.full_sync(&account, None, None).await.unwrap();
+
+        let queried_scripts = mock_server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|request| request.url.path().ends_with("/addresses/scripthashes/transactions"))
+            .map(|request| {
+                request.body_json::<serde_json::Value>().unwrap()["ScriptHashes"]
+                    .as_array()
+                    .unwrap()
+                    .len()
+            })
+            .sum::<usize>();
+
+        // Gap limit of 15 on both keychains
+        assert_eq!(queried_scripts, 30);
+    }
+
+    #[tokio::test]
+    async fn test_full_sync_external_scope() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'").with_gap_limit(15);
+        account.set_sync_scope(SyncScope::external_only()).await;
+
+        let mock_server = get_mock_server_with_empty_history().await;
+
+        let client = BlockchainClient::new(setup_test_connection(mock_server.uri()));
+        client.full_sync(&account, None, None).await.unwrap();
+
+        let queried_scripthashes = mock_server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|request| request.url.path().ends_with("/addresses/scripthashes/transactions"))
+            .flat_map(|request| {
+                request.body_json::<serde_json::Value>().unwrap()["ScriptHashes"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|script| script["ScriptHash"].as_str().unwrap().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let wallet_lock = account.get_wallet().await;
+        let scripthashes = |keychain: KeychainKind| {
+            (0..15)
+                .map(|index| scripthash_for_script(&wallet_lock.peek_address(keychain, index).script_pubkey()))
+                .collect::<Vec<_>>()
+        };
+        let external_scripthashes = scripthashes(KeychainKind::External);
+        let internal_scripthashes = scripthashes(KeychainKind::Internal);
+
+        assert_eq!(queried_scripthashes.len(), 15);
+        assert!(external_scripthashes
+            .iter()
+            .all(|scripthash| queried_scripthashes.contains(scripthash)));
+        assert!(!internal_scripthashes
+            .iter()
+            .any(|scripthash| queried_scripthashes.contains(scripthash)));
+    }
+
+    #[tokio::test]
+    async fn test_full_sync_cancellation() {
+        /// Returns an empty history for every script, aborting the sync along
+        /// with the first response
+        struct AbortingResponder(AbortHandle);
+
+        impl Respond for AbortingResponder {
+            fn respond(&self, _request: &Request) -> ResponseTemplate {
+                self.0.abort();
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "Code": 1000,
+                    "Transactions": {}
+                }))
+            }
+        }
+
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/blocks", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_string(read_mock_file!("get_blocks_body")))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!(
+                "{}/addresses/scripthashes/transactions",
+                BASE_WALLET_API_V1
+            )))
+            .respond_with(AbortingResponder(abort_handle))
+            .mount(&mock_server)
+            .await;
+
+        let client = BlockchainClient::new(setup_test_connection(mock_server.uri()));
+
+        // A stop gap above the batch size requires a second batch
+        let result = client.full_sync(&account, Some(60), Some(abort_registration)).await;
+        assert!(matches!(result, Err(Error::SyncCancelled)));
+
+        let scripthashes_requests = mock_server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|request| request.url.path().ends_with("/addresses/scripthashes/transactions"))
+            .count();
+        assert_eq!(scripthashes_requests, 1);
+        assert!(!account.has_sync_data().await);
+    }
+
     #[tokio::test]
     async fn test_get_maximum_gap_size_mainnet() {
         let account = set_test_account_for_mainnet(ScriptType::NativeSegwit, "m/84'/0'/0'");
@@ -1550,7 +5673,7 @@ mod tests {
         let client = BlockchainClient::new(api_client.clone());
 
         // do full sync
-        let update = client.full_sync(&account, Some(20)).await.unwrap();
+        let update = client.full_sync(&account, Some(20), None).await.unwrap();
         account
             .apply_update(update)
             .await
@@ -1574,7 +5697,7 @@ mod tests {
         let client = BlockchainClient::new(api_client.as_ref().clone());
 
         // do full sync
-        let update = client.full_sync(&account, None).await.unwrap();
+        let update = client.full_sync(&account, None, None).await.unwrap();
         account
             .apply_update(update)
             .await
@@ -1582,7 +5705,7 @@ mod tests {
             .unwrap();
 
         let txid = "6b62ad31e219c9dab4d7e24a0803b02bbc5d86ba53f6f02aa6de0f301b718e88".to_string();
-        let transaction_details = account.get_transaction(txid).await.unwrap();
+        let transaction_details = account.get_transaction(txid, None).await.unwrap();
         assert_eq!(transaction_details.fees.unwrap(), 141);
     }
 
@@ -1635,6 +5758,28 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn get_address_for_internal_keychain() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let external = account.get_address_for_keychain(KeychainKind::External, Some(0)).await;
+        let internal = account.get_address_for_keychain(KeychainKind::Internal, Some(0)).await;
+
+        assert_eq!(internal.keychain, KeychainKind::Internal);
+        assert_ne!(internal.address, external.address);
+        assert_eq!(
+            internal.address,
+            account
+                .get_wallet()
+                .await
+                .peek_address(KeychainKind::Internal, 0)
+                .address
+        );
+
+        let next_internal = account.get_address_for_keychain(KeychainKind::Internal, None).await;
+        assert_eq!(next_internal.address, internal.address);
+    }
+
     #[tokio::test]
     async fn get_last_unused_address() {
         let account = set_test_account(ScriptType::Taproot, "m/86'/1'/0'");
@@ -1677,4 +5822,18 @@ mod tests {
                 .await
         );
     }
+
+    #[tokio::test]
+    async fn get_is_script_owned_by_account() {
+        let account = set_test_account(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let address = account.get_next_receive_address().await.unwrap();
+        assert!(account.owns_script(&address.script_pubkey()).await);
+
+        let foreign_script = ScriptBuf::from_hex("0014bc67f585a4e23ed0895782b55b164e210b089691").unwrap();
+        assert!(!account.owns_script(&foreign_script).await);
+
+        let non_standard_script = ScriptBuf::from_hex("51").unwrap();
+        assert!(!account.owns_script(&non_standard_script).await);
+    }
 }
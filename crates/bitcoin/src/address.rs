@@ -1,3 +1,4 @@
+use andromeda_api::bitcoin_address::ApiBitcoinAddressCreationPayload;
 use bdk_wallet::{Balance, KeychainKind};
 
 use crate::transactions::TransactionDetails;
@@ -9,3 +10,13 @@ pub struct AddressDetails {
     pub balance: Balance,
     pub keychain: KeychainKind,
 }
+
+/// An address of the API pool that received funds on-chain, along with the
+/// payload of the address replacing it
+pub struct UsedPoolAddress {
+    /// API identifier of the pool address, to be provided to
+    /// `update_bitcoin_address`
+    pub id: String,
+    pub index: u64,
+    pub replacement: ApiBitcoinAddressCreationPayload,
+}
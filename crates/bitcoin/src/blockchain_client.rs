@@ -1,27 +1,53 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::Arc,
+    time::Duration,
+};
 
-use crate::{account::Account, error::Error, storage::WalletPersisterConnector};
+use crate::{
+    account::Account, error::Error, storage::WalletPersisterConnector, transactions::TransactionDetails,
+    utils::parse_txid,
+};
 use andromeda_api::transaction::RecommendedFees;
 use andromeda_api::{
     transaction::{BroadcastMessage, ExchangeRateOrTransactionTime},
     ProtonWalletApiClient,
 };
+use andromeda_common::{utils::now, Network};
 use andromeda_esplora::{AsyncClient, EsploraAsyncExt};
-use async_std::sync::RwLockReadGuard;
-use bdk_chain::spk_client::SyncRequest;
+use async_std::sync::{RwLock, RwLockReadGuard};
+use bdk_chain::spk_client::{FullScanRequest, SyncRequest};
 use bdk_wallet::{
     bitcoin::{Transaction, Txid},
     chain::spk_client::{FullScanResponse, SyncResponse},
     KeychainKind, PersistedWallet, WalletPersister,
 };
 use bitcoin::ScriptBuf;
+use futures::future::{AbortRegistration, Abortable};
 use serde::{Deserialize, Serialize};
 
-pub const DEFAULT_STOP_GAP: usize = 50;
 pub const PARALLEL_REQUESTS: usize = 5;
 
+pub const FAST_FEE_BLOCK_TARGET: u32 = 1;
+pub const MEDIUM_FEE_BLOCK_TARGET: u32 = 6;
+pub const SLOW_FEE_BLOCK_TARGET: u32 = 144;
+
+/// Default duration during which fee estimations are served from cache
+pub const FEES_ESTIMATION_TTL: Duration = Duration::from_secs(60);
+
+/// Fee estimations along with the time they were fetched at
+type FeesEstimationCache = Option<(Duration, HashMap<String, f64>)>;
+
 #[derive(Clone)]
-pub struct BlockchainClient(AsyncClient);
+pub struct BlockchainClient<E = AsyncClient> {
+    client: E,
+    /// Maximum number of esplora requests sent in parallel during partial syncs
+    parallel_requests: usize,
+    /// Last fetched fee estimations, shared between the client's clones
+    fees_estimation_cache: Arc<RwLock<FeesEstimationCache>>,
+    fees_estimation_ttl: Duration,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(non_snake_case)]
@@ -30,19 +56,202 @@ pub struct MinimumFees {
     pub MinimumIncrementalFee: f32,
 }
 
-impl BlockchainClient {
-    pub fn new(proton_api_client: ProtonWalletApiClient) -> Self {
-        let client = AsyncClient::from_client(proton_api_client);
-        BlockchainClient(client)
+/// Conventional fee rates buckets (sat/vB), respectively targeting next
+/// block, next hour and next day confirmation
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeBuckets {
+    pub fast: f32,
+    pub medium: f32,
+    pub slow: f32,
+}
+
+/// Outcome of a mempool acceptance test of a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceptCheck {
+    pub allowed: bool,
+    /// Reason the transaction would be rejected for, e.g. a too low fee or a
+    /// non-standard script
+    pub reject_reason: Option<String>,
+}
+
+/// Progress of a full sync, emitted as the account's scripts are handed to
+/// esplora
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncProgress {
+    /// Number of scripts queried so far
+    pub scanned_scripts: usize,
+    /// Estimated number of scripts to query: the revealed ones of each
+    /// keychain plus the stop gap. It grows if activity is found above it.
+    pub total_estimate: usize,
+}
+
+/// Keychains whose scripts are scanned by full syncs. Receive-only wallets
+/// can skip the internal keychain, as they never generate change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncScope {
+    pub external: bool,
+    pub internal: bool,
+}
+
+impl Default for SyncScope {
+    fn default() -> Self {
+        SyncScope {
+            external: true,
+            internal: true,
+        }
     }
+}
 
-    pub fn inner(&self) -> &AsyncClient {
-        &self.0
+impl SyncScope {
+    /// Scope only scanning the external keychain
+    pub fn external_only() -> Self {
+        SyncScope {
+            external: true,
+            internal: false,
+        }
     }
 
-    /// Given a stop gap (10 currently, hard-coded) and a descriptor, we query
-    /// transactions for each script pub key until we reach the stop gap,
-    /// incrementing address index each time. After fetching those
+    /// Returns the keychains in scope
+    pub fn keychains(&self) -> Vec<KeychainKind> {
+        [
+            (KeychainKind::External, self.external),
+            (KeychainKind::Internal, self.internal),
+        ]
+        .into_iter()
+        .filter_map(|(keychain, in_scope)| in_scope.then_some(keychain))
+        .collect()
+    }
+}
+
+/// Summary of what changed in an account's wallet after applying a sync
+/// update
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncReport {
+    /// Transactions that were not known by the wallet before the update
+    pub new_txs: Vec<Txid>,
+    /// Already known transactions that were unconfirmed and are now confirmed
+    pub confirmed_txs: Vec<Txid>,
+    /// Already known transactions that were confirmed and are not anymore
+    pub reorged_txs: Vec<Txid>,
+    /// Height of the wallet's chain tip after the update
+    pub tip_height: u32,
+}
+
+impl SyncReport {
+    /// Builds the report by comparing the confirmation status of the wallet's
+    /// transactions before and after the update
+    pub fn from_confirmation_status(
+        before: &BTreeMap<Txid, bool>,
+        after: &BTreeMap<Txid, bool>,
+        tip_height: u32,
+    ) -> Self {
+        let mut report = SyncReport {
+            tip_height,
+            ..Default::default()
+        };
+
+        for (txid, confirmed) in after {
+            match before.get(txid) {
+                None => report.new_txs.push(*txid),
+                Some(false) if *confirmed => report.confirmed_txs.push(*txid),
+                Some(true) if !*confirmed => report.reorged_txs.push(*txid),
+                _ => {}
+            }
+        }
+
+        report
+    }
+}
+
+/// Runs a sync future, stopping it with `Error::SyncCancelled` at its next
+/// await point (i.e. between esplora requests) if the matching `AbortHandle`
+/// is aborted. No update is returned in that case, so nothing gets applied.
+async fn abortable<T, E, F>(future: F, abort_registration: Option<AbortRegistration>) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, E>>,
+    Error: From<E>,
+{
+    match abort_registration {
+        Some(abort_registration) => Ok(Abortable::new(future, abort_registration)
+            .await
+            .map_err(|_| Error::SyncCancelled)??),
+        None => Ok(future.await?),
+    }
+}
+
+/// Returns the estimated fee rate for a given block target. When the target is
+/// missing from the estimation map, we use the estimation of the closest lower
+/// target, since it is always at least as high as the one we are looking for,
+/// or of the closest higher one if there is no lower target.
+fn get_fee_rate_for_target(fees_estimation: &HashMap<String, f64>, target: u32) -> Option<f32> {
+    let targets = fees_estimation
+        .iter()
+        .filter_map(|(key, fee_rate)| key.parse::<u32>().ok().map(|key| (key, *fee_rate)))
+        .collect::<Vec<_>>();
+
+    targets
+        .iter()
+        .filter(|(key, _)| *key <= target)
+        .max_by_key(|(key, _)| *key)
+        .or_else(|| targets.iter().min_by_key(|(key, _)| *key))
+        .map(|(_, fee_rate)| *fee_rate as f32)
+}
+
+/// Maps a fee estimation map to fast/medium/slow buckets, using the provided
+/// fallback fee rate when the map has no target. Buckets are kept ordered, i.e.
+/// fast >= medium >= slow, even if the map isn't.
+fn fee_buckets_from_estimation(fees_estimation: &HashMap<String, f64>, fallback: f32) -> FeeBuckets {
+    let slow = get_fee_rate_for_target(fees_estimation, SLOW_FEE_BLOCK_TARGET).unwrap_or(fallback);
+    let medium = get_fee_rate_for_target(fees_estimation, MEDIUM_FEE_BLOCK_TARGET)
+        .unwrap_or(fallback)
+        .max(slow);
+    let fast = get_fee_rate_for_target(fees_estimation, FAST_FEE_BLOCK_TARGET)
+        .unwrap_or(fallback)
+        .max(medium);
+
+    FeeBuckets { fast, medium, slow }
+}
+
+impl<E: EsploraAsyncExt + Sync> BlockchainClient<E> {
+    /// Builds a client syncing through the given esplora implementation
+    fn with_esplora_client(client: E) -> Self {
+        BlockchainClient {
+            client,
+            parallel_requests: PARALLEL_REQUESTS,
+            fees_estimation_cache: Arc::new(RwLock::new(None)),
+            fees_estimation_ttl: FEES_ESTIMATION_TTL,
+        }
+    }
+
+    /// Sets the maximum number of esplora requests sent in parallel during
+    /// partial syncs, `PARALLEL_REQUESTS` by default. Lower it for
+    /// rate-limited servers. At least one request is always allowed.
+    ///
+    /// Full syncs ignore it: esplora's `full_scan` takes no parallelism and
+    /// batches script queries on its own.
+    pub fn with_parallel_requests(mut self, parallel_requests: usize) -> Self {
+        self.parallel_requests = parallel_requests.max(1);
+        self
+    }
+
+    pub fn parallel_requests(&self) -> usize {
+        self.parallel_requests
+    }
+
+    /// Sets the duration during which fee estimations are served from cache,
+    /// `FEES_ESTIMATION_TTL` by default
+    pub fn with_fees_estimation_ttl(mut self, ttl: Duration) -> Self {
+        self.fees_estimation_ttl = ttl;
+        self
+    }
+
+    pub fn inner(&self) -> &E {
+        &self.client
+    }
+
+    /// Given a stop gap (the account's gap limit by default) and a descriptor,
+    /// we query transactions for each script pub key until we reach the stop
+    /// gap, incrementing address index each time. After fetching those
     /// transactions, we can query the blocks to check their confirmation. We
     /// get outpoints to track for spending and we also get unused addresses
     /// list
@@ -56,22 +265,75 @@ impl BlockchainClient {
     ///   reuse so we won't encounter this issue often. We should still offer
     ///   the possibility to manually trigger a new full sync via a button in
     ///   the UI.
-    /// - Transaction received on an address above stop gap: Stop Gap is
-    ///   hardcoded so far. We should soon offer to change the stop gap setting
-    ///   for a given account, so that he can find transactions sent above the
-    ///   previously defined one.
+    /// - Transaction received on an address above stop gap: the account's gap
+    ///   limit can be raised, so that the user can find transactions sent
+    ///   above the previously defined one.
+    ///
+    /// Only the keychains in the account's sync scope are scanned, see
+    /// [`Account::set_sync_scope`].
+    ///
+    /// The sync can be cancelled by aborting the `AbortHandle` paired with
+    /// `abort_registration`, in which case `Error::SyncCancelled` is returned.
     pub async fn full_sync<'a, C, P>(
         &self,
         account: &Account<C, P>,
         stop_gap: Option<usize>,
+        abort_registration: Option<AbortRegistration>,
+    ) -> Result<FullScanResponse<KeychainKind>, Error>
+    where
+        C: WalletPersisterConnector<P>,
+        P: WalletPersister,
+    {
+        self.full_sync_with_progress(account, stop_gap, |_| {}, abort_registration)
+            .await
+    }
+
+    /// Same as [`BlockchainClient::full_sync`], calling `on_progress` for
+    /// each script queried so that the UI can display the sync progress.
+    pub async fn full_sync_with_progress<'a, C, P, F>(
+        &self,
+        account: &Account<C, P>,
+        stop_gap: Option<usize>,
+        on_progress: F,
+        abort_registration: Option<AbortRegistration>,
     ) -> Result<FullScanResponse<KeychainKind>, Error>
     where
         C: WalletPersisterConnector<P>,
         P: WalletPersister,
+        F: Fn(SyncProgress) + Send + 'static,
     {
+        let stop_gap = match stop_gap {
+            Some(stop_gap) => stop_gap,
+            None => account.get_gap_limit(),
+        };
+        let keychains = account.get_sync_scope().await.keychains();
         let read_lock = account.get_wallet().await;
-        let request = read_lock.start_full_scan();
-        let update = self.0.full_scan(request, stop_gap.unwrap_or(DEFAULT_STOP_GAP)).await?;
+
+        let total_estimate = keychains
+            .iter()
+            .map(|&keychain| {
+                read_lock
+                    .derivation_index(keychain)
+                    .map_or(0, |index| index as usize + 1)
+                    + stop_gap
+            })
+            .sum::<usize>();
+
+        let request = keychains.into_iter().fold(
+            FullScanRequest::builder().chain_tip(read_lock.local_chain().tip()),
+            |request, keychain| request.spks_for_keychain(keychain, read_lock.unbounded_spk_iter(keychain)),
+        );
+
+        let mut scanned_scripts = 0;
+        let request = request.inspect(move |_, _, _| {
+            scanned_scripts += 1;
+            on_progress(SyncProgress {
+                scanned_scripts,
+                total_estimate: total_estimate.max(scanned_scripts),
+            });
+        });
+
+        let update = abortable(self.client.full_scan(request, stop_gap), abort_registration).await?;
         Ok(update)
     }
 
@@ -81,10 +343,13 @@ impl BlockchainClient {
     ///
     /// # Notes
     ///
-    /// This has to be done on top of a full sync.
+    /// This has to be done on top of a full sync. Like
+    /// [`BlockchainClient::full_sync`], it can be cancelled through
+    /// `abort_registration`.
     pub async fn partial_sync<'a, P>(
         &self,
         wallet: RwLockReadGuard<'a, PersistedWallet<P>>,
+        abort_registration: Option<AbortRegistration>,
     ) -> Result<SyncResponse, Error>
     where
         P: WalletPersister,
@@ -106,7 +371,7 @@ impl BlockchainClient {
             .outpoints(utxos.into_iter())
             .txids(unconfirmed_txids.into_iter());
 
-        let update = self.0.sync(request, PARALLEL_REQUESTS).await?;
+        let update = abortable(self.client.sync(request, self.parallel_requests), abort_registration).await?;
 
         Ok(update)
     }
@@ -120,10 +385,16 @@ impl BlockchainClient {
             .chain_tip(wallet.local_chain().tip())
             .spks(spks_to_sync);
 
-        let update = self.0.sync(request, PARALLEL_REQUESTS).await?;
+        let update = self.client.sync(request, self.parallel_requests).await?;
 
         Ok(update)
     }
+}
+
+impl BlockchainClient {
+    pub fn new(proton_api_client: ProtonWalletApiClient) -> Self {
+        Self::with_esplora_client(AsyncClient::from_client(proton_api_client))
+    }
 
     /// Special minimal sync to check account existence
     pub async fn check_account_existence<'a, P>(
@@ -141,7 +412,7 @@ impl BlockchainClient {
             .map(|spks| spks.clone().take(stop_gap).collect::<Vec<_>>())
             .unwrap_or_default();
 
-        let results = self.0.many_scripthash_txs(spks).await.ok();
+        let results = self.client.many_scripthash_txs(spks).await.ok();
 
         if let Some(results) = results {
             return Ok(results.values().any(|(_index, txs)| !txs.is_empty()));
@@ -155,7 +426,7 @@ impl BlockchainClient {
     where
         P: WalletPersister,
     {
-        let tip_hash = self.0.get_tip_hash().await?;
+        let tip_hash = self.client.get_tip_hash().await?;
         let latest_chekpoint_hash = wallet.latest_checkpoint().hash();
 
         Ok(tip_hash != latest_chekpoint_hash)
@@ -164,7 +435,7 @@ impl BlockchainClient {
     /// Returns mempool minimum fee, minimum relay tx fee and incremental relay
     /// fee in sat/vB instead of BTC/kB
     pub async fn get_minimum_fees(&self) -> Result<MinimumFees, Error> {
-        let mempool_info = self.0.get_mempool_info().await?;
+        let mempool_info = self.client.get_mempool_info().await?;
         let minimum_broadcast_fee = f32::max(
             mempool_info.MempoolMinFee * 100000.0,
             mempool_info.MinRelayTxFee * 100000.0,
@@ -177,20 +448,74 @@ impl BlockchainClient {
         })
     }
 
-    /// Returns fee estimations in a Map
-    pub async fn get_fees_estimation(&self) -> Result<HashMap<String, f64>, Error> {
-        let fees = self.0.get_fee_estimates().await?;
+    /// Returns fee estimations in a Map. Estimations fetched less than the
+    /// configured TTL ago are returned from cache, unless `force_refresh` is
+    /// set.
+    pub async fn get_fees_estimation(&self, force_refresh: bool) -> Result<HashMap<String, f64>, Error> {
+        if !force_refresh {
+            if let Some((fetched_at, fees)) = self.fees_estimation_cache.read().await.as_ref() {
+                if now().saturating_sub(*fetched_at) < self.fees_estimation_ttl {
+                    return Ok(fees.clone());
+                }
+            }
+        }
+
+        let fees = self.client.get_fee_estimates().await?;
+        *self.fees_estimation_cache.write().await = Some((now(), fees.clone()));
 
         Ok(fees)
     }
 
+    /// Returns fast/medium/slow fee rates buckets, derived from the 1, 6 and
+    /// 144 blocks targets of fee estimation, or the closest available ones.
+    /// When estimation map has no target, minimum broadcast fee is used instead
+    pub async fn get_fee_buckets(&self) -> Result<FeeBuckets, Error> {
+        let fees_estimation = self.get_fees_estimation(false).await?;
+
+        // Any parsable target is enough to derive every bucket
+        let fallback = if get_fee_rate_for_target(&fees_estimation, FAST_FEE_BLOCK_TARGET).is_none() {
+            self.get_minimum_fees().await?.MinimumBroadcastFee
+        } else {
+            0.0
+        };
+
+        Ok(fee_buckets_from_estimation(&fees_estimation, fallback))
+    }
+
     /// Returns recommended fees
     pub async fn get_recommended_fees(&self) -> Result<RecommendedFees, Error> {
-        let recommended_fees = self.0.get_recommended_fees().await?;
+        let recommended_fees = self.client.get_recommended_fees().await?;
 
         Ok(recommended_fees)
     }
 
+    /// Returns details of any transaction given its txid, even if it doesn't
+    /// belong to any of the user's accounts
+    pub async fn get_transaction(&self, txid: String, network: Network) -> Result<TransactionDetails, Error> {
+        let txid = parse_txid(&txid)?;
+
+        let api_tx = self
+            .client
+            .get_api_tx_info(&txid)
+            .await?
+            .ok_or(Error::TransactionNotFound)?;
+        let tip_height = self.client.get_height().await?;
+
+        TransactionDetails::from_foreign_api_tx(api_tx, network.into(), tip_height)
+    }
+
+    /// Checks whether a signed transaction would be accepted in the mempool
+    /// without broadcasting it, so that rejections (fee too low,
+    /// non-standard scripts...) can be caught beforehand
+    pub async fn test_accept(&self, transaction: &Transaction) -> Result<AcceptCheck, Error> {
+        let result = self.client.test_mempool_accept(transaction).await?;
+
+        Ok(AcceptCheck {
+            allowed: result.Allowed == 1,
+            reject_reason: result.RejectReason,
+        })
+    }
+
     /// Broadcasts a provided transaction
     #[allow(clippy::too_many_arguments)]
     pub async fn broadcast(
@@ -206,7 +531,7 @@ impl BlockchainClient {
         recipients: Option<HashMap<String, String>>,
         is_anonymous: Option<u8>,
     ) -> Result<(), Error> {
-        self.0
+        self.client
             .broadcast(
                 &transaction,
                 wallet_id,
@@ -224,3 +549,274 @@ impl BlockchainClient {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        str::FromStr,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use andromeda_api::{tests::utils::setup_test_connection, BASE_WALLET_API_V1};
+    use andromeda_common::{Network, ScriptType};
+    use andromeda_esplora::{error::Error as EsploraError, EsploraAsyncExt};
+    use async_trait::async_trait;
+    use bdk_chain::spk_client::{FullScanRequest, FullScanResponse, SyncRequest, SyncResponse};
+    use bdk_wallet::{
+        bitcoin::{
+            absolute::LockTime,
+            bip32::{DerivationPath, Xpriv},
+            transaction::Version,
+            NetworkKind, Transaction,
+        },
+        serde_json,
+    };
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::{fee_buckets_from_estimation, AcceptCheck, BlockchainClient, FeeBuckets, PARALLEL_REQUESTS};
+    use crate::{account::Account, mnemonic::Mnemonic, read_mock_file, storage::MemoryPersisted};
+
+    /// Esplora stub recording the parallelism requested by each sync
+    #[derive(Clone, Default)]
+    struct RecordingEsplora {
+        sync_parallel_requests: Arc<Mutex<Vec<usize>>>,
+    }
+
+    #[async_trait]
+    impl EsploraAsyncExt for RecordingEsplora {
+        async fn full_scan<K: Ord + Clone + Send, R: Into<FullScanRequest<K>> + Send>(
+            &self,
+            _request: R,
+            _stop_gap: usize,
+        ) -> Result<FullScanResponse<K>, EsploraError> {
+            Ok(FullScanResponse::default())
+        }
+
+        async fn sync<I: Send, R: Into<SyncRequest<I>> + Send>(
+            &self,
+            _request: R,
+            parallel_requests: usize,
+        ) -> Result<SyncResponse, EsploraError> {
+            self.sync_parallel_requests.lock().unwrap().push(parallel_requests);
+            Ok(SyncResponse::default())
+        }
+    }
+
+    #[test]
+    fn should_map_fees_estimation_to_buckets() {
+        let fees_estimation = HashMap::from([
+            ("1".to_string(), 25.5),
+            ("2".to_string(), 20.1),
+            ("3".to_string(), 18.0),
+            ("6".to_string(), 12.2),
+            ("25".to_string(), 6.0),
+            ("144".to_string(), 2.3),
+            ("504".to_string(), 1.1),
+        ]);
+
+        assert_eq!(
+            fee_buckets_from_estimation(&fees_estimation, 1.0),
+            FeeBuckets {
+                fast: 25.5,
+                medium: 12.2,
+                slow: 2.3,
+            }
+        );
+    }
+
+    #[test]
+    fn should_use_closest_available_target_when_map_is_sparse() {
+        let fees_estimation = HashMap::from([("3".to_string(), 18.0), ("25".to_string(), 6.0)]);
+
+        // No target lower than 1, so the closest higher one is used
+        assert_eq!(
+            fee_buckets_from_estimation(&fees_estimation, 1.0),
+            FeeBuckets {
+                fast: 18.0,
+                medium: 18.0,
+                slow: 6.0,
+            }
+        );
+    }
+
+    #[test]
+    fn should_keep_buckets_ordered() {
+        let fees_estimation = HashMap::from([("144".to_string(), 8.0), ("504".to_string(), 2.0)]);
+        let buckets = fee_buckets_from_estimation(&fees_estimation, 1.0);
+        assert_eq!(
+            buckets,
+            FeeBuckets {
+                fast: 8.0,
+                medium: 8.0,
+                slow: 8.0,
+            }
+        );
+
+        // Non-monotonic estimation
+        let fees_estimation = HashMap::from([("1".to_string(), 5.0), ("6".to_string(), 9.0), ("144".to_string(), 3.0)]);
+        let buckets = fee_buckets_from_estimation(&fees_estimation, 1.0);
+        assert!(buckets.fast >= buckets.medium && buckets.medium >= buckets.slow);
+    }
+
+    #[test]
+    fn should_use_fallback_when_map_is_empty() {
+        assert_eq!(
+            fee_buckets_from_estimation(&HashMap::new(), 1.0),
+            FeeBuckets {
+                fast: 1.0,
+                medium: 1.0,
+                slow: 1.0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn should_configure_parallel_requests() {
+        let mock_server = MockServer::start().await;
+        let client = BlockchainClient::new(setup_test_connection(mock_server.uri()));
+        assert_eq!(client.parallel_requests(), PARALLEL_REQUESTS);
+
+        let client = client.with_parallel_requests(12);
+        assert_eq!(client.parallel_requests(), 12);
+
+        let client = client.with_parallel_requests(0);
+        assert_eq!(client.parallel_requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_pass_parallel_requests_to_partial_syncs_only() {
+        let mnemonic = Mnemonic::from_string(
+            "onion ancient develop team busy purchase salmon robust danger wheat rich empower".to_string(),
+        )
+        .unwrap();
+        let master_secret_key = Xpriv::new_master(NetworkKind::Test, &mnemonic.inner().to_seed("")).unwrap();
+        let account = Account::new(
+            master_secret_key,
+            Network::Regtest,
+            ScriptType::NativeSegwit,
+            DerivationPath::from_str("m/84'/1'/0'").unwrap(),
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        let esplora = RecordingEsplora::default();
+        let client = BlockchainClient::with_esplora_client(esplora.clone()).with_parallel_requests(3);
+
+        client.partial_sync(account.get_wallet().await, None).await.unwrap();
+        client.sync_spks(&account.get_wallet().await, Vec::new()).await.unwrap();
+        client.full_sync(&account, None, None).await.unwrap();
+
+        // Full scan doesn't take any parallelism, only the two syncs were recorded
+        assert_eq!(*esplora.sync_parallel_requests.lock().unwrap(), vec![3, 3]);
+    }
+
+    #[tokio::test]
+    async fn should_cache_fees_estimation() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/transactions/fee-estimates", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Code": 1000,
+                "FeeEstimates": {"1": 25.5, "6": 12.2, "144": 2.3},
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let ttl = Duration::from_secs(30);
+        let client = BlockchainClient::new(setup_test_connection(mock_server.uri())).with_fees_estimation_ttl(ttl);
+        let requests_count = || async { mock_server.received_requests().await.unwrap().len() };
+
+        let fees = client.get_fees_estimation(false).await.unwrap();
+        assert_eq!(fees.get("6"), Some(&12.2));
+        client.get_fees_estimation(false).await.unwrap();
+        assert_eq!(requests_count().await, 1);
+
+        client.get_fees_estimation(true).await.unwrap();
+        assert_eq!(requests_count().await, 2);
+
+        // Age the cached estimations past the TTL instead of waiting for it
+        if let Some((fetched_at, _)) = client.fees_estimation_cache.write().await.as_mut() {
+            *fetched_at -= ttl;
+        }
+        client.get_fees_estimation(false).await.unwrap();
+        assert_eq!(requests_count().await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_foreign_transaction() {
+        let mock_server = MockServer::start().await;
+        let txid = "6bbfc06ef911e4b2fffe1150fa8f3729b3ee52c78ef21093b5ae45544ff690fa";
+
+        let response_contents = read_mock_file!("get_transaction_info_body");
+        Mock::given(method("GET"))
+            .and(path(format!("{}/transactions/{}/info", BASE_WALLET_API_V1, txid)))
+            .respond_with(ResponseTemplate::new(200).set_body_string(response_contents))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path(format!("{}/blocks/tip/height", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Code": 1000,
+                "Height": 871886,
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let api_client = setup_test_connection(mock_server.uri());
+        let client = BlockchainClient::new(api_client);
+
+        let transaction = client
+            .get_transaction(txid.to_string(), Network::Bitcoin)
+            .await
+            .unwrap();
+
+        assert_eq!(transaction.txid.to_string(), txid);
+        assert_eq!(transaction.outputs.len(), 2);
+        assert_eq!(
+            transaction.outputs[0].address.clone().unwrap().to_string(),
+            "14r4Mw3hzsvrGvP7ecGBSU3ooBBeWTGX6W"
+        );
+        assert!(transaction.outputs.iter().all(|output| !output.is_mine));
+        assert_eq!(transaction.inputs[0].previous_output.clone().unwrap().value, 415699);
+        assert_eq!(transaction.fees, Some(1420));
+        assert_eq!(transaction.confirmations, Some(10));
+    }
+
+    #[tokio::test]
+    async fn should_surface_mempool_reject_reason() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/transactions/test-mempool-accept", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Code": 1000,
+                "MempoolAccept": {
+                    "Allowed": 0,
+                    "RejectReason": "min relay fee not met, 0 < 110",
+                },
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = BlockchainClient::new(setup_test_connection(mock_server.uri()));
+        let transaction = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![],
+            output: vec![],
+        };
+
+        assert_eq!(
+            client.test_accept(&transaction).await.unwrap(),
+            AcceptCheck {
+                allowed: false,
+                reject_reason: Some("min relay fee not met, 0 < 110".to_string()),
+            }
+        );
+    }
+}
@@ -1,18 +1,22 @@
 use std::fmt::Debug;
 
+use andromeda_api::error::{is_retryable_muon_error, Error as ApiError};
+use andromeda_common::{Network, ScriptType};
 use andromeda_esplora::error::Error as EsploraClientError;
 use bdk_wallet::{
     bitcoin::{
+        absolute::{ConversionError as LockTimeConversionError, LockTime},
         address::ParseError as BitcoinAddressParseError,
-        bip32::Error as Bip32Error,
+        bip32::{DerivationPath, Error as Bip32Error},
         psbt::{Error as PsbtError, ExtractTxError},
+        relative::TimeOverflowError,
         OutPoint,
     },
     chain::local_chain::CannotConnectError,
     descriptor::DescriptorError,
     error::{BuildFeeBumpError, MiniscriptPsbtError},
     signer::SignerError,
-    tx_builder::AddUtxoError,
+    tx_builder::{AddForeignUtxoError, AddUtxoError},
 };
 pub use bdk_wallet::{
     coin_selection::InsufficientFunds as InsufficientFundsError, error::CreateTxError, keys::bip39::Error as Bip39Error,
@@ -23,6 +27,8 @@ use bitcoin::address::FromScriptError;
 pub enum Error {
     #[error("Account wasn't found")]
     AccountNotFound,
+    #[error("No account is bound to the transaction builder")]
+    NoAccountBound,
     #[error("An error occurred when trying to create persisted wallet")]
     CreateWithPersistError, /* (#[from] CreateWithPersistError) */
     #[error("An error occurred when trying to load persisted wallet")]
@@ -32,11 +38,15 @@ pub enum Error {
     #[error("An error related to Miniscript occurred: \n\t{0}")]
     MiniscriptPsbt(#[from] MiniscriptPsbtError),
     #[error("An error occurred when creating tx: \n\t{0:?}")]
-    CreateTx(#[from] CreateTxError),
+    CreateTx(CreateTxError),
+    #[error("Insufficient funds: {available} sat available of {needed} sat needed")]
+    InsufficientFunds { needed: u64, available: u64 },
     #[error("An error occurred when bumping fees: \n\t{0}")]
     BuildFeeBump(#[from] BuildFeeBumpError),
     #[error("An error occurred when adding UTXO: \n\t{0}")]
     AddUtxo(#[from] AddUtxoError),
+    #[error("An error occurred when adding a foreign UTXO: \n\t{0}")]
+    AddForeignUtxo(#[from] AddForeignUtxoError),
     #[error("An error occurred when signing the transaction: \n\t{0}")]
     Signer(#[from] SignerError),
     #[error("Cannot connect update: update does not have a common checkpoint with the original chain.: \n\t{0}")]
@@ -47,6 +57,8 @@ pub enum Error {
     Bip39(#[from] Bip39Error),
     #[error("An error occurred in esplora client: \n\t{0}")]
     EsploraClient(#[from] EsploraClientError),
+    #[error("An error occurred in Proton Wallet API: \n\t{0}")]
+    Api(#[from] ApiError),
     #[error("Invalid Hex data returned: \n\t{0}")]
     HexToArray(#[from] bitcoin::hashes::hex::HexToArrayError),
     #[error("Invalid Hex data returned: \n\t{0}")]
@@ -63,12 +75,92 @@ pub enum Error {
     Psbt(#[from] PsbtError),
     #[error("Address is invalid: {0}")]
     InvalidAddress(String),
+    #[error("Txid is invalid: {0}")]
+    InvalidTxId(String),
+    #[error("Transaction hex is invalid: {0}")]
+    InvalidTransactionHex(String),
+    #[error("Amount is invalid, expected a number of sats: {0}")]
+    InvalidAmount(String),
+    #[error("Amount exceeds the maximum number of sats: {0}")]
+    AmountOverflow(String),
+    #[error("Fee rate is invalid: {0} sat/vB")]
+    InvalidFeeRate(f32),
+    #[error("Invalid entropy length: got {got_bits} bits, expected 128, 160, 192, 224 or 256")]
+    InvalidEntropyLength { got_bits: usize },
+    #[error("Amount exceeds the 21M BTC supply: {0} sat")]
+    AmountAboveMaxMoney(u64),
+    #[error("Address doesn't belong to the account: {0}")]
+    AddressNotOwned(String),
+    #[error("Address index is out of the unhardened derivation range")]
+    AddressIndexOutOfRange,
+    #[error("Derivation path {derivation_path} doesn't match {script_type:?} script type purpose")]
+    ScriptTypePathMismatch {
+        script_type: ScriptType,
+        derivation_path: DerivationPath,
+    },
+    #[error("Account was already added with {existing:?} script type, not {requested:?}")]
+    AccountScriptTypeMismatch {
+        existing: ScriptType,
+        requested: ScriptType,
+    },
+    #[error("Address is for {got} network while {expected} is expected")]
+    AddressNetworkMismatch { expected: Network, got: Network },
+    #[error("Descriptor is invalid: {0}")]
+    InvalidDescriptor(String),
+    #[error("Extended public key is not a valid account key: {0}")]
+    InvalidAccountXpub(String),
+    #[error("Another key was already added as account {0}")]
+    AccountXpubMismatch(DerivationPath),
+    #[error("Watch-only account cannot sign transactions")]
+    WatchOnlyCannotSign,
+    #[error("Message signing is not supported for taproot addresses")]
+    MessageSigningNotSupported,
     #[error("Data is invalid: {0:?}")]
     InvalidData(Vec<u8>),
     #[error("Transaction was not found")]
     TransactionNotFound,
+    #[error("Recipient was not found: {0}")]
+    RecipientNotFound(String),
     #[error("UTXO was not found: {0:?}")]
     UtxoNotFound(OutPoint),
+    #[error("Locktime is invalid: \n\t{0}")]
+    LockTimeConversion(#[from] LockTimeConversionError),
+    #[error("Relative locktime is too large: \n\t{0}")]
+    RelativeLockTimeOverflow(#[from] TimeOverflowError),
+    #[error("Locktime {requested} doesn't allow spending coins timelocked until {required}")]
+    LocktimeNotSatisfied { requested: LockTime, required: LockTime },
+    #[error("Sync was cancelled")]
+    SyncCancelled,
+    #[error("Invalid proof of reserves: {0}")]
+    InvalidProofOfReserves(String),
+    #[error("Invalid account snapshot: {0}")]
+    InvalidSnapshot(String),
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
+
+impl Error {
+    /// Returns whether the operation failed because of a transient API error
+    /// and can be retried, see [`ApiError::is_retryable`]. Syncs aren't
+    /// retried internally, callers running them decide from it.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Api(error) | Error::EsploraClient(EsploraClientError::ApiError(error)) => error.is_retryable(),
+            Error::EsploraClient(EsploraClientError::MuonError(error)) => is_retryable_muon_error(error),
+            _ => false,
+        }
+    }
+}
+
+impl From<CreateTxError> for Error {
+    fn from(error: CreateTxError) -> Self {
+        match error {
+            CreateTxError::CoinSelection(InsufficientFundsError { needed, available }) => Error::InsufficientFunds {
+                needed: needed.to_sat(),
+                available: available.to_sat(),
+            },
+            CreateTxError::LockTime { requested, required } => Error::LocktimeNotSatisfied { requested, required },
+            error => Error::CreateTx(error),
+        }
+    }
+}
@@ -3,8 +3,10 @@ pub mod address;
 pub mod bdk_wallet_ext;
 pub mod blockchain_client;
 pub mod error;
+pub mod message;
 pub mod mnemonic;
 pub mod payment_link;
+pub mod proof_of_reserves;
 pub mod psbt;
 pub mod storage;
 pub mod transaction_builder;
@@ -12,6 +14,9 @@ pub mod transactions;
 pub mod utils;
 pub mod wallet;
 
+#[cfg(test)]
+mod tests;
+
 // Define a type alias for the common result type used in this crate
 type Result<T> = std::result::Result<T, error::Error>;
 
@@ -20,7 +25,8 @@ pub use bdk_wallet::rusqlite::Connection;
 #[doc(hidden)]
 pub use bdk_wallet::{
     bitcoin::{
-        bip32::{ChildNumber, DerivationPath, Xpriv},
+        address::FromScriptError,
+        bip32::{ChildNumber, DerivationPath, Xpriv, Xpub},
         block::Header as BlockHeader,
         blockdata::{
             constants::genesis_block,
@@ -28,7 +34,7 @@ pub use bdk_wallet::{
         },
         consensus::Params as ConsensusParams,
         Address, Amount, BlockHash, Network as BdkNetwork, OutPoint, ScriptBuf, Sequence, Transaction, TxIn, TxOut,
-        Witness,
+        Txid, Witness,
     },
     chain::ConfirmationBlockTime,
     keys::{
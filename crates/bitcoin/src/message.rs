@@ -0,0 +1,252 @@
+use std::str::FromStr;
+
+use bitcoin::{
+    absolute::LockTime,
+    base64::{engine::general_purpose::STANDARD, Engine},
+    consensus::{deserialize, serialize},
+    ecdsa,
+    hashes::{sha256, Hash, HashEngine},
+    key::{Keypair, TapTweak},
+    opcodes::{all::OP_RETURN, OP_0},
+    script::Builder,
+    secp256k1::{Message, Secp256k1, SecretKey},
+    sighash::{EcdsaSighashType, Prevouts, SighashCache, TapSighashType},
+    taproot::{self, TapNodeHash},
+    transaction::Version,
+    Address, AddressType, Amount, OutPoint, PublicKey, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Witness,
+    XOnlyPublicKey,
+};
+
+use crate::error::Error;
+
+/// Tag of the BIP-322 message hash
+const BIP322_TAG: &[u8] = b"BIP0322-signed-message";
+
+/// Returns the BIP-322 tagged hash of the message
+fn message_hash(message: &str) -> sha256::Hash {
+    let tag = sha256::Hash::hash(BIP322_TAG);
+
+    let mut engine = sha256::Hash::engine();
+    engine.input(tag.as_byte_array());
+    engine.input(tag.as_byte_array());
+    engine.input(message.as_bytes());
+
+    sha256::Hash::from_engine(engine)
+}
+
+/// Builds the virtual `to_spend` transaction committing to the message and
+/// paying to the address script
+fn to_spend(script_pubkey: &ScriptBuf, message: &str) -> Transaction {
+    Transaction {
+        version: Version(0),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint::null(),
+            script_sig: Builder::new()
+                .push_opcode(OP_0)
+                .push_slice(message_hash(message).to_byte_array())
+                .into_script(),
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: script_pubkey.clone(),
+        }],
+    }
+}
+
+/// Builds the virtual `to_sign` transaction spending `to_spend`, whose
+/// input witness is the message signature
+fn to_sign(to_spend: &Transaction) -> Transaction {
+    Transaction {
+        version: Version(0),
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint::new(to_spend.compute_txid(), 0),
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: Builder::new().push_opcode(OP_RETURN).into_script(),
+        }],
+    }
+}
+
+/// Signs a message following BIP-322 simple signature format, with the key
+/// of the provided address. The returned signature is the base64-encoded
+/// witness of the `to_sign` transaction.
+///
+/// P2TR addresses committing to a script tree are signed for their key path,
+/// the key being tweaked with the tree's `merkle_root`.
+///
+/// # Notes
+///
+/// Only P2WPKH and P2TR (key path) addresses are supported.
+pub(crate) fn sign_message(
+    message: &str,
+    address: &Address,
+    secret_key: &SecretKey,
+    merkle_root: Option<TapNodeHash>,
+) -> Result<String, Error> {
+    let secp = Secp256k1::new();
+    let script_pubkey = address.script_pubkey();
+
+    let to_spend = to_spend(&script_pubkey, message);
+    let to_sign = to_sign(&to_spend);
+    let mut sighash_cache = SighashCache::new(&to_sign);
+
+    let witness = match address.address_type() {
+        Some(AddressType::P2wpkh) => {
+            let sighash = sighash_cache
+                .p2wpkh_signature_hash(0, &script_pubkey, Amount::ZERO, EcdsaSighashType::All)
+                .map_err(anyhow::Error::from)?;
+            let signature = secp.sign_ecdsa(&Message::from_digest(sighash.to_byte_array()), secret_key);
+
+            Witness::p2wpkh(&ecdsa::Signature::sighash_all(signature), &secret_key.public_key(&secp))
+        }
+        Some(AddressType::P2tr) => {
+            let keypair = Keypair::from_secret_key(&secp, secret_key)
+                .tap_tweak(&secp, merkle_root)
+                .to_inner();
+
+            let sighash = sighash_cache
+                .taproot_key_spend_signature_hash(0, &Prevouts::All(&to_spend.output), TapSighashType::Default)
+                .map_err(anyhow::Error::from)?;
+            let signature = secp.sign_schnorr_no_aux_rand(&Message::from_digest(sighash.to_byte_array()), &keypair);
+
+            Witness::p2tr_key_spend(&taproot::Signature {
+                signature,
+                sighash_type: TapSighashType::Default,
+            })
+        }
+        _ => return Err(Error::MessageSigningNotSupported),
+    };
+
+    Ok(STANDARD.encode(serialize(&witness)))
+}
+
+/// Verifies a BIP-322 simple signature of a message against an address.
+///
+/// Returns `false` when the signature doesn't match, including when it is
+/// malformed, and an error if the address can't be parsed or isn't a
+/// P2WPKH or P2TR one.
+pub fn verify_message(message: &str, signature: &str, address: &str) -> Result<bool, Error> {
+    let address = Address::from_str(address)?.assume_checked();
+
+    let Some(witness) = STANDARD
+        .decode(signature)
+        .ok()
+        .and_then(|bytes| deserialize::<Witness>(&bytes).ok())
+    else {
+        return Ok(false);
+    };
+
+    let secp = Secp256k1::verification_only();
+    let script_pubkey = address.script_pubkey();
+
+    let to_spend = to_spend(&script_pubkey, message);
+    let to_sign = to_sign(&to_spend);
+    let mut sighash_cache = SighashCache::new(&to_sign);
+
+    match address.address_type() {
+        Some(AddressType::P2wpkh) => {
+            if witness.len() != 2 {
+                return Ok(false);
+            }
+
+            let (Ok(signature), Ok(pubkey)) = (
+                ecdsa::Signature::from_slice(&witness[0]),
+                PublicKey::from_slice(&witness[1]),
+            ) else {
+                return Ok(false);
+            };
+
+            if !pubkey.compressed || !address.is_related_to_pubkey(&pubkey) {
+                return Ok(false);
+            }
+
+            let sighash = sighash_cache
+                .p2wpkh_signature_hash(0, &script_pubkey, Amount::ZERO, signature.sighash_type)
+                .map_err(anyhow::Error::from)?;
+
+            Ok(secp
+                .verify_ecdsa(
+                    &Message::from_digest(sighash.to_byte_array()),
+                    &signature.signature,
+                    &pubkey.inner,
+                )
+                .is_ok())
+        }
+        Some(AddressType::P2tr) => {
+            if witness.len() != 1 {
+                return Ok(false);
+            }
+
+            let (Ok(signature), Ok(output_key)) = (
+                taproot::Signature::from_slice(&witness[0]),
+                XOnlyPublicKey::from_slice(&script_pubkey.as_bytes()[2..]),
+            ) else {
+                return Ok(false);
+            };
+
+            let sighash = sighash_cache
+                .taproot_key_spend_signature_hash(0, &Prevouts::All(&to_spend.output), signature.sighash_type)
+                .map_err(anyhow::Error::from)?;
+
+            Ok(secp
+                .verify_schnorr(
+                    &signature.signature,
+                    &Message::from_digest(sighash.to_byte_array()),
+                    &output_key,
+                )
+                .is_ok())
+        }
+        _ => Err(Error::MessageSigningNotSupported),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bitcoin::{Address, PrivateKey};
+
+    use super::{message_hash, sign_message, verify_message};
+
+    // Test vectors from BIP-322
+    const TEST_ADDRESS: &str = "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l";
+    const TEST_PRIVATE_KEY: &str = "L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k";
+    const HELLO_WORLD_SIGNATURE: &str = "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=";
+
+    #[test]
+    fn should_compute_message_hash() {
+        assert_eq!(
+            message_hash("").to_string(),
+            "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1"
+        );
+        assert_eq!(
+            message_hash("Hello World").to_string(),
+            "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a"
+        );
+    }
+
+    #[test]
+    fn should_sign_message_with_p2wpkh_address() {
+        let address = Address::from_str(TEST_ADDRESS).unwrap().assume_checked();
+        let private_key = PrivateKey::from_wif(TEST_PRIVATE_KEY).unwrap();
+
+        let signature = sign_message("Hello World", &address, &private_key.inner, None).unwrap();
+        assert_eq!(signature, HELLO_WORLD_SIGNATURE);
+    }
+
+    #[test]
+    fn should_verify_message() {
+        assert!(verify_message("Hello World", HELLO_WORLD_SIGNATURE, TEST_ADDRESS).unwrap());
+
+        assert!(!verify_message("Hello World!", HELLO_WORLD_SIGNATURE, TEST_ADDRESS).unwrap());
+        assert!(!verify_message("Hello World", "not a signature", TEST_ADDRESS).unwrap());
+    }
+}
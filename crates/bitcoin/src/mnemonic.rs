@@ -11,6 +11,9 @@ use bdk_wallet::{
 
 use crate::error::Error;
 
+/// Entropy lengths allowed by BIP39, in bits.
+const ALLOWED_ENTROPY_BITS: [usize; 5] = [128, 160, 192, 224, 256];
+
 #[derive(Debug)]
 pub struct Mnemonic {
     inner: BdkMnemonic,
@@ -96,7 +99,28 @@ impl Mnemonic {
         Ok(Mnemonic { inner: mnemonic })
     }
 
+    /// Creates a `Mnemonic` from the given entropy.
+    ///
+    /// # Arguments
+    ///
+    /// * `entropy` - raw entropy bytes. BIP39 only allows 128, 160, 192, 224
+    ///   or 256 bits (16, 20, 24, 28 or 32 bytes), any other length returns
+    ///   `Error::InvalidEntropyLength`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use andromeda_bitcoin::mnemonic::Mnemonic;
+    ///
+    /// let result = Mnemonic::new_with(&[0u8; 16]).unwrap();
+    /// assert_eq!(result.as_words().len(), 12);
+    /// ```
     pub fn new_with(entropy: &[u8]) -> Result<Self, Error> {
+        let got_bits = entropy.len() * 8;
+        if !ALLOWED_ENTROPY_BITS.contains(&got_bits) {
+            return Err(Error::InvalidEntropyLength { got_bits });
+        }
+
         let mnemonic = BdkMnemonic::from_entropy_in(Language::English, entropy)?;
         Ok(Mnemonic { inner: mnemonic })
     }
@@ -282,6 +306,25 @@ mod tests {
         });
     }
 
+    #[test]
+    fn should_create_mnemonic_from_entropy() {
+        let mnemonic = Mnemonic::new_with(&[0u8; 16]).unwrap();
+        assert_eq!(
+            mnemonic.as_string(),
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        );
+
+        let mnemonic = Mnemonic::new_with(&[0u8; 32]).unwrap();
+        assert_eq!(mnemonic.as_words().len(), 24);
+    }
+
+    #[test]
+    fn should_throw_when_invalid_entropy_length() {
+        let mnemonic_error = Mnemonic::new_with(&[0u8; 17]).err().unwrap();
+
+        assert!(matches!(mnemonic_error, Error::InvalidEntropyLength { got_bits: 136 }));
+    }
+
     #[test]
     fn should_return_word_vector() {
         // lang is in French
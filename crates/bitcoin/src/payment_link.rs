@@ -5,7 +5,7 @@ use std::{
 };
 
 use andromeda_common::{BitcoinUnit, Network};
-use bitcoin::Address;
+use bitcoin::{amount::Denomination, Address, Amount};
 use urlencoding::{decode, encode};
 
 use super::Result;
@@ -62,8 +62,11 @@ const MESSAGE_KEY: &str = "message";
 impl PaymentLink {
     /// Helper function to generate a query string from optional BIP-21
     /// parameters.
+    ///
+    /// The amount is formatted from integer sats so that it never uses more
+    /// than 8 decimals nor scientific notation.
     fn get_query_string(amount: &Option<u64>, label: &Option<String>, message: &Option<String>) -> String {
-        let str_amount = amount.map(|am| convert_amount(am as f64, BitcoinUnit::SATS, BitcoinUnit::BTC).to_string());
+        let str_amount = amount.map(|am| Amount::from_sat(am).to_string_in(Denomination::Bitcoin));
         vec![
             (AMOUNT_KEY, str_amount),
             (LABEL_KEY, label.clone()),
@@ -98,6 +101,41 @@ impl PaymentLink {
         }
     }
 
+    /// Returns the payload to encode in a QR code: the address for a bare
+    /// address, the URI otherwise.
+    ///
+    /// When `uppercase_bech32` is set, bech32 addresses are uppercased as it
+    /// lets QR encoders use the denser alphanumeric mode. The `bitcoin:`
+    /// scheme and query parameters are left untouched, as are base58
+    /// addresses which are case-sensitive.
+    pub fn to_qr_string(&self, uppercase_bech32: bool) -> String {
+        let format_address = |address: &Address| {
+            if uppercase_bech32 && address.script_pubkey().is_witness_program() {
+                address.to_string().to_ascii_uppercase()
+            } else {
+                address.to_string()
+            }
+        };
+
+        match self {
+            Self::BitcoinAddress(address) => format_address(address),
+            Self::BitcoinURI {
+                address,
+                amount,
+                label,
+                message,
+            } => {
+                let params_str = Self::get_query_string(amount, label, message);
+                if !params_str.is_empty() {
+                    format!("bitcoin:{}?{}", format_address(address), params_str)
+                } else {
+                    format!("bitcoin:{}", format_address(address))
+                }
+            }
+            Self::LightningURI { .. } | Self::UnifiedURI { .. } => self.to_string(),
+        }
+    }
+
     /// Returns the address as a string, regardless of the type of payment link.
     pub fn to_address_string(&self) -> String {
         match self {
@@ -158,18 +196,23 @@ impl PaymentLink {
         Ok(PaymentLink::BitcoinAddress(address))
     }
 
+    /// Creates a BIP-21 URI, rejecting amounts above the 21M BTC supply.
     pub fn new_bitcoin_uri(
         address: Address,
         amount: Option<u64>,
         label: Option<String>,
         message: Option<String>,
-    ) -> PaymentLink {
-        PaymentLink::BitcoinURI {
+    ) -> Result<PaymentLink> {
+        if let Some(amount) = amount.filter(|amount| *amount > Amount::MAX_MONEY.to_sat()) {
+            return Err(Error::AmountAboveMaxMoney(amount));
+        }
+
+        Ok(PaymentLink::BitcoinURI {
             address,
             amount,
             label,
             message,
-        }
+        })
     }
 }
 
@@ -253,6 +296,59 @@ mod tests {
         assert!(bitcoin_address == *TEST_ADDRESS);
     }
 
+    #[test]
+    fn to_qr_string_uppercases_bech32_only() {
+        let payment_link = PaymentLink::BitcoinAddress(test_address());
+        assert_eq!(payment_link.to_qr_string(false), TEST_ADDRESS);
+        assert_eq!(payment_link.to_qr_string(true), TEST_ADDRESS.to_ascii_uppercase());
+
+        let payment_link = PaymentLink::BitcoinURI {
+            address: test_address(),
+            amount: Some(166727),
+            label: Some("label tests".to_string()),
+            message: None,
+        };
+        let qr_string = payment_link.to_qr_string(true);
+        assert_eq!(
+            qr_string,
+            "bitcoin:TB1QNMSYCZN68T628M4UCT5NQGJR7VF3W6MC0LVKFN?amount=0.00166727&label=label%20tests"
+        );
+        assert_eq!(
+            PaymentLink::try_parse(qr_string, Network::Testnet).unwrap(),
+            payment_link
+        );
+
+        let legacy_address = "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn";
+        let payment_link =
+            PaymentLink::try_parse(format!("bitcoin:{}?amount=0.001", legacy_address), Network::Testnet).unwrap();
+        assert_eq!(
+            payment_link.to_qr_string(true),
+            format!("bitcoin:{}?amount=0.001", legacy_address)
+        );
+    }
+
+    #[test]
+    fn new_bitcoin_uri_formats_amount_in_btc() {
+        let payment_link = PaymentLink::new_bitcoin_uri(test_address(), Some(1), None, None).unwrap();
+        assert_eq!(
+            payment_link.to_string(),
+            "bitcoin:tb1qnmsyczn68t628m4uct5nqgjr7vf3w6mc0lvkfn?amount=0.00000001"
+        );
+
+        let payment_link =
+            PaymentLink::new_bitcoin_uri(test_address(), Some(2_100_000_000_000_000), None, None).unwrap();
+        assert_eq!(
+            payment_link.to_string(),
+            "bitcoin:tb1qnmsyczn68t628m4uct5nqgjr7vf3w6mc0lvkfn?amount=21000000"
+        );
+    }
+
+    #[test]
+    fn new_bitcoin_uri_rejects_amount_above_max_money() {
+        let result = PaymentLink::new_bitcoin_uri(test_address(), Some(2_100_000_000_000_001), None, None);
+        assert!(matches!(result, Err(Error::AmountAboveMaxMoney(2_100_000_000_000_001))));
+    }
+
     #[test]
     fn test_invalid_address_parse_error() {
         let invalid_address = "bitcoin:invalid_address";
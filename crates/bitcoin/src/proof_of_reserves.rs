@@ -0,0 +1,119 @@
+use bdk_wallet::bitcoin::{
+    hashes::{sha256d, Hash},
+    opcodes::{
+        all::{OP_EQUAL, OP_HASH160},
+        OP_TRUE,
+    },
+    psbt::Input as PsbtInput,
+    script::Builder,
+    secp256k1::Secp256k1,
+    sighash::Prevouts,
+    Amount, OutPoint, ScriptBuf, TxOut, Txid, Weight, Witness,
+};
+use miniscript::interpreter::Interpreter;
+
+use crate::{error::Error, psbt::Psbt};
+
+/// Prefix of the message committed to by the challenge input
+const CHALLENGE_PREFIX: &str = "Proof-of-Reserves: ";
+
+/// Returns the outpoint of the challenge input, derived from the message so
+/// that a proof can't be reused for another one
+fn challenge_outpoint(message: &str) -> OutPoint {
+    let hash = sha256d::Hash::hash(format!("{CHALLENGE_PREFIX}{message}").as_bytes());
+    OutPoint::new(Txid::from_raw_hash(hash), 0)
+}
+
+/// Returns the challenge input committing to the message, along with its
+/// satisfaction weight. It spends a non-existent output, which makes the
+/// proof transaction impossible to broadcast.
+pub(crate) fn challenge_input(message: &str) -> (OutPoint, PsbtInput, Weight) {
+    let input = PsbtInput {
+        witness_utxo: Some(TxOut {
+            value: Amount::ZERO,
+            script_pubkey: Builder::new().push_opcode(OP_TRUE).into_script(),
+        }),
+        final_script_sig: Some(ScriptBuf::new()),
+        final_script_witness: Some(Witness::new()),
+        ..Default::default()
+    };
+
+    (challenge_outpoint(message), input, Weight::ZERO)
+}
+
+/// Returns the script the proof transaction pays to: a P2SH of an all-zero
+/// hash, which can't be spent
+pub(crate) fn unspendable_script() -> ScriptBuf {
+    Builder::new()
+        .push_opcode(OP_HASH160)
+        .push_slice([0u8; 20])
+        .push_opcode(OP_EQUAL)
+        .into_script()
+}
+
+/// Verifies a BIP-127 style proof of reserves for the given message and
+/// returns the proven amount, in sats.
+///
+/// The proof must spend the challenge input first, then the proven UTXOs
+/// into a single unspendable output, and every proven input must be signed.
+/// Whether the proven UTXOs are still unspent isn't checked, as it requires
+/// a blockchain client.
+pub fn verify_proof_of_reserves(psbt: &Psbt, message: &str) -> Result<u64, Error> {
+    let psbt = psbt.inner();
+    let tx = &psbt.unsigned_tx;
+
+    if tx.input.len() < 2 || tx.input[0].previous_output != challenge_outpoint(message) {
+        return Err(Error::InvalidProofOfReserves(
+            "first input doesn't commit to the message".to_string(),
+        ));
+    }
+
+    if tx.output.len() != 1 || tx.output[0].script_pubkey != unspendable_script() {
+        return Err(Error::InvalidProofOfReserves(
+            "proof should have a single unspendable output".to_string(),
+        ));
+    }
+
+    let prevouts = psbt
+        .inputs
+        .iter()
+        .zip(&tx.input)
+        .map(|(input, txin)| match (&input.witness_utxo, &input.non_witness_utxo) {
+            (Some(txout), _) => Some(txout.clone()),
+            (None, Some(prev_tx)) => prev_tx.output.get(txin.previous_output.vout as usize).cloned(),
+            (None, None) => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| Error::InvalidProofOfReserves("missing spent output of an input".to_string()))?;
+
+    let signed_tx = psbt.clone().extract_tx_unchecked_fee_rate();
+    let all_prevouts = Prevouts::All(&prevouts);
+    let secp = Secp256k1::verification_only();
+
+    for (index, (txin, prevout)) in signed_tx.input.iter().zip(&prevouts).enumerate().skip(1) {
+        let interpreter = Interpreter::from_txdata(
+            &prevout.script_pubkey,
+            &txin.script_sig,
+            &txin.witness,
+            txin.sequence,
+            signed_tx.lock_time,
+        )
+        .map_err(|e| Error::InvalidProofOfReserves(e.to_string()))?;
+
+        if let Some(error) = interpreter
+            .iter(&secp, &signed_tx, index, &all_prevouts)
+            .find_map(Result::err)
+        {
+            return Err(Error::InvalidProofOfReserves(error.to_string()));
+        }
+    }
+
+    let amount = prevouts.iter().skip(1).map(|prevout| prevout.value).sum::<Amount>();
+    if tx.output[0].value != amount {
+        return Err(Error::InvalidProofOfReserves(
+            "output doesn't match the proven amount".to_string(),
+        ));
+    }
+
+    Ok(amount.to_sat())
+}
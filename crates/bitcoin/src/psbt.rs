@@ -1,6 +1,12 @@
 use std::fmt::Debug;
 
-use bdk_wallet::bitcoin::psbt::Psbt as BdkPsbt;
+use bdk_wallet::{
+    bitcoin::{
+        psbt::{Input as BdkPsbtInput, Psbt as BdkPsbt},
+        Address, OutPoint, TxIn, TxOut,
+    },
+    KeychainKind, Wallet as BdkWallet,
+};
 use bitcoin::{Amount, Transaction};
 
 use crate::error::Error;
@@ -8,6 +14,35 @@ use crate::error::Error;
 #[derive(Clone, Debug)]
 pub struct Psbt(BdkPsbt);
 
+#[derive(Clone, Debug)]
+pub struct PsbtInput {
+    pub outpoint: OutPoint,
+    /// Value (sats) of the spent output, `None` if the PSBT doesn't contain it
+    pub value: Option<u64>,
+    pub address: Option<Address>,
+    pub is_mine: bool,
+    pub is_witness: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct PsbtOutput {
+    pub address: Option<Address>,
+    pub value: u64,
+    /// Whether the output pays to the internal keychain of the wallet
+    pub is_change: bool,
+}
+
+/// Returns the output spent by a PSBT input, from its witness or non-witness
+/// UTXO
+fn previous_output(txin: &TxIn, input: &BdkPsbtInput) -> Option<TxOut> {
+    input.witness_utxo.clone().or_else(|| {
+        input
+            .non_witness_utxo
+            .as_ref()
+            .and_then(|tx| tx.output.get(txin.previous_output.vout as usize).cloned())
+    })
+}
+
 impl From<BdkPsbt> for Psbt {
     fn from(value: BdkPsbt) -> Self {
         Psbt(value)
@@ -31,7 +66,96 @@ impl Psbt {
         Ok(self.0.clone().fee()?)
     }
 
+    /// Returns the fee (sats) paid by the PSBT, computed from the UTXOs it
+    /// contains. Returns `None` if the value of any of its inputs is unknown.
+    pub fn fee_from_utxos(&self) -> Option<u64> {
+        let inputs_value = self
+            .0
+            .unsigned_tx
+            .input
+            .iter()
+            .zip(self.0.inputs.iter())
+            .map(|(txin, input)| previous_output(txin, input).map(|output| output.value))
+            .sum::<Option<Amount>>()?;
+        let outputs_value = self
+            .0
+            .unsigned_tx
+            .output
+            .iter()
+            .map(|output| output.value)
+            .sum::<Amount>();
+
+        inputs_value.checked_sub(outputs_value).map(|fee| fee.to_sat())
+    }
+
     pub fn compute_tx_vbytes(&self) -> Result<u64, Error> {
         Ok(self.extract_tx()?.weight().to_vbytes_ceil())
     }
+
+    /// Estimates the virtual size of the transaction once signed, using the
+    /// maximum satisfaction weight of the wallet's descriptors for its inputs.
+    pub fn estimated_signed_vsize(&self, wallet: &BdkWallet) -> Result<u64, Error> {
+        let mut weight = self.0.unsigned_tx.weight();
+
+        for input in &self.0.unsigned_tx.input {
+            let keychain = wallet
+                .get_utxo(input.previous_output)
+                .map(|utxo| utxo.keychain)
+                .ok_or(Error::UtxoNotFound(input.previous_output))?;
+
+            weight += wallet
+                .public_descriptor(keychain)
+                .max_weight_to_satisfy()
+                .map_err(|e| Error::InvalidDescriptor(e.to_string()))?;
+        }
+
+        Ok(weight.to_vbytes_ceil())
+    }
+
+    /// Returns a breakdown of the PSBT inputs, flagged against the given
+    /// wallet, e.g. for a signing confirmation screen.
+    pub fn inputs(&self, wallet: &BdkWallet) -> Vec<PsbtInput> {
+        self.0
+            .unsigned_tx
+            .input
+            .iter()
+            .zip(self.0.inputs.iter())
+            .map(|(txin, input)| {
+                let previous_output = previous_output(txin, input);
+
+                PsbtInput {
+                    outpoint: txin.previous_output,
+                    value: previous_output.as_ref().map(|output| output.value.to_sat()),
+                    address: previous_output
+                        .as_ref()
+                        .and_then(|output| Address::from_script(&output.script_pubkey, wallet.network()).ok()),
+                    is_mine: previous_output
+                        .as_ref()
+                        .is_some_and(|output| wallet.is_mine(output.script_pubkey.clone())),
+                    is_witness: input.witness_utxo.is_some()
+                        || previous_output
+                            .as_ref()
+                            .is_some_and(|output| output.script_pubkey.is_witness_program()),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns a breakdown of the PSBT outputs, flagging change outputs of
+    /// the given wallet.
+    pub fn outputs(&self, wallet: &BdkWallet) -> Vec<PsbtOutput> {
+        self.0
+            .unsigned_tx
+            .output
+            .iter()
+            .map(|output| PsbtOutput {
+                address: Address::from_script(&output.script_pubkey, wallet.network()).ok(),
+                value: output.value.to_sat(),
+                is_change: matches!(
+                    wallet.derivation_of_spk(output.script_pubkey.clone()),
+                    Some((KeychainKind::Internal, _))
+                ),
+            })
+            .collect()
+    }
 }
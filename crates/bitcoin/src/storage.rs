@@ -1,4 +1,9 @@
-use std::{convert::Infallible, fmt::Debug};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    fmt::Debug,
+    sync::{Arc, Mutex, PoisonError},
+};
 
 pub use bdk_wallet::{chain::Merge, ChangeSet, WalletPersister};
 
@@ -43,3 +48,56 @@ impl WalletPersister for MemoryPersisted {
         Ok(())
     }
 }
+
+type InMemoryStore = Arc<Mutex<HashMap<String, ChangeSet>>>;
+
+/// Persister keeping a wallet's changes in memory, under its key. Unlike
+/// [`MemoryPersisted`], changes survive the wallet being reloaded.
+#[derive(Clone, Debug)]
+pub struct InMemoryPersister {
+    key: String,
+    store: InMemoryStore,
+}
+
+impl WalletPersister for InMemoryPersister {
+    type Error = Infallible;
+
+    fn initialize(persister: &mut Self) -> Result<ChangeSet, Self::Error> {
+        let store = persister.store.lock().unwrap_or_else(PoisonError::into_inner);
+
+        Ok(store.get(&persister.key).cloned().unwrap_or_default())
+    }
+
+    fn persist(persister: &mut Self, changeset: &ChangeSet) -> Result<(), Self::Error> {
+        let mut store = persister.store.lock().unwrap_or_else(PoisonError::into_inner);
+        store.entry(persister.key.clone()).or_default().merge(changeset.clone());
+
+        Ok(())
+    }
+}
+
+impl WalletPersisterConnector<InMemoryPersister> for InMemoryPersister {
+    fn connect(&self) -> InMemoryPersister {
+        self.clone()
+    }
+}
+
+/// Factory of [`InMemoryPersister`], sharing the same store between all of
+/// them, so that wallets and accounts can be exercised natively, e.g. in
+/// tests, without any storage backend
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryStorageFactory {
+    store: InMemoryStore,
+}
+
+impl InMemoryStorageFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl WalletConnectorFactory<InMemoryPersister, InMemoryPersister> for InMemoryStorageFactory {
+    fn build(self, key: String) -> InMemoryPersister {
+        InMemoryPersister { key, store: self.store }
+    }
+}
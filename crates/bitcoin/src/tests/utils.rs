@@ -0,0 +1,57 @@
+use std::str::FromStr;
+
+use bdk_wallet::{
+    bitcoin::{absolute::LockTime, transaction::Version, OutPoint, Transaction, TxIn, TxOut, Txid},
+    WalletPersister,
+};
+
+use crate::{account::Account, storage::WalletPersisterConnector};
+
+/// Time at which the first unconfirmed test transaction is seen
+const UNCONFIRMED_SEEN_AT: u64 = 1733468825;
+
+/// Returns an outpoint of a transaction that doesn't belong to test accounts,
+/// to be spent by the transactions they receive
+pub fn foreign_outpoint(vout: u32) -> OutPoint {
+    OutPoint::new(
+        Txid::from_str("6b62ad31e219c9dab4d7e24a0803b02bbc5d86ba53f6f02aa6de0f301b718e88").unwrap(),
+        vout,
+    )
+}
+
+/// Builds a transaction spending a foreign outpoint to the provided outputs
+pub fn funding_tx(output: Vec<TxOut>) -> Transaction {
+    Transaction {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: foreign_outpoint(0),
+            ..Default::default()
+        }],
+        output,
+    }
+}
+
+/// Adds the transactions to the account's wallet as unconfirmed ones, seen a
+/// second apart in the provided order
+pub async fn apply_unconfirmed_txs<C: WalletPersisterConnector<P>, P: WalletPersister>(
+    account: &Account<C, P>,
+    txs: impl IntoIterator<Item = Transaction>,
+) {
+    account
+        .get_mutable_wallet()
+        .await
+        .apply_unconfirmed_txs(txs.into_iter().zip(UNCONFIRMED_SEEN_AT..).collect::<Vec<_>>());
+}
+
+/// Adds an unconfirmed transaction paying to the provided outputs to the
+/// account's wallet, and returns it
+pub async fn receive_unconfirmed_tx<C: WalletPersisterConnector<P>, P: WalletPersister>(
+    account: &Account<C, P>,
+    output: Vec<TxOut>,
+) -> Transaction {
+    let tx = funding_tx(output);
+    apply_unconfirmed_txs(account, [tx.clone()]).await;
+
+    tx
+}
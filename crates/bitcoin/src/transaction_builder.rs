@@ -1,17 +1,21 @@
 use std::{fmt::Debug, str::FromStr, sync::Arc};
 
+use andromeda_common::{Network, ScriptType};
 use bdk_wallet::{
-    bitcoin::{absolute::LockTime, script::PushBytesBuf, Address, Amount, FeeRate, OutPoint, ScriptBuf},
+    bitcoin::{
+        absolute::LockTime, script::PushBytesBuf, Address, Amount, FeeRate, Network as BdkNetwork, OutPoint, ScriptBuf,
+        Weight,
+    },
     coin_selection::{
-        BranchAndBoundCoinSelection, CoinSelectionAlgorithm, InsufficientFunds, LargestFirstCoinSelection,
-        OldestFirstCoinSelection, SingleRandomDraw,
+        BranchAndBoundCoinSelection, CoinSelectionAlgorithm, LargestFirstCoinSelection, OldestFirstCoinSelection,
+        SingleRandomDraw,
     },
-    error::CreateTxError,
     tx_builder::{ChangeSpendPolicy, TxBuilder as BdkTxBuilder},
-    WalletPersister,
+    KeychainKind, WalletPersister,
 };
 use bitcoin::key::rand::RngCore;
 use hashbrown::HashSet;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use uuid::Uuid;
 
 use super::account::Account;
@@ -21,7 +25,7 @@ use crate::{
     storage::{MemoryPersisted, WalletPersisterConnector},
 };
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum CoinSelection {
     BranchAndBound,
     LargestFirst,
@@ -29,19 +33,19 @@ pub enum CoinSelection {
     Manual,
 }
 
-struct FixedRng(pub u32);
+struct FixedRng(pub u64);
 
 impl RngCore for FixedRng {
     fn next_u32(&mut self) -> u32 {
-        self.0
+        self.0 as u32
     }
 
     fn next_u64(&mut self) -> u64 {
-        self.0 as u64
+        self.0
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
-        for chunk in dest.chunks_mut(4) {
+        for chunk in dest.chunks_mut(8) {
             let bytes = self.0.to_le_bytes();
             for (i, byte) in chunk.iter_mut().enumerate() {
                 *byte = bytes[i];
@@ -58,6 +62,26 @@ impl RngCore for FixedRng {
 #[derive(Clone, Debug, PartialEq)]
 pub struct TmpRecipient(pub String, pub String, pub Amount);
 
+/// Issue with a recipient of the transaction builder, along with the index
+/// of the recipient it relates to
+#[derive(Clone, Debug, PartialEq)]
+pub enum RecipientError {
+    EmptyAddress {
+        index: usize,
+    },
+    InvalidAddress {
+        index: usize,
+    },
+    ZeroAmount {
+        index: usize,
+    },
+    /// Amount is below the dust limit of the recipient's script, in sats
+    Dust {
+        index: usize,
+        limit: u64,
+    },
+}
+
 /// BDK's implementation of Transaction builder is quite complete, but we need a
 /// struct that enables stateful transaction creation, so we just added a layer
 /// on top of it.
@@ -79,7 +103,7 @@ pub struct TxBuilder<C: WalletPersisterConnector<P>, P: WalletPersister = Memory
     account: Option<Arc<Account<C, P>>>,
     // A random number set on each tx builder instance to randomize coin selection on BNB fallback algorithm, while
     // keeping deterministic inside the same txbuilder
-    random_number: u32,
+    random_number: u64,
     /// A list of recipients for the transaction, including a uuid, their
     /// addresses and the amounts to send.
     pub recipients: Vec<TmpRecipient>,
@@ -96,6 +120,9 @@ pub struct TxBuilder<C: WalletPersisterConnector<P>, P: WalletPersister = Memory
     /// An optional script to which any leftover funds should be sent, if
     /// `drain_wallet` is enabled.
     pub drain_to: Option<ScriptBuf>,
+    /// An optional external address change is sent to, instead of the next
+    /// unused address of the account's internal keychain.
+    pub change_address: Option<String>,
     /// A flag indicating whether Replace-By-Fee (RBF) is enabled for this
     /// transaction.
     pub rbf_enabled: bool,
@@ -119,6 +146,7 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Clone for TxBuilder<C,
             fee_rate: self.fee_rate,
             drain_wallet: self.drain_wallet,
             drain_to: self.drain_to.clone(),
+            change_address: self.change_address.clone(),
             rbf_enabled: self.rbf_enabled,
             data: self.data.clone(),
             coin_selection: self.coin_selection.clone(),
@@ -127,16 +155,164 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Clone for TxBuilder<C,
     }
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "ChangeSpendPolicy")]
+enum ChangeSpendPolicyDef {
+    ChangeAllowed,
+    OnlyChange,
+    ChangeForbidden,
+}
+
+/// Serializable state of a TxBuilder, used to save a draft send and restore
+/// it later. The account isn't part of it and needs to be set again using
+/// `set_account` once restored.
+#[derive(Serialize, Deserialize)]
+struct TxBuilderState {
+    random_number: u64,
+    /// Recipients as (uuid, address, amount in sats)
+    recipients: Vec<(String, String, u64)>,
+    utxos_to_spend: Vec<OutPoint>,
+    #[serde(with = "ChangeSpendPolicyDef")]
+    change_policy: ChangeSpendPolicy,
+    /// Fee rate in sat/kwu
+    fee_rate: Option<u64>,
+    drain_wallet: bool,
+    drain_to: Option<ScriptBuf>,
+    #[serde(default)]
+    change_address: Option<String>,
+    rbf_enabled: bool,
+    data: Vec<u8>,
+    coin_selection: CoinSelection,
+    /// Consensus encoded locktime
+    locktime: Option<u32>,
+}
+
+impl<C: WalletPersisterConnector<P>, P: WalletPersister> From<&TxBuilder<C, P>> for TxBuilderState {
+    fn from(value: &TxBuilder<C, P>) -> Self {
+        TxBuilderState {
+            random_number: value.random_number,
+            recipients: value
+                .recipients
+                .iter()
+                .map(|TmpRecipient(uuid, address, amount)| (uuid.clone(), address.clone(), amount.to_sat()))
+                .collect(),
+            utxos_to_spend: value.utxos_to_spend.iter().copied().collect(),
+            change_policy: value.change_policy,
+            fee_rate: value.fee_rate.map(FeeRate::to_sat_per_kwu),
+            drain_wallet: value.drain_wallet,
+            drain_to: value.drain_to.clone(),
+            change_address: value.change_address.clone(),
+            rbf_enabled: value.rbf_enabled,
+            data: value.data.clone(),
+            coin_selection: value.coin_selection.clone(),
+            locktime: value.locktime.map(|locktime| locktime.to_consensus_u32()),
+        }
+    }
+}
+
+impl<C: WalletPersisterConnector<P>, P: WalletPersister> From<TxBuilderState> for TxBuilder<C, P> {
+    fn from(value: TxBuilderState) -> Self {
+        TxBuilder {
+            account: None,
+            random_number: value.random_number,
+            recipients: value
+                .recipients
+                .into_iter()
+                .map(|(uuid, address, amount)| TmpRecipient(uuid, address, Amount::from_sat(amount)))
+                .collect(),
+            utxos_to_spend: value.utxos_to_spend.into_iter().collect(),
+            change_policy: value.change_policy,
+            fee_rate: value.fee_rate.map(FeeRate::from_sat_per_kwu),
+            drain_wallet: value.drain_wallet,
+            drain_to: value.drain_to,
+            change_address: value.change_address,
+            rbf_enabled: value.rbf_enabled,
+            data: value.data,
+            coin_selection: value.coin_selection,
+            locktime: value.locktime.map(LockTime::from_consensus),
+        }
+    }
+}
+
+/// Only the builder's state is serialized, the account must be set again using
+/// `set_account` after deserialization
+impl<C: WalletPersisterConnector<P>, P: WalletPersister> Serialize for TxBuilder<C, P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TxBuilderState::from(self).serialize(serializer)
+    }
+}
+
+impl<'de, C: WalletPersisterConnector<P>, P: WalletPersister> Deserialize<'de> for TxBuilder<C, P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        TxBuilderState::deserialize(deserializer).map(TxBuilder::from)
+    }
+}
+
 pub struct ScriptAmount {
     pub script: ScriptBuf,
     pub amount: u64,
 }
 
+/// Change output of a created PSBT, derived from the account's internal
+/// keychain
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeInfo {
+    pub address: Address,
+    pub index: u32,
+}
+
+/// Number of vbytes the signed size estimation can be off by before the fee
+/// paid is considered as exceeding the requested fee rate
+const FEERATE_OVERPAYMENT_TOLERANCE_VBYTES: u64 = 2;
+
+/// Fee rate, in sat/vB, above which the user should confirm they really want
+/// to pay that much
+pub const HIGH_FEE_RATE_SAT_PER_VB: f32 = 1000.0;
+
+/// A created PSBT along with details the user might need to review before
+/// signing it
+#[derive(Clone, Debug)]
+pub struct PsbtDetails {
+    pub psbt: Psbt,
+    pub change: Option<ChangeInfo>,
+    /// Whether the transaction pays more than the requested fee rate, which
+    /// happens when a dust change got added to fees instead of being output
+    pub feerate_overpayment: bool,
+    /// Difference (sat/vB) between the effective and the requested fee rates
+    pub feerate_delta: f64,
+}
+
 struct AllocateBalanceAcc {
     remaining: Amount,
     recipients: Vec<TmpRecipient>,
 }
 
+/// Checks that an address belongs to the expected network. Addresses that
+/// cannot be parsed yet (e.g. being typed) are only rejected when the PSBT is
+/// created.
+fn check_address_network(address_str: &str, expected: Network) -> Result<(), Error> {
+    let Ok(address) = Address::from_str(address_str) else {
+        return Ok(());
+    };
+
+    if address.clone().require_network(expected.into()).is_ok() {
+        return Ok(());
+    }
+
+    let got = [
+        BdkNetwork::Bitcoin,
+        BdkNetwork::Testnet,
+        BdkNetwork::Signet,
+        BdkNetwork::Regtest,
+    ]
+    .into_iter()
+    .find(|network| address.is_valid_for_network(*network))
+    .map(Network::from)
+    .unwrap_or(expected);
+
+    Err(Error::AddressNetworkMismatch { expected, got })
+}
+
 /// This function remove allocated amount from the last recipient to the first
 /// one and returns an array of updated recipients
 fn correct_recipients_amounts(recipients: Vec<TmpRecipient>, amount_to_remove: Amount) -> Vec<TmpRecipient> {
@@ -168,6 +344,70 @@ fn correct_recipients_amounts(recipients: Vec<TmpRecipient>, amount_to_remove: A
     acc_result.recipients
 }
 
+/// Weight of the transaction fields not depending on inputs and outputs:
+/// version, locktime and single-byte input and output counts
+const TX_BASE_WEIGHT: Weight = Weight::from_wu(40);
+/// Weight of the segwit marker and flag
+const SEGWIT_HEADER_WEIGHT: Weight = Weight::from_wu(2);
+
+/// Returns the weight of an input spending the provided script type, assuming
+/// a 72 bytes ECDSA signature and a compressed pubkey, or a key path spend
+/// with default sighash for taproot. Non-segwit inputs weigh an extra unit
+/// for their empty witness when the transaction has a segwit input.
+fn input_weight(script_type: ScriptType, is_segwit_tx: bool) -> Weight {
+    let empty_witness = if is_segwit_tx { 1 } else { 0 };
+
+    Weight::from_wu(match script_type {
+        // outpoint, sequence, scriptSig with signature and pubkey
+        ScriptType::Legacy => (36 + 4 + 1 + 107) * 4 + empty_witness,
+        // outpoint, sequence, scriptSig with P2WPKH redeem script, witness
+        ScriptType::NestedSegwit => (36 + 4 + 1 + 23) * 4 + 108,
+        // outpoint, sequence, empty scriptSig, witness
+        ScriptType::NativeSegwit => (36 + 4 + 1) * 4 + 108,
+        // outpoint, sequence, empty scriptSig, schnorr signature witness
+        ScriptType::Taproot => (36 + 4 + 1) * 4 + 66,
+    })
+}
+
+/// Returns the weight of an output paying to the provided script type
+fn output_weight(script_type: ScriptType) -> Weight {
+    let script_len = match script_type {
+        ScriptType::Legacy => 25,
+        ScriptType::NestedSegwit => 23,
+        ScriptType::NativeSegwit => 22,
+        ScriptType::Taproot => 34,
+    };
+
+    // value, script length and script
+    Weight::from_vb_unchecked(8 + 1 + script_len)
+}
+
+/// Estimates the virtual size of a transaction spending inputs of the
+/// provided script types, without building it. This is meant for fee
+/// previews, e.g. before recipients are known.
+///
+/// As their script types aren't known, outputs (including change) are
+/// assumed to be of the same script type as the first input, which is
+/// usually the one of the wallet account.
+pub fn estimate_tx_vsize(inputs: &[ScriptType], outputs: usize, has_change: bool) -> usize {
+    let is_segwit_tx = inputs.iter().any(|script_type| *script_type != ScriptType::Legacy);
+    let output_script_type = inputs.first().copied().unwrap_or(ScriptType::NativeSegwit);
+    let outputs_count = outputs + usize::from(has_change);
+
+    let mut weight = TX_BASE_WEIGHT;
+    if is_segwit_tx {
+        weight += SEGWIT_HEADER_WEIGHT;
+    }
+
+    weight += inputs
+        .iter()
+        .map(|script_type| input_weight(*script_type, is_segwit_tx))
+        .sum::<Weight>();
+    weight += output_weight(output_script_type) * outputs_count as u64;
+
+    weight.to_vbytes_ceil() as usize
+}
+
 impl<C: WalletPersisterConnector<P>, P: WalletPersister> Default for TxBuilder<C, P> {
     fn default() -> Self {
         Self::new()
@@ -178,13 +418,14 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
     pub fn new() -> Self {
         TxBuilder {
             account: None,
-            random_number: bitcoin::key::rand::thread_rng().next_u32(),
+            random_number: bitcoin::key::rand::thread_rng().next_u64(),
             recipients: vec![TmpRecipient(Uuid::new_v4().to_string(), String::new(), Amount::ZERO)],
             utxos_to_spend: HashSet::new(),
             change_policy: ChangeSpendPolicy::ChangeAllowed,
             fee_rate: None,
             drain_wallet: false,
             drain_to: None,
+            change_address: None,
             rbf_enabled: true,
             locktime: None,
             coin_selection: CoinSelection::BranchAndBound,
@@ -192,7 +433,8 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
         }
     }
 
-    /// Sets the account to be used to finalise the transaction.
+    /// Sets the account to be used to finalise the transaction. It is required
+    /// to create a PSBT, which otherwise fails with [`Error::NoAccountBound`].
     ///
     /// ```rust, ignore
     /// let tx_builder = TxBuilder::new();
@@ -276,13 +518,66 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
         }
     }
 
+    /// Checks every recipient and reports all their issues at once, so that
+    /// each invalid one can be highlighted before building the transaction.
+    ///
+    /// When an account is set, an address from another network is reported
+    /// as invalid.
+    pub fn validate(&self) -> Result<(), Vec<RecipientError>> {
+        let network = self.account.as_ref().map(|account| account.get_network());
+
+        let errors = self
+            .recipients
+            .iter()
+            .enumerate()
+            .flat_map(|(index, TmpRecipient(_uuid, address_str, amount))| {
+                let mut errors = Vec::new();
+
+                let script_pubkey = if address_str.trim().is_empty() {
+                    errors.push(RecipientError::EmptyAddress { index });
+                    None
+                } else {
+                    let address = Address::from_str(address_str).ok().filter(|_| {
+                        network.map_or(true, |network| check_address_network(address_str, network).is_ok())
+                    });
+
+                    if address.is_none() {
+                        errors.push(RecipientError::InvalidAddress { index });
+                    }
+
+                    address.map(|address| address.assume_checked().script_pubkey())
+                };
+
+                if *amount == Amount::ZERO {
+                    errors.push(RecipientError::ZeroAmount { index });
+                } else if let Some(script_pubkey) = script_pubkey {
+                    let limit = script_pubkey.minimal_non_dust();
+
+                    if *amount < limit {
+                        errors.push(RecipientError::Dust {
+                            index,
+                            limit: limit.to_sat(),
+                        });
+                    }
+                }
+
+                errors
+            })
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     pub async fn constrain_recipient_amounts(&self) -> Self {
         if self.account.is_some() {
             let result = self.create_draft_psbt(true).await;
 
-            if let Err(Error::CreateTx(CreateTxError::CoinSelection(InsufficientFunds { needed, available }))) = result
-            {
-                let amount_to_remove = needed - available;
+            if let Err(Error::InsufficientFunds { needed, available }) = result {
+                let amount_to_remove = Amount::from_sat(needed - available);
 
                 return TxBuilder {
                     recipients: correct_recipients_amounts(self.recipients.clone(), amount_to_remove),
@@ -294,14 +589,22 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
         self.clone()
     }
 
-    /// Update either recipient's address or amount at provided index
-    ///  
+    /// Update either recipient's address or amount at provided index. The
+    /// recipient keeps its uuid.
+    ///
+    /// When an account is set, an address from another network is rejected
+    /// with [`Error::AddressNetworkMismatch`].
+    ///
     /// ```rust, ignore
     /// let tx_builder = TxBuilder::new();
     /// ...
     /// let updated = tx_builder.update_recipient(1usize, Some("bc1..."), Some(18788.0), Some(BitcoinUnit::SATS)).unwrap();
     /// ```
-    pub fn update_recipient(&self, index: usize, update: (Option<String>, Option<u64>)) -> Self {
+    pub fn update_recipient(&self, index: usize, update: (Option<String>, Option<u64>)) -> Result<Self, Error> {
+        if let (Some(account), Some(address_str)) = (&self.account, &update.0) {
+            check_address_network(address_str, account.get_network())?;
+        }
+
         let mut recipients = self.recipients.clone();
         let TmpRecipient(uuid, prev_script, prev_amount) = recipients[index].clone();
 
@@ -311,10 +614,29 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
             update.1.map_or(prev_amount, Amount::from_sat),
         );
 
-        TxBuilder {
+        Ok(TxBuilder {
             recipients,
             ..self.clone()
-        }
+        })
+    }
+
+    /// Update either recipient's address or amount, identifying it by its
+    /// uuid rather than its index, which might change when the list is
+    /// reordered or a recipient is removed.
+    ///
+    /// ```rust, ignore
+    /// let tx_builder = TxBuilder::new();
+    /// ...
+    /// let updated = tx_builder.update_recipient_by_uuid(&uuid, (None, Some(18788))).unwrap();
+    /// ```
+    pub fn update_recipient_by_uuid(&self, uuid: &str, update: (Option<String>, Option<u64>)) -> Result<Self, Error> {
+        let index = self
+            .recipients
+            .iter()
+            .position(|TmpRecipient(recipient_uuid, _, _)| recipient_uuid == uuid)
+            .ok_or_else(|| Error::RecipientNotFound(uuid.to_string()))?;
+
+        self.update_recipient(index, update)
     }
 
     /// Update one recipient's amount to max, meaning it sets remaining balance
@@ -391,7 +713,9 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
         }
     }
 
-    /// Adds a locktime to the transaction
+    /// Adds a locktime to the transaction. Creating the PSBT fails with
+    /// [`Error::LocktimeNotSatisfied`] when it is lower than the one required
+    /// by the account's spending policy.
     pub fn add_locktime(&self, locktime: LockTime) -> Self {
         TxBuilder {
             locktime: Some(locktime),
@@ -407,6 +731,42 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
         }
     }
 
+    /// Sets the script remaining funds are sent to, once recipients and fees
+    /// are paid. Combined with outpoints to spend and no recipient, it allows
+    /// to sweep a set of UTXOs to a single output.
+    pub fn set_drain_to(&self, drain_to: Option<ScriptBuf>) -> Self {
+        TxBuilder {
+            drain_to,
+            ..self.clone()
+        }
+    }
+
+    /// Sets an external address change is sent to, e.g. to move it to cold
+    /// storage, instead of an address derived from the account's internal
+    /// keychain. Passing `None` restores the default change destination.
+    ///
+    /// When an account is set, an address from another network is rejected
+    /// with [`Error::AddressNetworkMismatch`].
+    ///
+    /// # Notes
+    ///
+    /// A script set with `set_drain_to` takes precedence over this address.
+    pub fn set_change_address(&self, change_address: Option<String>) -> Result<Self, Error> {
+        if let Some(address_str) = &change_address {
+            Address::from_str(address_str)?;
+
+            // Otherwise network is checked when the PSBT is created
+            if let Some(account) = &self.account {
+                check_address_network(address_str, account.get_network())?;
+            }
+        }
+
+        Ok(TxBuilder {
+            change_address,
+            ..self.clone()
+        })
+    }
+
     /// Do not spend change outputs. This effectively adds all the change
     /// outputs to the "unspendable" list. See TxBuilder.unspendable.
     ///
@@ -420,10 +780,44 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
         }
     }
 
-    /// Set a custom fee rate.
-    pub fn set_fee_rate(&self, sat_per_vb: u64) -> Self {
+    /// Set a custom fee rate, in sat/vB.
+    ///
+    /// # Notes
+    ///
+    /// Rates above [`HIGH_FEE_RATE_SAT_PER_VB`] are accepted, use
+    /// [`TxBuilder::has_high_fee_rate`] to ask the user for a confirmation.
+    pub fn set_fee_rate(&self, sat_per_vb: f32) -> Result<Self, Error> {
+        if !sat_per_vb.is_finite() || sat_per_vb <= 0.0 {
+            return Err(Error::InvalidFeeRate(sat_per_vb));
+        }
+
+        // 1 vbyte is 4 weight units
+        let sat_per_kwu = (f64::from(sat_per_vb) * 250.0).round() as u64;
+
+        Ok(TxBuilder {
+            fee_rate: Some(FeeRate::from_sat_per_kwu(sat_per_kwu)),
+            ..self.clone()
+        })
+    }
+
+    /// Returns whether the fee rate set is unusually high, in which case the
+    /// user should confirm it before creating the transaction
+    pub fn has_high_fee_rate(&self) -> bool {
+        self.fee_rate
+            .is_some_and(|fee_rate| fee_rate.to_sat_per_kwu() as f32 / 250.0 > HIGH_FEE_RATE_SAT_PER_VB)
+    }
+
+    /// Seeds the randomness used by coin selection and outputs ordering, so
+    /// that the same inputs always produce the same PSBT.
+    ///
+    /// ```rust, ignore
+    /// let tx_builder = TxBuilder::new();
+    /// ...
+    /// let updated = tx_builder.with_seed(42);
+    /// ```
+    pub fn with_seed(&self, seed: u64) -> Self {
         TxBuilder {
-            fee_rate: FeeRate::from_sat_per_vb(sat_per_vb),
+            random_number: seed,
             ..self.clone()
         }
     }
@@ -462,6 +856,17 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
             tx_builder.drain_wallet();
         }
 
+        if let Some(drain_to) = &self.drain_to {
+            tx_builder.drain_to(drain_to.clone());
+        } else if let Some(change_address) = &self.change_address {
+            // Without `drain_wallet`, the drain script is only used for change
+            tx_builder.drain_to(Address::from_str(change_address)?.assume_checked().script_pubkey());
+        }
+
+        if let Some(locktime) = self.locktime {
+            tx_builder.nlocktime(locktime);
+        }
+
         if !&self.data.is_empty() {
             let mut buf = PushBytesBuf::new();
             buf.extend_from_slice(self.data.as_slice())
@@ -479,13 +884,23 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
 
     /// Creates a PSBT from current TxBuilder
     ///
-    /// The resulting psbt can then be provided to Account.sign() method
+    /// The resulting psbt can then be provided to Account.sign() method. An
+    /// account must have been set using `set_account` beforehand, otherwise
+    /// [`Error::NoAccountBound`] is returned.
     pub async fn create_psbt(&self, allow_dust: bool, draft: bool) -> Result<Psbt, Error> {
-        let account = self.account.clone().ok_or(Error::AccountNotFound)?;
+        let account = self.account.clone().ok_or(Error::NoAccountBound)?;
+
+        // The change address might have been set before the account
+        if let Some(change_address) = &self.change_address {
+            check_address_network(change_address, account.get_network())?;
+        }
+
+        let frozen_utxos = account.get_frozen_utxos().await;
         let mut write_lock = account.get_mutable_wallet().await;
 
         let psbt = {
-            let tx_builder = write_lock.build_tx();
+            let mut tx_builder = write_lock.build_tx();
+            tx_builder.unspendable(frozen_utxos.into_iter().collect());
 
             match self.coin_selection {
                 CoinSelection::BranchAndBound => self.finish_tx(
@@ -516,6 +931,58 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> TxBuilder<C, P> {
         let psbt = self.create_psbt(allow_dust, true).await?;
         Ok(psbt)
     }
+
+    /// Creates a PSBT from current TxBuilder along with the change address it
+    /// pays to, if any, so that it can be reviewed before signing.
+    ///
+    /// Change is sent to the next unused address of the internal keychain.
+    /// `None` is returned when the transaction has no change output.
+    pub async fn create_psbt_with_change_info(
+        &self,
+        allow_dust: bool,
+        draft: bool,
+    ) -> Result<(Psbt, Option<ChangeInfo>), Error> {
+        let details = self.create_psbt_with_details(allow_dust, draft).await?;
+        Ok((details.psbt, details.change))
+    }
+
+    /// Creates a PSBT from current TxBuilder along with its change output and
+    /// the effective fee rate compared to the requested one.
+    pub async fn create_psbt_with_details(&self, allow_dust: bool, draft: bool) -> Result<PsbtDetails, Error> {
+        let psbt = self.create_psbt(allow_dust, draft).await?;
+
+        let account = self.account.clone().ok_or(Error::NoAccountBound)?;
+        let wallet = account.get_wallet().await;
+
+        let change = psbt.inner().unsigned_tx.output.iter().find_map(|output| {
+            match wallet.derivation_of_spk(output.script_pubkey.clone()) {
+                Some((KeychainKind::Internal, index)) => Some(ChangeInfo {
+                    address: Address::from_script(&output.script_pubkey, wallet.network()).ok()?,
+                    index,
+                }),
+                _ => None,
+            }
+        });
+
+        // BDK falls back on the minimum relay fee when no fee rate is set
+        let requested_fee_rate = self.fee_rate.unwrap_or(FeeRate::BROADCAST_MIN);
+
+        let fee = psbt.fee()?;
+        let vsize = psbt.estimated_signed_vsize(&wallet)?;
+        let max_expected_fee = requested_fee_rate
+            .fee_vb(vsize + FEERATE_OVERPAYMENT_TOLERANCE_VBYTES)
+            .unwrap_or(Amount::MAX);
+
+        let feerate_delta =
+            fee.to_sat() as f64 / vsize as f64 - requested_fee_rate.to_sat_per_kwu() as f64 * 4.0 / 1000.0;
+
+        Ok(PsbtDetails {
+            psbt,
+            change,
+            feerate_overpayment: fee > max_expected_fee,
+            feerate_delta,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -523,7 +990,10 @@ mod tests {
     use super::Account;
     use andromeda_common::ScriptType;
 
-    use super::{super::transaction_builder::CoinSelection, correct_recipients_amounts, TmpRecipient, TxBuilder};
+    use super::{
+        super::transaction_builder::CoinSelection, correct_recipients_amounts, estimate_tx_vsize, RecipientError,
+        TmpRecipient, TxBuilder,
+    };
 
     use std::{str::FromStr, sync::Arc};
 
@@ -533,16 +1003,21 @@ mod tests {
         bitcoin::{
             absolute::LockTime,
             bip32::{DerivationPath, Xpriv},
-            Amount, FeeRate, NetworkKind,
+            Address, Amount, FeeRate, NetworkKind, TxOut,
         },
+        serde_json,
         tx_builder::ChangeSpendPolicy,
+        KeychainKind,
     };
     use wiremock::{
         matchers::{body_string_contains, method, path, path_regex},
         Mock, MockServer, ResponseTemplate,
     };
 
-    use crate::{blockchain_client::BlockchainClient, mnemonic::Mnemonic, read_mock_file, storage::MemoryPersisted};
+    use crate::{
+        blockchain_client::BlockchainClient, error::Error, mnemonic::Mnemonic, psbt::Psbt, read_mock_file,
+        storage::MemoryPersisted, tests::utils::receive_unconfirmed_tx,
+    };
 
     #[test]
     fn should_remove_correct_amount() {
@@ -616,6 +1091,93 @@ mod tests {
         assert_eq!(updated.locktime, None);
     }
 
+    #[tokio::test]
+    async fn should_apply_locktime_to_psbt() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let address = account.get_next_receive_address().await.unwrap().address;
+        receive_unconfirmed_tx(
+            &account,
+            vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        )
+        .await;
+
+        let psbt = TxBuilder::<MemoryPersisted>::new()
+            .set_account(Arc::new(account))
+            .add_locktime(LockTime::from_consensus(800_000))
+            .update_recipient(
+                0,
+                (
+                    Some("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string()),
+                    Some(5_000),
+                ),
+            )
+            .unwrap()
+            .create_draft_psbt(false)
+            .await
+            .unwrap();
+
+        assert_eq!(psbt.inner().unsigned_tx.lock_time, LockTime::from_consensus(800_000));
+    }
+
+    #[tokio::test]
+    async fn should_create_psbt_spending_timelocked_utxo() {
+        let first_account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let second_account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/1'");
+
+        // Account's coins can only be spent from block 800100
+        let account = Account::<MemoryPersisted, MemoryPersisted>::new_multisig(
+            &format!(
+                "wsh(and_v(v:multi(1,{}/0/*,{}/0/*),after(800100)))",
+                first_account.export_account_xpub(),
+                second_account.export_account_xpub()
+            ),
+            None,
+            Network::Regtest,
+            MemoryPersisted {},
+        )
+        .unwrap();
+
+        let address = account.get_next_receive_address().await.unwrap().address;
+        receive_unconfirmed_tx(
+            &account,
+            vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        )
+        .await;
+
+        let account = Arc::new(account);
+        let build = |locktime: u32| {
+            TxBuilder::<MemoryPersisted>::new()
+                .set_account(account.clone())
+                .add_locktime(LockTime::from_consensus(locktime))
+                .update_recipient(
+                    0,
+                    (
+                        Some("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string()),
+                        Some(5_000),
+                    ),
+                )
+                .unwrap()
+        };
+
+        let psbt = build(800_100).create_draft_psbt(false).await.unwrap().inner();
+        assert_eq!(psbt.unsigned_tx.lock_time, LockTime::from_consensus(800_100));
+
+        // Timelock of the account's policy is checked before coins are selected
+        let result = build(800_000).create_draft_psbt(false).await;
+        assert!(matches!(
+            result,
+            Err(Error::LocktimeNotSatisfied { requested, required })
+                if requested == LockTime::from_consensus(800_000) && required == LockTime::from_consensus(800_100)
+        ));
+    }
+
     #[test]
     fn should_set_coin_selection() {
         let tx_builder = TxBuilder::<MemoryPersisted>::new();
@@ -642,8 +1204,67 @@ mod tests {
     fn should_change_fee_rate() {
         let tx_builder = TxBuilder::<MemoryPersisted>::new();
 
-        let updated = tx_builder.set_fee_rate(15);
+        let updated = tx_builder.set_fee_rate(15.0).unwrap();
         assert_eq!(updated.fee_rate, FeeRate::from_sat_per_vb(15));
+
+        let updated = tx_builder.set_fee_rate(1.5).unwrap();
+        assert_eq!(updated.fee_rate, Some(FeeRate::from_sat_per_kwu(375)));
+        assert!(!updated.has_high_fee_rate());
+    }
+
+    #[test]
+    fn should_reject_invalid_fee_rate() {
+        let tx_builder = TxBuilder::<MemoryPersisted>::new();
+
+        assert!(matches!(tx_builder.set_fee_rate(0.0), Err(Error::InvalidFeeRate(_))));
+        assert!(matches!(tx_builder.set_fee_rate(-2.0), Err(Error::InvalidFeeRate(_))));
+        assert!(matches!(
+            tx_builder.set_fee_rate(f32::NAN),
+            Err(Error::InvalidFeeRate(_))
+        ));
+    }
+
+    #[test]
+    fn should_flag_high_fee_rate() {
+        let tx_builder = TxBuilder::<MemoryPersisted>::new();
+        assert!(!tx_builder.has_high_fee_rate());
+
+        assert!(!tx_builder.set_fee_rate(1000.0).unwrap().has_high_fee_rate());
+        assert!(tx_builder.set_fee_rate(2000.0).unwrap().has_high_fee_rate());
+    }
+
+    #[test]
+    fn should_round_trip_through_serde() {
+        let tx_builder = TxBuilder::<MemoryPersisted>::new()
+            .add_recipient(Some((
+                Some("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string()),
+                Some(2_000),
+            )))
+            .update_recipient(
+                0,
+                (
+                    Some("bcrt1qekjrshcthdqafs0du85llvkwhg25zzpc8ztj4h".to_string()),
+                    Some(1_000),
+                ),
+            )
+            .unwrap()
+            .set_fee_rate(12.0)
+            .unwrap()
+            .set_coin_selection(CoinSelection::LargestFirst)
+            .add_locktime(LockTime::from_consensus(800_000));
+
+        let serialized = serde_json::to_string(&tx_builder).unwrap();
+        let restored: TxBuilder<MemoryPersisted> = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(restored.recipients, tx_builder.recipients);
+        assert_eq!(restored.recipients.len(), 2);
+        assert_eq!(restored.fee_rate, FeeRate::from_sat_per_vb(12));
+        assert_eq!(restored.coin_selection, CoinSelection::LargestFirst);
+        assert_eq!(restored.locktime, Some(LockTime::from_consensus(800_000)));
+        assert_eq!(restored.change_policy, tx_builder.change_policy);
+        assert_eq!(restored.rbf_enabled, tx_builder.rbf_enabled);
+        assert_eq!(restored.random_number, tx_builder.random_number);
+        assert!(restored.account.is_none());
     }
 
     #[test]
@@ -669,12 +1290,14 @@ mod tests {
     async fn should_update_recipient() {
         let tx_builder = TxBuilder::<MemoryPersisted>::new();
 
-        let updated = tx_builder.update_recipient(0, (Some("tb1...xyz".to_string()), Some(15837)));
+        let updated = tx_builder
+            .update_recipient(0, (Some("tb1...xyz".to_string()), Some(15837)))
+            .unwrap();
 
         assert_eq!(updated.recipients[0].1, "tb1...xyz".to_string());
         assert_eq!(updated.recipients[0].2, Amount::from_sat(15837));
 
-        let updated = tx_builder.update_recipient(0, (None, Some(668932)));
+        let updated = tx_builder.update_recipient(0, (None, Some(668932))).unwrap();
         assert_eq!(updated.recipients[0].2, Amount::from_sat(668932));
     }
 
@@ -701,6 +1324,415 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn should_keep_recipient_uuid_on_update() {
+        let tx_builder = TxBuilder::<MemoryPersisted>::new()
+            .add_recipient(None)
+            .add_recipient(None);
+        let uuids = tx_builder
+            .recipients
+            .iter()
+            .map(|TmpRecipient(uuid, _, _)| uuid.clone())
+            .collect::<Vec<_>>();
+
+        let updated = tx_builder.update_recipient(1, (None, Some(15_837))).unwrap();
+        assert_eq!(updated.recipients[1].0, uuids[1]);
+        assert_eq!(updated.recipients[1].2, Amount::from_sat(15_837));
+
+        // Removing the first recipient shifts indexes, but not uuids
+        let updated = updated
+            .remove_recipient(0)
+            .update_recipient_by_uuid(&uuids[2], (None, Some(2_000)))
+            .unwrap();
+        assert_eq!(updated.recipients.len(), 2);
+        assert_eq!(
+            updated.recipients[0],
+            TmpRecipient(uuids[1].clone(), String::new(), Amount::from_sat(15_837))
+        );
+        assert_eq!(
+            updated.recipients[1],
+            TmpRecipient(uuids[2].clone(), String::new(), Amount::from_sat(2_000))
+        );
+
+        assert!(matches!(
+            updated.update_recipient_by_uuid(&uuids[0], (None, Some(1_000))),
+            Err(Error::RecipientNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn should_report_recipient_errors() {
+        let tx_builder = TxBuilder::<MemoryPersisted>::new()
+            .update_recipient(0, (None, Some(10_000)))
+            .unwrap()
+            .add_recipient(Some((
+                Some("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string()),
+                Some(100),
+            )));
+
+        assert_eq!(
+            tx_builder.validate(),
+            Err(vec![
+                RecipientError::EmptyAddress { index: 0 },
+                RecipientError::Dust { index: 1, limit: 294 },
+            ])
+        );
+
+        let tx_builder = tx_builder
+            .update_recipient(0, (Some("not an address".to_string()), None))
+            .unwrap()
+            .update_recipient(1, (None, Some(0)))
+            .unwrap();
+
+        assert_eq!(
+            tx_builder.validate(),
+            Err(vec![
+                RecipientError::InvalidAddress { index: 0 },
+                RecipientError::ZeroAmount { index: 1 },
+            ])
+        );
+
+        let tx_builder = tx_builder
+            .update_recipient(
+                0,
+                (Some("bcrt1qekjrshcthdqafs0du85llvkwhg25zzpc8ztj4h".to_string()), None),
+            )
+            .unwrap()
+            .update_recipient(1, (None, Some(1_000)))
+            .unwrap();
+
+        assert_eq!(tx_builder.validate(), Ok(()));
+    }
+
+    #[tokio::test]
+    async fn should_fail_to_create_psbt_without_account() {
+        let tx_builder = TxBuilder::<MemoryPersisted>::new()
+            .update_recipient(
+                0,
+                (
+                    Some("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string()),
+                    Some(1_000),
+                ),
+            )
+            .unwrap();
+
+        let result = tx_builder.create_psbt(false, false).await;
+        assert!(matches!(result, Err(Error::NoAccountBound)));
+
+        let result = TxBuilder::<MemoryPersisted>::new()
+            .create_psbt_with_details(false, true)
+            .await;
+        assert!(matches!(result, Err(Error::NoAccountBound)));
+    }
+
+    #[tokio::test]
+    async fn should_reject_recipient_from_another_network() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+        let address = account.get_next_receive_address().await.unwrap().address;
+
+        let tx_builder = TxBuilder::new().set_account(Arc::new(account));
+
+        let result = tx_builder.update_recipient(
+            0,
+            (Some("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string()), None),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::AddressNetworkMismatch {
+                expected: Network::Regtest,
+                got: Network::Bitcoin
+            })
+        ));
+
+        let updated = tx_builder
+            .update_recipient(0, (Some(address.to_string()), Some(1000)))
+            .unwrap();
+        assert_eq!(updated.recipients[0].1, address.to_string());
+    }
+
+    #[test]
+    fn should_check_recipient_network_for_mainnet_and_testnet() {
+        let mnemonic = Mnemonic::from_string(
+            "onion ancient develop team busy purchase salmon robust danger wheat rich empower".to_string(),
+        )
+        .unwrap();
+        let tx_builder = |network: Network, network_kind: NetworkKind, derivation_path: &str| {
+            let master_secret_key = Xpriv::new_master(network_kind, &mnemonic.inner().to_seed("")).unwrap();
+            let account = Account::<MemoryPersisted, MemoryPersisted>::new(
+                master_secret_key,
+                network,
+                ScriptType::NativeSegwit,
+                DerivationPath::from_str(derivation_path).unwrap(),
+                MemoryPersisted {},
+            )
+            .unwrap();
+
+            TxBuilder::new().set_account(Arc::new(account))
+        };
+        let mainnet_address = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string();
+        let testnet_address = "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx".to_string();
+
+        let testnet_tx_builder = tx_builder(Network::Testnet, NetworkKind::Test, "m/84'/1'/0'");
+        assert!(matches!(
+            testnet_tx_builder.update_recipient(0, (Some(mainnet_address.clone()), None)),
+            Err(Error::AddressNetworkMismatch {
+                expected: Network::Testnet,
+                got: Network::Bitcoin
+            })
+        ));
+        assert!(testnet_tx_builder
+            .update_recipient(0, (Some(testnet_address.clone()), None))
+            .is_ok());
+
+        let mainnet_tx_builder = tx_builder(Network::Bitcoin, NetworkKind::Main, "m/84'/0'/0'");
+        let updated = mainnet_tx_builder
+            .update_recipient(0, (Some(mainnet_address.clone()), Some(1000)))
+            .unwrap();
+        assert_eq!(updated.recipients[0].1, mainnet_address);
+        assert!(matches!(
+            mainnet_tx_builder.update_recipient(0, (Some(testnet_address), None)),
+            Err(Error::AddressNetworkMismatch {
+                expected: Network::Bitcoin,
+                got: Network::Testnet
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_return_insufficient_funds_amounts() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        // Account only holds a single 10k sats UTXO
+        let address = account.get_next_receive_address().await.unwrap().address;
+        receive_unconfirmed_tx(
+            &account,
+            vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        )
+        .await;
+
+        let tx_builder = TxBuilder::<MemoryPersisted>::new()
+            .set_account(Arc::new(account))
+            .update_recipient(
+                0,
+                (
+                    Some("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string()),
+                    Some(20_000),
+                ),
+            )
+            .unwrap();
+
+        match tx_builder.create_draft_psbt(false).await {
+            Err(Error::InsufficientFunds { needed, available }) => {
+                assert_eq!(available, 10_000);
+                // Needed amount includes fees
+                assert!(needed > 20_000);
+            }
+            _ => panic!("Expected insufficient funds error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_report_change_address() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let address = account.get_next_receive_address().await.unwrap().address;
+        receive_unconfirmed_tx(
+            &account,
+            vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        )
+        .await;
+
+        let account = Arc::new(account);
+        let tx_builder = TxBuilder::<MemoryPersisted>::new()
+            .set_account(account.clone())
+            .update_recipient(
+                0,
+                (
+                    Some("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string()),
+                    Some(5_000),
+                ),
+            )
+            .unwrap();
+
+        let (psbt, change) = tx_builder.create_psbt_with_change_info(false, true).await.unwrap();
+        let change = change.expect("Expected a change output");
+
+        let wallet = account.get_wallet().await;
+        assert_eq!(
+            wallet.derivation_of_spk(change.address.script_pubkey()),
+            Some((KeychainKind::Internal, change.index))
+        );
+        assert_eq!(change.index, 0);
+
+        let change_output = psbt
+            .inner()
+            .unsigned_tx
+            .output
+            .into_iter()
+            .find(|output| output.script_pubkey == change.address.script_pubkey())
+            .expect("Change output should be in the PSBT");
+        assert_eq!(psbt.inner().unsigned_tx.output.len(), 2);
+        assert!(change_output.value < Amount::from_sat(5_000));
+    }
+
+    #[tokio::test]
+    async fn should_send_change_to_external_address() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let address = account.get_next_receive_address().await.unwrap().address;
+        receive_unconfirmed_tx(
+            &account,
+            vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        )
+        .await;
+
+        let account = Arc::new(account);
+        let tx_builder = TxBuilder::<MemoryPersisted>::new()
+            .set_account(account.clone())
+            .update_recipient(
+                0,
+                (
+                    Some("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string()),
+                    Some(5_000),
+                ),
+            )
+            .unwrap();
+
+        let result = tx_builder.set_change_address(Some("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string()));
+        assert!(matches!(result, Err(Error::AddressNetworkMismatch { .. })));
+
+        let change_address = Address::from_str("bcrt1qekjrshcthdqafs0du85llvkwhg25zzpc8ztj4h")
+            .unwrap()
+            .assume_checked();
+        let tx_builder = tx_builder.set_change_address(Some(change_address.to_string())).unwrap();
+
+        let (psbt, change) = tx_builder.create_psbt_with_change_info(false, true).await.unwrap();
+        // Change isn't sent to the internal keychain
+        assert!(change.is_none());
+
+        let outputs = psbt.inner().unsigned_tx.output;
+        assert_eq!(outputs.len(), 2);
+
+        let change_output = outputs
+            .iter()
+            .find(|output| output.script_pubkey == change_address.script_pubkey())
+            .expect("Change should be sent to the external address");
+        assert!(change_output.value < Amount::from_sat(5_000));
+
+        let wallet = account.get_wallet().await;
+        assert!(outputs
+            .iter()
+            .all(|output| wallet.derivation_of_spk(output.script_pubkey.clone()).is_none()));
+    }
+
+    #[tokio::test]
+    async fn should_select_same_inputs_with_same_seed() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let mut output = Vec::new();
+        for value in [3_000, 4_000, 5_000, 6_000, 7_000, 8_000] {
+            let address = account.get_next_receive_address().await.unwrap().address;
+            output.push(TxOut {
+                value: Amount::from_sat(value),
+                script_pubkey: address.script_pubkey(),
+            });
+        }
+        receive_unconfirmed_tx(&account, output).await;
+
+        let account = Arc::new(account);
+        let build = |seed: u64| {
+            TxBuilder::<MemoryPersisted>::new()
+                .set_account(account.clone())
+                .with_seed(seed)
+                .update_recipient(
+                    0,
+                    (
+                        Some("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string()),
+                        Some(9_500),
+                    ),
+                )
+                .unwrap()
+        };
+
+        let first = build(42).create_draft_psbt(false).await.unwrap();
+        let second = build(42).create_draft_psbt(false).await.unwrap();
+
+        let inputs = |psbt: &Psbt| {
+            psbt.inner()
+                .unsigned_tx
+                .input
+                .iter()
+                .map(|input| input.previous_output)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(inputs(&first), inputs(&second));
+        assert_eq!(first.inner().unsigned_tx, second.inner().unsigned_tx);
+    }
+
+    #[tokio::test]
+    async fn should_flag_feerate_overpayment_on_dust_change() {
+        let account = set_test_account_regtest(ScriptType::NativeSegwit, "m/84'/1'/0'");
+
+        let address = account.get_next_receive_address().await.unwrap().address;
+        receive_unconfirmed_tx(
+            &account,
+            vec![TxOut {
+                value: Amount::from_sat(10_000),
+                script_pubkey: address.script_pubkey(),
+            }],
+        )
+        .await;
+
+        let tx_builder = TxBuilder::<MemoryPersisted>::new()
+            .set_account(Arc::new(account))
+            .set_fee_rate(1.0)
+            .unwrap();
+
+        // Change is large enough to get its own output
+        let details = tx_builder
+            .update_recipient(
+                0,
+                (
+                    Some("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string()),
+                    Some(5_000),
+                ),
+            )
+            .unwrap()
+            .create_psbt_with_details(false, true)
+            .await
+            .unwrap();
+        assert!(details.change.is_some());
+        assert!(!details.feerate_overpayment);
+
+        // Remaining 90 sats would be a dust change, they are added to fees instead
+        let details = tx_builder
+            .update_recipient(
+                0,
+                (
+                    Some("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string()),
+                    Some(9_800),
+                ),
+            )
+            .unwrap()
+            .create_psbt_with_details(false, true)
+            .await
+            .unwrap();
+        assert!(details.change.is_none());
+        assert_eq!(details.psbt.fee().unwrap(), Amount::from_sat(200));
+        assert!(details.feerate_overpayment);
+        assert!(details.feerate_delta > 0.5);
+    }
+
     #[tokio::test]
     async fn test_build_transaction_flow() {
         let mut tx_builder = TxBuilder::<MemoryPersisted>::new();
@@ -770,7 +1802,7 @@ mod tests {
         let client = BlockchainClient::new(api_client.clone());
 
         // do full sync
-        let update = client.full_sync(&account, None).await.unwrap();
+        let update = client.full_sync(&account, None, None).await.unwrap();
         account
             .apply_update(update)
             .await
@@ -803,20 +1835,24 @@ mod tests {
         assert_eq!(tx_builder.recipients[1].2.to_sat(), 8781);
 
         // test update recipient
-        tx_builder = tx_builder.update_recipient(
-            0,
-            (
-                Some("bcrt1qekjrshcthdqafs0du85llvkwhg25zzpc8ztj4h".to_string()),
-                Some(2333),
-            ),
-        );
-        tx_builder = tx_builder.update_recipient(
-            1,
-            (
-                Some("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string()),
-                Some(1234),
-            ),
-        );
+        tx_builder = tx_builder
+            .update_recipient(
+                0,
+                (
+                    Some("bcrt1qekjrshcthdqafs0du85llvkwhg25zzpc8ztj4h".to_string()),
+                    Some(2333),
+                ),
+            )
+            .unwrap();
+        tx_builder = tx_builder
+            .update_recipient(
+                1,
+                (
+                    Some("bcrt1qh3nltpdyugldpz2hc294k9jwyy9s3953yg7g9j".to_string()),
+                    Some(1234),
+                ),
+            )
+            .unwrap();
         assert_eq!(tx_builder.recipients.len(), 2);
         assert_eq!(
             tx_builder.recipients[0].1,
@@ -866,7 +1902,7 @@ mod tests {
         assert_eq!(tx_builder.change_policy, ChangeSpendPolicy::OnlyChange);
 
         // test set fee rate
-        tx_builder = tx_builder.set_fee_rate(399);
+        tx_builder = tx_builder.set_fee_rate(399.0).unwrap();
         assert_eq!(tx_builder.fee_rate.unwrap().to_sat_per_vb_floor(), 399);
 
         // test create psbt
@@ -879,4 +1915,19 @@ mod tests {
         // InsufficientFunds error
         assert!(psbt.is_err());
     }
+
+    #[test]
+    fn should_estimate_tx_vsize() {
+        // 1-in-2-out P2WPKH transaction (recipient and change)
+        assert_eq!(estimate_tx_vsize(&[ScriptType::NativeSegwit], 1, true), 141);
+
+        // 2-in-1-out P2TR key path spend transaction
+        assert_eq!(
+            estimate_tx_vsize(&[ScriptType::Taproot, ScriptType::Taproot], 1, false),
+            169
+        );
+
+        // 1-in-2-out P2PKH transaction
+        assert_eq!(estimate_tx_vsize(&[ScriptType::Legacy], 2, false), 226);
+    }
 }
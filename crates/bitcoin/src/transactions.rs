@@ -1,16 +1,28 @@
 use std::{cmp::Ordering, sync::Arc};
 
+use andromeda_api::{address::ApiTx, exchange_rate::ApiExchangeRate, transaction::ApiTransactionStatus};
 use andromeda_common::utils::now;
-use async_std::sync::RwLockReadGuard;
+use async_std::sync::{RwLock, RwLockReadGuard};
 use bdk_chain::tx_graph::TxNode;
 use bdk_wallet::{
-    bitcoin::{bip32::DerivationPath, Address, ScriptBuf, Sequence, TxIn, TxOut, Txid, Witness},
+    bitcoin::{
+        bip32::DerivationPath, Address, Network as BdkNetwork, Script, ScriptBuf, Sequence, TxIn, TxOut, Txid, Witness,
+    },
     chain::{ChainPosition, ConfirmationBlockTime},
-    PersistedWallet, Wallet as BdkWallet, WalletPersister, WalletTx,
+    KeychainKind, PersistedWallet, Wallet as BdkWallet, WalletPersister, WalletTx,
+};
+use bitcoin::{
+    consensus::encode::{deserialize_hex, serialize_hex},
+    Transaction,
 };
-use bitcoin::Transaction;
 
-use crate::{account::Account, error::Error, psbt::Psbt, storage::WalletPersisterConnector};
+use crate::{
+    account::Account,
+    error::Error,
+    psbt::Psbt,
+    storage::WalletPersisterConnector,
+    utils::{cmp_txs_by_time, convert_to_fiat, SortOrder},
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TransactionTime {
@@ -20,29 +32,40 @@ pub enum TransactionTime {
 
 impl PartialOrd for TransactionTime {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match self {
-            TransactionTime::Unconfirmed { .. } => match other {
-                TransactionTime::Unconfirmed { .. } => Some(Ordering::Equal),
-                TransactionTime::Confirmed { .. } => Some(Ordering::Greater),
-            },
-            TransactionTime::Confirmed {
-                confirmation_time: confirmation_time_a,
-            } => match other {
-                TransactionTime::Unconfirmed { .. } => Some(Ordering::Less),
-                TransactionTime::Confirmed {
-                    confirmation_time: confirmation_time_b,
-                } => Some(confirmation_time_a.cmp(confirmation_time_b)),
-            },
-        }
+        Some(self.cmp(other))
     }
 }
 
+/// Confirmed transactions are ordered by confirmation time, and come before
+/// unconfirmed ones, which are all considered equal
 impl Ord for TransactionTime {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        match (self, other) {
+            (TransactionTime::Unconfirmed { .. }, TransactionTime::Unconfirmed { .. }) => Ordering::Equal,
+            (TransactionTime::Unconfirmed { .. }, TransactionTime::Confirmed { .. }) => Ordering::Greater,
+            (TransactionTime::Confirmed { .. }, TransactionTime::Unconfirmed { .. }) => Ordering::Less,
+            (
+                TransactionTime::Confirmed {
+                    confirmation_time: confirmation_time_a,
+                },
+                TransactionTime::Confirmed {
+                    confirmation_time: confirmation_time_b,
+                },
+            ) => confirmation_time_a.cmp(confirmation_time_b),
+        }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransactionKind {
+    /// Transaction moving funds in or out of the wallet
+    External,
+    /// Transaction moving funds between accounts of the same wallet. Contains
+    /// the derivation paths of the involved accounts other than the spending
+    /// one.
+    InternalTransfer { accounts: Vec<DerivationPath> },
+}
+
 #[derive(Clone, Debug)]
 pub struct TransactionDetails {
     /// Transaction id
@@ -62,16 +85,33 @@ pub struct TransactionDetails {
     /// Can be used to compute feerate for transaction given an absolute fee
     /// amount
     pub vbytes_size: u64,
+    /// Transaction weight in weight units
+    pub weight: u64,
     /// If the transaction is confirmed, contains height and Unix timestamp of
     /// the block containing the transaction, unconfirmed transaction
     /// contains `None`.
     pub time: TransactionTime,
+    /// Number of confirmations of the transaction relatively to the chain tip
+    /// known by the wallet, `None` if the transaction is unconfirmed.
+    ///
+    /// The wallet's tip only moves forward on sync, so this count lags
+    /// behind the blockchain's tip until the account is synced again.
+    pub confirmations: Option<u32>,
+    /// Whether the transaction is unconfirmed and signals replaceability
+    /// (BIP-125), meaning its fees can be bumped.
+    pub replaceable: bool,
     /// List of transaction inputs.
     pub inputs: Vec<DetailledTxIn>,
     /// List of transaction outputs.
     pub outputs: Vec<DetailledTxOutput>,
     /// BIP44 Account to which the transaction is bound
     pub account_derivation_path: DerivationPath,
+    /// Whether the transaction is external to the wallet or an internal
+    /// transfer between its accounts
+    pub kind: TransactionKind,
+    /// Consensus-serialized transaction, hex encoded. Transactions built from
+    /// an unsigned PSBT are serialized without their witnesses
+    pub raw_hex: String,
 }
 
 fn get_detailled_inputs(txins: Vec<TxIn>, wallet: &BdkWallet) -> Result<Vec<DetailledTxIn>, Error> {
@@ -94,7 +134,7 @@ fn get_detailled_outputs(txout: Vec<TxOut>, wallet: &BdkWallet) -> Result<Vec<De
     Ok(outputs)
 }
 
-fn get_time(chain_position: Option<ChainPosition<ConfirmationBlockTime>>) -> TransactionTime {
+pub(crate) fn get_time(chain_position: Option<ChainPosition<ConfirmationBlockTime>>) -> TransactionTime {
     if let Some(chain_position) = chain_position {
         return match chain_position {
             ChainPosition::Confirmed { anchor, .. } => TransactionTime::Confirmed {
@@ -111,6 +151,46 @@ fn get_time(chain_position: Option<ChainPosition<ConfirmationBlockTime>>) -> Tra
     }
 }
 
+/// Returns the number of confirmations of a transaction mined at the given
+/// height, the block containing the transaction counting as the first one.
+pub(crate) fn get_confirmations_at_height(block_height: u32, tip_height: u32) -> u32 {
+    tip_height.saturating_sub(block_height).saturating_add(1)
+}
+
+fn get_confirmations(chain_position: Option<ChainPosition<ConfirmationBlockTime>>, tip_height: u32) -> Option<u32> {
+    match chain_position {
+        Some(ChainPosition::Confirmed { anchor, .. }) => {
+            Some(get_confirmations_at_height(anchor.block_id.height, tip_height))
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether the transaction is unconfirmed and any of its inputs
+/// signals replaceability
+fn is_replaceable(tx: &Transaction, time: &TransactionTime) -> bool {
+    matches!(time, TransactionTime::Unconfirmed { .. }) && tx.is_explicitly_rbf()
+}
+
+/// Returns transaction time and number of confirmations given a transaction
+/// status returned by Proton Wallet API
+fn get_api_tx_time(status: &ApiTransactionStatus, tip_height: u32) -> (TransactionTime, Option<u32>) {
+    match (status.IsConfirmed != 0, status.BlockTime) {
+        (true, Some(confirmation_time)) => (
+            TransactionTime::Confirmed { confirmation_time },
+            status
+                .BlockHeight
+                .map(|height| get_confirmations_at_height(height, tip_height)),
+        ),
+        _ => (
+            TransactionTime::Unconfirmed {
+                last_seen: now().as_secs(),
+            },
+            None,
+        ),
+    }
+}
+
 pub trait ToTransactionDetails<A> {
     fn to_transaction_details(&self, account: A) -> Result<TransactionDetails, Error>;
 }
@@ -126,6 +206,8 @@ where
         let (sent, received) = wallet_lock.sent_and_received(&self.tx_node.tx);
 
         let time = get_time(Some(self.chain_position));
+        let confirmations = get_confirmations(Some(self.chain_position), wallet_lock.local_chain().tip().height());
+        let replaceable = is_replaceable(&self.tx_node.tx, &time);
         let outputs = get_detailled_outputs(self.tx_node.output.clone(), wallet_lock)?;
         let inputs = get_detailled_inputs(self.tx_node.input.clone(), wallet_lock)?;
 
@@ -137,12 +219,17 @@ where
             fees: wallet_lock.calculate_fee(&self.tx_node.tx).ok().map(|a| a.to_sat()),
 
             vbytes_size: self.tx_node.weight().to_vbytes_ceil(),
+            weight: self.tx_node.weight().to_wu(),
             time,
+            confirmations,
+            replaceable,
 
             inputs,
             outputs,
 
             account_derivation_path,
+            kind: TransactionKind::External,
+            raw_hex: serialize_hex(self.tx_node.tx.as_ref()),
         })
     }
 }
@@ -161,10 +248,10 @@ where
             .tx_graph()
             .list_canonical_txs(wallet_lock.local_chain(), wallet_lock.local_chain().tip().block_id())
             .find(|tx| tx.tx_node.txid == self.compute_txid());
-        let time = match tx {
-            Some(tx) => get_time(Some(tx.chain_position)),
-            None => get_time(None),
-        };
+        let chain_position = tx.map(|tx| tx.chain_position);
+        let time = get_time(chain_position);
+        let confirmations = get_confirmations(chain_position, wallet_lock.local_chain().tip().height());
+        let replaceable = is_replaceable(&self.tx, &time);
 
         let outputs = get_detailled_outputs(self.output.clone(), wallet_lock)?;
         let inputs = get_detailled_inputs(self.input.clone(), wallet_lock)?;
@@ -177,12 +264,17 @@ where
             fees: wallet_lock.calculate_fee(&self.tx).ok().map(|a| a.to_sat()),
 
             vbytes_size: self.weight().to_vbytes_ceil(),
+            weight: self.weight().to_wu(),
             time,
+            confirmations,
+            replaceable,
 
             inputs,
             outputs,
 
             account_derivation_path,
+            kind: TransactionKind::External,
+            raw_hex: serialize_hex(self.tx.as_ref()),
         })
     }
 }
@@ -192,42 +284,216 @@ impl TransactionDetails {
         psbt: &Psbt,
         account: Arc<Account<C, P>>,
     ) -> Result<Self, Error> {
-        let tx = psbt.extract_tx()?;
+        // Fee rate can't be checked when input values are unknown
+        let tx = psbt.inner().extract_tx_unchecked_fee_rate();
 
         let wallet_lock = account.get_wallet().await;
 
-        let outputs = get_detailled_outputs(tx.output.clone(), &wallet_lock)?;
-        let inputs = get_detailled_inputs(tx.input.clone(), &wallet_lock)?;
+        Self::from_unknown_tx(tx, psbt.fee_from_utxos(), &wallet_lock, account.get_derivation_path())
+    }
+
+    /// Builds transaction details from a consensus-serialized transaction,
+    /// hex encoded, e.g. pasted from a block explorer. Inputs and outputs are
+    /// checked against the account's wallet. Transactions unknown to the
+    /// wallet are considered unconfirmed.
+    pub async fn from_hex<C: WalletPersisterConnector<P>, P: WalletPersister>(
+        hex: &str,
+        account: Arc<Account<C, P>>,
+    ) -> Result<Self, Error> {
+        let tx = deserialize_hex::<Transaction>(hex.trim()).map_err(|e| Error::InvalidTransactionHex(e.to_string()))?;
+
+        let wallet_lock = account.get_wallet().await;
+
+        if let Some(wallet_tx) = wallet_lock.get_tx(tx.compute_txid()) {
+            return wallet_tx.to_transaction_details((&wallet_lock, account.get_derivation_path()));
+        }
+
+        let fees = wallet_lock.calculate_fee(&tx).ok().map(|fee| fee.to_sat());
+
+        Self::from_unknown_tx(tx, fees, &wallet_lock, account.get_derivation_path())
+    }
+
+    /// Builds details of a transaction that isn't in the account's wallet
+    /// yet, e.g. one being created, as an unconfirmed one
+    fn from_unknown_tx<P: WalletPersister>(
+        tx: Transaction,
+        fees: Option<u64>,
+        wallet_lock: &RwLockReadGuard<'_, PersistedWallet<P>>,
+        account_derivation_path: DerivationPath,
+    ) -> Result<Self, Error> {
+        let outputs = get_detailled_outputs(tx.output.clone(), wallet_lock)?;
+        let inputs = get_detailled_inputs(tx.input.clone(), wallet_lock)?;
 
         let (sent, received) = wallet_lock.sent_and_received(&tx);
 
-        let tx = TransactionDetails {
+        Ok(TransactionDetails {
             txid: tx.compute_txid(),
             received: received.to_sat(),
             sent: sent.to_sat(),
 
-            fees: wallet_lock.calculate_fee(&tx).ok().map(|a| a.to_sat()),
+            fees,
             vbytes_size: tx.weight().to_vbytes_ceil(),
+            weight: tx.weight().to_wu(),
 
             time: TransactionTime::Unconfirmed {
                 last_seen: now().as_secs(),
             },
+            confirmations: None,
+            replaceable: tx.is_explicitly_rbf(),
 
             inputs,
             outputs,
 
-            account_derivation_path: account.get_derivation_path(),
+            account_derivation_path,
+            kind: TransactionKind::External,
+            raw_hex: serialize_hex(&tx),
+        })
+    }
+
+    /// Builds transaction details from a transaction fetched from Proton Wallet
+    /// API, so that it can be displayed the same way as the ones from the
+    /// account's wallet
+    ///
+    /// Confirmations are computed against `tip_height`, which should be the
+    /// current tip fetched from the API. When none is given, the tip of the
+    /// account's local chain is used, which may lag behind until next sync.
+    pub async fn from_api_tx<C: WalletPersisterConnector<P>, P: WalletPersister>(
+        api_tx: ApiTx,
+        account: Arc<Account<C, P>>,
+        tip_height: Option<u32>,
+    ) -> Result<Self, Error> {
+        let wallet_lock = account.get_wallet().await;
+
+        Self::from_api_tx_with_wallet(api_tx, &wallet_lock, account.get_derivation_path(), tip_height)
+    }
+
+    /// Same as [`Self::from_api_tx`], from an already locked account's wallet
+    pub(crate) fn from_api_tx_with_wallet<P: WalletPersister>(
+        api_tx: ApiTx,
+        wallet_lock: &RwLockReadGuard<'_, PersistedWallet<P>>,
+        account_derivation_path: DerivationPath,
+        tip_height: Option<u32>,
+    ) -> Result<Self, Error> {
+        let status = api_tx.TransactionStatus.clone();
+        let fees = api_tx.Fee;
+        let tx = Transaction::try_from(api_tx)?;
+
+        let outputs = get_detailled_outputs(tx.output.clone(), wallet_lock)?;
+        let inputs = get_detailled_inputs(tx.input.clone(), wallet_lock)?;
+
+        let (sent, received) = wallet_lock.sent_and_received(&tx);
+
+        let tip_height = tip_height.unwrap_or_else(|| wallet_lock.local_chain().tip().height());
+        let (time, confirmations) = get_api_tx_time(&status, tip_height);
+        let replaceable = is_replaceable(&tx, &time);
+
+        let tx = TransactionDetails {
+            txid: tx.compute_txid(),
+            received: received.to_sat(),
+            sent: sent.to_sat(),
+
+            fees: Some(fees),
+            vbytes_size: tx.weight().to_vbytes_ceil(),
+            weight: tx.weight().to_wu(),
+
+            time,
+            confirmations,
+            replaceable,
+
+            inputs,
+            outputs,
+
+            account_derivation_path,
+            kind: TransactionKind::External,
+            raw_hex: serialize_hex(&tx),
         };
 
         Ok(tx)
     }
 
+    /// Builds transaction details for a transaction that doesn't belong to
+    /// any account, e.g. a payment being watched. Inputs' previous outputs are
+    /// filled from the ones returned by the API and, since no account is
+    /// involved, derivation path is the master one.
+    pub fn from_foreign_api_tx(api_tx: ApiTx, network: BdkNetwork, tip_height: u32) -> Result<Self, Error> {
+        let (time, confirmations) = get_api_tx_time(&api_tx.TransactionStatus, tip_height);
+        let fees = api_tx.Fee;
+
+        let previous_outputs = api_tx
+            .Vin
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|vin| vin.Prevout.map(TxOut::try_from).transpose())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let tx = Transaction::try_from(api_tx)?;
+        let replaceable = is_replaceable(&tx, &time);
+
+        let inputs = tx
+            .input
+            .clone()
+            .into_iter()
+            .zip(previous_outputs)
+            .map(|(input, previous_output)| DetailledTxIn {
+                previous_output: previous_output.map(|output| DetailledTxOutput::from_foreign_txout(output, network)),
+                script_sig: input.script_sig,
+                sequence: input.sequence,
+                witness: input.witness,
+            })
+            .collect::<Vec<_>>();
+
+        let outputs = tx
+            .output
+            .clone()
+            .into_iter()
+            .map(|output| DetailledTxOutput::from_foreign_txout(output, network))
+            .collect::<Vec<_>>();
+
+        Ok(TransactionDetails {
+            txid: tx.compute_txid(),
+            received: 0,
+            sent: 0,
+
+            fees: Some(fees),
+            vbytes_size: tx.weight().to_vbytes_ceil(),
+            weight: tx.weight().to_wu(),
+
+            time,
+            confirmations,
+            replaceable,
+
+            inputs,
+            outputs,
+
+            account_derivation_path: DerivationPath::master(),
+            kind: TransactionKind::External,
+            raw_hex: serialize_hex(&tx),
+        })
+    }
+
     pub fn get_time(&self) -> u64 {
         match self.time {
             TransactionTime::Confirmed { confirmation_time } => confirmation_time,
             TransactionTime::Unconfirmed { last_seen } => last_seen,
         }
     }
+
+    /// Net value (sats) of the transaction for the account: positive for a
+    /// receive, negative for a spend. Fees paid by the account are included.
+    pub fn value(&self) -> i64 {
+        self.received as i64 - self.sent as i64
+    }
+
+    /// Net value of the transaction converted to fiat, see [`Self::value`].
+    pub fn fiat_value(&self, exchange_rate: &ApiExchangeRate) -> f64 {
+        convert_to_fiat(self.value(), exchange_rate)
+    }
+
+    /// Fees of the transaction converted to fiat, `None` if fees are unknown.
+    pub fn fiat_fees(&self, exchange_rate: &ApiExchangeRate) -> Option<f64> {
+        self.fees.map(|fees| convert_to_fiat(fees as i64, exchange_rate))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -254,19 +520,69 @@ impl DetailledTxIn {
 #[derive(Clone, Debug)]
 pub struct DetailledTxOutput {
     pub value: u64,
+    /// Address the output pays to, if any: OP_RETURN and non-standard outputs
+    /// don't have one
     pub address: Option<Address>,
     pub script_pubkey: ScriptBuf,
+    /// Type of the output script, e.g. `p2wpkh`, `p2tr` or `op_return`
+    pub script_type: String,
+    /// Human-readable assembly of the output script
+    pub script_asm: String,
     pub is_mine: bool,
+    /// Whether the output pays back to the account's internal keychain, i.e.
+    /// is the change of a spend
+    pub is_change: bool,
+    /// Derivation index of the output script in its keychain, for owned
+    /// outputs, e.g. to match a received payment with the address it was
+    /// requested on
+    pub derivation_index: Option<u32>,
+}
+
+/// Returns the label of a script's type, `unknown` for non-standard ones
+fn get_script_type(script: &Script) -> &'static str {
+    if script.is_op_return() {
+        "op_return"
+    } else if script.is_p2pk() {
+        "p2pk"
+    } else if script.is_p2pkh() {
+        "p2pkh"
+    } else if script.is_p2sh() {
+        "p2sh"
+    } else if script.is_p2wpkh() {
+        "p2wpkh"
+    } else if script.is_p2wsh() {
+        "p2wsh"
+    } else if script.is_p2tr() {
+        "p2tr"
+    } else {
+        "unknown"
+    }
 }
 
 impl DetailledTxOutput {
     pub fn from_txout(output: TxOut, wallet: &BdkWallet) -> Result<DetailledTxOutput, Error> {
+        let derivation = wallet.derivation_of_spk(output.script_pubkey.clone());
+
         Ok(DetailledTxOutput {
+            is_mine: derivation.is_some(),
+            is_change: matches!(derivation, Some((KeychainKind::Internal, _))),
+            derivation_index: derivation.map(|(_, index)| index),
+            ..Self::from_foreign_txout(output, wallet.network())
+        })
+    }
+
+    /// Builds output details for an output that doesn't belong to any account
+    pub fn from_foreign_txout(output: TxOut, network: BdkNetwork) -> DetailledTxOutput {
+        DetailledTxOutput {
             value: output.value.to_sat(),
-            is_mine: wallet.is_mine(output.script_pubkey.clone()),
-            address: Address::from_script(output.script_pubkey.as_script(), wallet.network()).ok(),
+            is_mine: false,
+            is_change: false,
+            derivation_index: None,
+            address: Address::from_script(output.script_pubkey.as_script(), network).ok(),
+            script_type: get_script_type(&output.script_pubkey).to_string(),
+            script_asm: output.script_pubkey.to_asm_string(),
             script_pubkey: output.script_pubkey,
-        })
+        }
     }
 }
 
@@ -275,6 +591,64 @@ pub struct Pagination {
     pub take: usize,
 }
 
+/// Cursor over an account's transactions, returning them page by page.
+///
+/// Transactions are sorted upfront on their time only, and converted to
+/// [`TransactionDetails`] when their page is requested, so that big
+/// histories are never fully materialized.
+pub struct TransactionsCursor<P: WalletPersister> {
+    wallet: Arc<RwLock<PersistedWallet<P>>>,
+    account_derivation_path: DerivationPath,
+    txids: std::vec::IntoIter<Txid>,
+}
+
+impl<P: WalletPersister> TransactionsCursor<P> {
+    pub(crate) async fn new(
+        wallet: Arc<RwLock<PersistedWallet<P>>>,
+        account_derivation_path: DerivationPath,
+        sort_order: Option<SortOrder>,
+    ) -> Self {
+        let mut txs = wallet
+            .read()
+            .await
+            .transactions()
+            .map(|tx| (tx.tx_node.txid, get_time(Some(tx.chain_position))))
+            .collect::<Vec<_>>();
+
+        // Same ordering as `sort_and_paginate_txs`
+        if let Some(sort_order) = sort_order {
+            txs.sort_by(|(txid_a, time_a), (txid_b, time_b)| {
+                cmp_txs_by_time(sort_order, (time_a, txid_a), (time_b, txid_b))
+            });
+        }
+
+        TransactionsCursor {
+            wallet,
+            account_derivation_path,
+            txids: txs.into_iter().map(|(txid, _)| txid).collect::<Vec<_>>().into_iter(),
+        }
+    }
+
+    /// Returns the number of transactions not returned yet
+    pub fn remaining(&self) -> usize {
+        self.txids.len()
+    }
+
+    /// Returns the next `take` transactions, or an empty list once all of
+    /// them were returned. Transactions evicted from the wallet since the
+    /// cursor creation are skipped.
+    pub async fn next_page(&mut self, take: usize) -> Result<Vec<TransactionDetails>, Error> {
+        let txids = self.txids.by_ref().take(take).collect::<Vec<_>>();
+        let wallet_lock = self.wallet.read().await;
+
+        txids
+            .into_iter()
+            .filter_map(|txid| wallet_lock.get_tx(txid))
+            .map(|tx| tx.to_transaction_details((&wallet_lock, self.account_derivation_path.clone())))
+            .collect()
+    }
+}
+
 impl Pagination {
     pub fn new(skip: usize, take: usize) -> Self {
         Pagination { skip, take }
@@ -286,3 +660,53 @@ impl Default for Pagination {
         Pagination::new(0, usize::MAX)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bdk_wallet::bitcoin::{
+        opcodes::all::OP_RETURN, script::Builder, Address, Amount, Network as BdkNetwork, TxOut,
+    };
+
+    use super::DetailledTxOutput;
+
+    #[test]
+    fn should_label_output_script_type() {
+        let address = Address::from_str("bc1qaaj3aqkkw9lwnuw8f69v6k73ha3eh99pel7hus")
+            .unwrap()
+            .assume_checked();
+        let output = DetailledTxOutput::from_foreign_txout(
+            TxOut {
+                value: Amount::from_sat(373759),
+                script_pubkey: address.script_pubkey(),
+            },
+            BdkNetwork::Bitcoin,
+        );
+
+        assert_eq!(output.script_type, "p2wpkh");
+        assert_eq!(
+            output.script_asm,
+            "OP_0 OP_PUSHBYTES_20 ef651e82d6717ee9f1c74e8acd5bd1bf639b94a1"
+        );
+        assert_eq!(output.address, Some(address));
+    }
+
+    #[test]
+    fn should_label_op_return_output_without_address() {
+        let output = DetailledTxOutput::from_foreign_txout(
+            TxOut {
+                value: Amount::ZERO,
+                script_pubkey: Builder::new()
+                    .push_opcode(OP_RETURN)
+                    .push_slice(*b"hello")
+                    .into_script(),
+            },
+            BdkNetwork::Bitcoin,
+        );
+
+        assert_eq!(output.script_type, "op_return");
+        assert_eq!(output.script_asm, "OP_RETURN OP_PUSHBYTES_5 68656c6c6f");
+        assert!(output.address.is_none());
+    }
+}
@@ -1,14 +1,30 @@
+use std::{cmp::Ordering, str::FromStr};
+
+use andromeda_api::exchange_rate::ApiExchangeRate;
 use andromeda_common::{BitcoinUnit, BITCOIN, MILLI_BITCOIN, SATOSHI};
+use bdk_wallet::{bitcoin::Txid, chain::ChainPosition, LocalOutput};
 
 use super::transactions::Pagination;
-use crate::transactions::TransactionDetails;
+use crate::{
+    error::Error,
+    transactions::{TransactionDetails, TransactionTime},
+};
 
-#[derive(PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
 pub enum SortOrder {
     Asc,
     Desc,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UtxoSort {
+    ValueAsc,
+    ValueDesc,
+    /// Confirmed UTXOs first by confirmation height, then unconfirmed ones by
+    /// first seen time
+    Oldest,
+}
+
 #[cfg(target_arch = "wasm32")]
 pub fn spawn<F>(future: F)
 where
@@ -46,6 +62,15 @@ pub fn convert_amount(value: f64, from: BitcoinUnit, to: BitcoinUnit) -> f64 {
     }
 }
 
+/// Converts a signed amount of sats to fiat using the given exchange rate,
+/// rounded to the minor unit of the fiat currency (e.g. cents for USD).
+pub fn convert_to_fiat(sats: i64, exchange_rate: &ApiExchangeRate) -> f64 {
+    let amount = convert_amount(sats as f64, BitcoinUnit::SATS, exchange_rate.BitcoinUnit);
+    let cents = exchange_rate.Cents.max(1) as f64;
+
+    (amount * exchange_rate.ExchangeRate as f64).round() / cents
+}
+
 /// Returns the maximum value between two `f64` numbers, or 0.0 if both are NaN.
 ///
 /// # Notes
@@ -100,6 +125,43 @@ pub fn min_f64(a: f64, b: f64) -> f64 {
     }
 }
 
+/// Parses a txid, expected as 64 hex characters
+pub fn parse_txid(txid: &str) -> Result<Txid, Error> {
+    if txid.len() != 64 || !txid.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(Error::InvalidTxId(txid.to_string()));
+    }
+
+    Txid::from_str(txid).map_err(|_| Error::InvalidTxId(txid.to_string()))
+}
+
+/// Parses an amount of sats provided as a string of decimal digits, e.g. by
+/// JS callers which can't represent every `u64` with their numbers
+pub fn parse_sat_amount(amount: &str) -> Result<u64, Error> {
+    if amount.is_empty() || !amount.chars().all(|c| c.is_ascii_digit()) {
+        return Err(Error::InvalidAmount(amount.to_string()));
+    }
+
+    // Only digits are left, so parsing can only fail on overflow
+    amount
+        .parse::<u64>()
+        .map_err(|_| Error::AmountOverflow(amount.to_string()))
+}
+
+/// Compares transactions by time in the given order. Transactions sharing the
+/// same time are ordered by txid, so that sorting is deterministic.
+pub(crate) fn cmp_txs_by_time(
+    sort_order: SortOrder,
+    (time_a, txid_a): (&TransactionTime, &Txid),
+    (time_b, txid_b): (&TransactionTime, &Txid),
+) -> Ordering {
+    let by_time = match sort_order {
+        SortOrder::Asc => time_a.cmp(time_b),
+        SortOrder::Desc => time_b.cmp(time_a),
+    };
+
+    by_time.then_with(|| txid_a.cmp(txid_b))
+}
+
 pub fn sort_and_paginate_txs(
     mut txs: Vec<TransactionDetails>,
     pagination: Pagination,
@@ -107,13 +169,7 @@ pub fn sort_and_paginate_txs(
 ) -> Vec<TransactionDetails> {
     if let Some(sort_order) = sort_order {
         // we only sort by time for now
-        txs.sort_by(|a, b| {
-            if sort_order == SortOrder::Desc {
-                b.time.cmp(&a.time)
-            } else {
-                a.time.cmp(&b.time)
-            }
-        });
+        txs.sort_by(|a, b| cmp_txs_by_time(sort_order, (&a.time, &a.txid), (&b.time, &b.txid)));
     }
 
     // We paginated sorted vector
@@ -123,11 +179,190 @@ pub fn sort_and_paginate_txs(
         .collect::<Vec<_>>()
 }
 
+pub fn sort_and_paginate_utxos(
+    mut utxos: Vec<LocalOutput>,
+    pagination: Pagination,
+    sort: Option<UtxoSort>,
+) -> Vec<LocalOutput> {
+    match sort {
+        Some(UtxoSort::ValueAsc) => utxos.sort_by_key(|utxo| utxo.txout.value),
+        Some(UtxoSort::ValueDesc) => utxos.sort_by_key(|utxo| std::cmp::Reverse(utxo.txout.value)),
+        Some(UtxoSort::Oldest) => utxos.sort_by_key(|utxo| match &utxo.chain_position {
+            ChainPosition::Confirmed { anchor, .. } => (false, anchor.block_id.height as u64),
+            ChainPosition::Unconfirmed { last_seen } => (true, last_seen.unwrap_or(u64::MAX)),
+        }),
+        None => {}
+    }
+
+    // We paginated sorted vector
+    utxos
+        .into_iter()
+        .skip(pagination.skip)
+        .take(pagination.take)
+        .collect::<Vec<_>>()
+}
+
 #[cfg(test)]
 mod tests {
+    use andromeda_api::{exchange_rate::ApiExchangeRate, settings::FiatCurrencySymbol};
     use andromeda_common::BitcoinUnit;
 
-    use super::super::utils::{convert_amount, max_f64, min_f64};
+    use bdk_wallet::bitcoin::{bip32::DerivationPath, hashes::Hash, Txid};
+
+    use super::super::utils::{
+        convert_amount, convert_to_fiat, max_f64, min_f64, parse_sat_amount, parse_txid, sort_and_paginate_txs,
+        SortOrder,
+    };
+    use crate::{
+        error::Error,
+        transactions::{Pagination, TransactionDetails, TransactionKind, TransactionTime},
+    };
+
+    fn usd_exchange_rate() -> ApiExchangeRate {
+        ApiExchangeRate {
+            ID: "BG2rHbE0giOBTvPWDVHdS_MMyxemjRxSzrKOTbxaINTH0zYnS5hD5zEqV9TURB-mzMy2LPC3qg4XnPq_kHmf9g==".to_string(),
+            BitcoinUnit: BitcoinUnit::BTC,
+            FiatCurrency: FiatCurrencySymbol::USD,
+            Sign: Some("$".to_string()),
+            ExchangeRateTime: "1732266518".to_string(),
+            ExchangeRate: 9890500,
+            Cents: 100,
+        }
+    }
+
+    fn transaction(id: u8, time: TransactionTime) -> TransactionDetails {
+        TransactionDetails {
+            txid: Txid::from_byte_array([id; 32]),
+            received: 0,
+            sent: 0,
+            fees: None,
+            vbytes_size: 0,
+            weight: 0,
+            time,
+            confirmations: None,
+            replaceable: false,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            account_derivation_path: DerivationPath::master(),
+            kind: TransactionKind::External,
+            raw_hex: String::new(),
+        }
+    }
+
+    fn sorted_ids(sort_order: Option<SortOrder>, pagination: Pagination) -> Vec<u8> {
+        let txs = vec![
+            transaction(
+                1,
+                TransactionTime::Confirmed {
+                    confirmation_time: 1733470000,
+                },
+            ),
+            transaction(2, TransactionTime::Unconfirmed { last_seen: 1733460000 }),
+            transaction(
+                3,
+                TransactionTime::Confirmed {
+                    confirmation_time: 1733450000,
+                },
+            ),
+            transaction(
+                4,
+                TransactionTime::Confirmed {
+                    confirmation_time: 1733480000,
+                },
+            ),
+        ];
+
+        sort_and_paginate_txs(txs, pagination, sort_order)
+            .into_iter()
+            .map(|tx| tx.txid.to_byte_array()[0])
+            .collect()
+    }
+
+    #[test]
+    fn should_sort_transactions_ascending() {
+        // Unconfirmed transactions come last, whatever their last seen time
+        assert_eq!(
+            sorted_ids(Some(SortOrder::Asc), Pagination::default()),
+            vec![3, 1, 4, 2]
+        );
+    }
+
+    #[test]
+    fn should_sort_transactions_descending() {
+        assert_eq!(
+            sorted_ids(Some(SortOrder::Desc), Pagination::default()),
+            vec![2, 4, 1, 3]
+        );
+    }
+
+    #[test]
+    fn should_keep_transactions_order_when_unsorted() {
+        assert_eq!(sorted_ids(None, Pagination::default()), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn should_parse_txid() {
+        let txid = "6bbfc06ef911e4b2fffe1150fa8f3729b3ee52c78ef21093b5ae45544ff690fa";
+        assert_eq!(parse_txid(txid).unwrap().to_string(), txid);
+
+        assert!(matches!(
+            parse_txid("6bbfc06ef911e4b2"),
+            Err(Error::InvalidTxId(txid)) if txid == "6bbfc06ef911e4b2"
+        ));
+        assert!(matches!(
+            parse_txid("zzbfc06ef911e4b2fffe1150fa8f3729b3ee52c78ef21093b5ae45544ff690fa"),
+            Err(Error::InvalidTxId(_))
+        ));
+    }
+
+    #[test]
+    fn should_parse_sat_amount() {
+        assert_eq!(parse_sat_amount("0").unwrap(), 0);
+        // 2^53 + 1, which JS numbers can't represent
+        assert_eq!(parse_sat_amount("9007199254740993").unwrap(), 9_007_199_254_740_993);
+        assert_eq!(parse_sat_amount("18446744073709551615").unwrap(), u64::MAX);
+
+        assert!(matches!(
+            parse_sat_amount("18446744073709551616"),
+            Err(Error::AmountOverflow(_))
+        ));
+        for invalid in ["", "-1", "+1", " 1", "1.5", "1e3"] {
+            assert!(matches!(parse_sat_amount(invalid), Err(Error::InvalidAmount(_))));
+        }
+    }
+
+    #[test]
+    fn should_order_transactions_with_same_time_by_txid() {
+        let confirmed = TransactionTime::Confirmed {
+            confirmation_time: 1733470000,
+        };
+        let txs = vec![
+            transaction(9, confirmed),
+            transaction(5, confirmed),
+            transaction(7, TransactionTime::Unconfirmed { last_seen: 1733480000 }),
+            transaction(6, TransactionTime::Unconfirmed { last_seen: 1733490000 }),
+        ];
+
+        let sort = |txs: Vec<TransactionDetails>, sort_order: SortOrder| {
+            sort_and_paginate_txs(txs, Pagination::default(), Some(sort_order))
+                .into_iter()
+                .map(|tx| tx.txid.to_byte_array()[0])
+                .collect::<Vec<_>>()
+        };
+
+        let reversed_txs = txs.iter().rev().cloned().collect::<Vec<_>>();
+
+        assert_eq!(sort(txs.clone(), SortOrder::Asc), vec![5, 9, 6, 7]);
+        assert_eq!(sort(reversed_txs.clone(), SortOrder::Asc), vec![5, 9, 6, 7]);
+
+        assert_eq!(sort(txs, SortOrder::Desc), vec![6, 7, 5, 9]);
+        assert_eq!(sort(reversed_txs, SortOrder::Desc), vec![6, 7, 5, 9]);
+    }
+
+    #[test]
+    fn should_paginate_sorted_transactions() {
+        assert_eq!(sorted_ids(Some(SortOrder::Desc), Pagination::new(1, 2)), vec![4, 1]);
+    }
 
     #[test]
     fn should_return_max_value() {
@@ -198,6 +433,16 @@ mod tests {
         )
     }
 
+    #[test]
+    fn should_convert_receive_to_fiat() {
+        assert_eq!(convert_to_fiat(100_000, &usd_exchange_rate()), 98.91)
+    }
+
+    #[test]
+    fn should_convert_spend_to_fiat() {
+        assert_eq!(convert_to_fiat(-250_000, &usd_exchange_rate()), -247.26)
+    }
+
     #[test]
     fn should_do_nothing_sat_to_sat() {
         assert_eq!(
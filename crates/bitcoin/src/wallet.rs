@@ -5,26 +5,103 @@ use andromeda_api::ProtonWalletApiClient;
 use andromeda_common::{FromParts, Network, ScriptType};
 use bdk_wallet::{
     bitcoin::{
-        bip32::{DerivationPath, Xpriv},
+        bip32::{ChildNumber, DerivationPath, Xpriv, Xpub},
+        hashes::{hmac, sha512, Hash, HashEngine},
         secp256k1::Secp256k1,
-        Amount, NetworkKind,
+        Amount, NetworkKind, Txid,
     },
+    keys::bip39::WordCount,
     Balance, WalletPersister,
 };
-use futures::future::try_join_all;
+use futures::{
+    future::{join_all, try_join_all},
+    stream, StreamExt,
+};
 
-use super::{account::Account, transactions::Pagination, utils::sort_and_paginate_txs};
+use super::{
+    account::{watch_only_derivation_path, Account},
+    transactions::Pagination,
+    utils::sort_and_paginate_txs,
+};
 use crate::{
-    blockchain_client::BlockchainClient,
+    blockchain_client::{BlockchainClient, SyncReport},
     error::Error,
     mnemonic::Mnemonic,
     storage::{WalletConnectorFactory, WalletPersisterConnector},
-    transactions::{ToTransactionDetails, TransactionDetails},
-    utils::SortOrder,
+    transactions::{ToTransactionDetails, TransactionDetails, TransactionKind},
+    utils::{parse_txid, SortOrder},
 };
 
+/// BIP85 application number for BIP39 mnemonics, under the `83696968'`
+/// purpose
+const BIP85_PURPOSE: u32 = 83696968;
+const BIP85_BIP39_APPLICATION: u32 = 39;
+/// BIP85 language code for english word list
+const BIP85_ENGLISH_LANGUAGE: u32 = 0;
+const BIP85_HMAC_KEY: &[u8] = b"bip-entropy-from-k";
+
 const ACCOUNT_DISCOVERY_STOP_GAP: u32 = 2;
 const ADDRESS_DISCOVERY_STOP_GAP: usize = 10;
+/// Maximum number of accounts synced at the same time, each sync already
+/// sending parallel requests on its own
+const PARALLEL_ACCOUNT_SYNCS: usize = 3;
+
+/// Fingerprints identifying an account: the one of the wallet master key and
+/// the one of the account-level extended key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountFingerprint {
+    pub master: String,
+    pub account: String,
+}
+
+/// Collapses entries of a same transaction seen by several accounts of the
+/// wallet (i.e. transfers between accounts) into a single entry tagged as an
+/// internal transfer. The spending account is kept as the entry's account.
+fn merge_internal_transfers(txs: Vec<TransactionDetails>) -> Vec<TransactionDetails> {
+    let mut merged: Vec<TransactionDetails> = Vec::new();
+    let mut index_by_txid: HashMap<Txid, usize> = HashMap::new();
+
+    for tx in txs {
+        let Some(&index) = index_by_txid.get(&tx.txid) else {
+            index_by_txid.insert(tx.txid, merged.len());
+            merged.push(tx);
+            continue;
+        };
+
+        let entry = &mut merged[index];
+
+        let other_account = if tx.sent > entry.sent {
+            std::mem::replace(&mut entry.account_derivation_path, tx.account_derivation_path)
+        } else {
+            tx.account_derivation_path
+        };
+
+        entry.received += tx.received;
+        entry.sent += tx.sent;
+        entry.fees = entry.fees.or(tx.fees);
+
+        for (input, other_input) in entry.inputs.iter_mut().zip(tx.inputs) {
+            if input.previous_output.is_none() {
+                input.previous_output = other_input.previous_output;
+            }
+        }
+
+        for (output, other_output) in entry.outputs.iter_mut().zip(tx.outputs) {
+            output.is_mine |= other_output.is_mine;
+        }
+
+        match &mut entry.kind {
+            TransactionKind::InternalTransfer { accounts } => accounts.push(other_account),
+            TransactionKind::External => {
+                entry.kind = TransactionKind::InternalTransfer {
+                    accounts: vec![other_account],
+                }
+            }
+        }
+    }
+
+    merged
+}
 
 #[derive(Debug)]
 pub struct Wallet<C: WalletPersisterConnector<P>, P: WalletPersister> {
@@ -34,22 +111,25 @@ pub struct Wallet<C: WalletPersisterConnector<P>, P: WalletPersister> {
 }
 
 impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
-    pub fn new(network: Network, bip39_mnemonic: String, bip38_passphrase: Option<String>) -> Result<Self, Error> {
+    /// Derives the master key from the mnemonic and the optional passphrase
+    fn master_key(
+        network_kind: NetworkKind,
+        bip39_mnemonic: String,
+        bip38_passphrase: Option<String>,
+    ) -> Result<Xpriv, Error> {
         let mnemonic = Mnemonic::from_string(bip39_mnemonic)?;
+        let seed = mnemonic.inner().to_seed(bip38_passphrase.unwrap_or_default());
 
+        Ok(Xpriv::new_master(network_kind, &seed)?)
+    }
+
+    pub fn new(network: Network, bip39_mnemonic: String, bip38_passphrase: Option<String>) -> Result<Self, Error> {
         let network_kind = match network {
             Network::Bitcoin => NetworkKind::Main,
             _ => NetworkKind::Test,
         };
 
-        let mprv = Xpriv::new_master(
-            network_kind,
-            &mnemonic.inner().to_seed(match bip38_passphrase {
-                Some(bip38_passphrase) => bip38_passphrase,
-                None => "".to_string(),
-            }),
-        )
-        .unwrap();
+        let mprv = Self::master_key(network_kind, bip39_mnemonic, bip38_passphrase)?;
 
         Ok(Wallet {
             mprv,
@@ -62,6 +142,10 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
         (self.mprv, self.network)
     }
 
+    /// Adds an account to the wallet at the given derivation path. If an
+    /// account is already registered at this path, it is returned as is
+    /// instead of building a second wallet over the same keys, provided it
+    /// has the same script type.
     pub fn add_account<F>(
         &mut self,
         script_type: ScriptType,
@@ -71,6 +155,17 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
     where
         F: WalletConnectorFactory<C, P>,
     {
+        if let Some(account) = self.get_account(&derivation_path) {
+            if account.get_script_type() != script_type {
+                return Err(Error::AccountScriptTypeMismatch {
+                    existing: account.get_script_type(),
+                    requested: script_type,
+                });
+            }
+
+            return Ok(account);
+        }
+
         let account = Account::new(self.mprv, self.network, script_type, derivation_path, factory)?;
 
         let derivation_path = account.get_derivation_path();
@@ -82,6 +177,37 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
         Ok(account_arc)
     }
 
+    /// Adds a watch-only account built from an account-level extended public
+    /// key. If the same key is already registered at the inferred derivation
+    /// path, its account is returned as is, while another key at this path is
+    /// rejected.
+    pub fn add_watch_only_account<F>(
+        &mut self,
+        script_type: ScriptType,
+        account_xpub: Xpub,
+        factory: F,
+    ) -> Result<Arc<Account<C, P>>, Error>
+    where
+        F: WalletConnectorFactory<C, P>,
+    {
+        let derivation_path = watch_only_derivation_path(&account_xpub, self.network, script_type)?;
+        if let Some(account) = self.get_account(&derivation_path) {
+            if account.get_account_xpub() != account_xpub {
+                return Err(Error::AccountXpubMismatch(derivation_path));
+            }
+
+            return Ok(account);
+        }
+
+        let account = Account::new_watch_only(account_xpub, self.network, script_type, factory)?;
+
+        let account_arc = Arc::new(account);
+
+        self.accounts.insert(derivation_path, account_arc.clone());
+
+        Ok(account_arc)
+    }
+
     pub fn get_account(&self, derivation_path: &DerivationPath) -> Option<Arc<Account<C, P>>> {
         self.accounts.get(derivation_path).cloned()
     }
@@ -90,6 +216,26 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
         self.accounts.values().cloned().collect::<Vec<_>>()
     }
 
+    /// Returns the derivation paths of the accounts added to the wallet,
+    /// sorted in ascending order.
+    pub fn list_accounts(&self) -> Vec<DerivationPath> {
+        let mut derivation_paths = self.accounts.keys().cloned().collect::<Vec<_>>();
+        derivation_paths.sort();
+
+        derivation_paths
+    }
+
+    /// Removes the account at the given derivation path from the wallet.
+    /// Persisted data of the account is left untouched.
+    pub fn remove_account(&mut self, derivation_path: &DerivationPath) -> Result<(), Error> {
+        self.accounts
+            .remove(derivation_path)
+            .map(|_| ())
+            .ok_or(Error::AccountNotFound)
+    }
+
+    /// Returns the balance of the wallet, summing each balance bucket
+    /// (confirmed, pending, immature) over all the added accounts.
     pub async fn get_balance(&self) -> Result<Balance, Error> {
         let async_iter = self.accounts.keys().map(|account_key| async move {
             let account = self.accounts.get(account_key).ok_or(Error::AccountNotFound)?;
@@ -113,6 +259,48 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
         Ok(balance)
     }
 
+    /// Returns the balance of each account added to the wallet, sorted by
+    /// derivation path.
+    pub async fn get_balance_per_account(&self) -> Vec<(DerivationPath, Balance)> {
+        let async_iter = self.list_accounts().into_iter().map(|derivation_path| async move {
+            let balance = match self.accounts.get(&derivation_path) {
+                Some(account) => account.get_balance().await,
+                None => Balance::default(),
+            };
+
+            (derivation_path, balance)
+        });
+
+        join_all(async_iter).await
+    }
+
+    /// Fully syncs every account added to the wallet, a few at a time, and
+    /// applies the resulting updates. Each account gets its own result so
+    /// that one failing sync doesn't prevent the others from being applied.
+    /// Results are sorted by derivation path.
+    pub async fn sync_all(
+        &self,
+        client: &BlockchainClient,
+        stop_gap: Option<usize>,
+    ) -> Vec<(DerivationPath, Result<SyncReport, Error>)> {
+        let async_iter = self.list_accounts().into_iter().map(|derivation_path| async move {
+            let result = match self.accounts.get(&derivation_path) {
+                Some(account) => match client.full_sync(account, stop_gap, None).await {
+                    Ok(update) => account.apply_update(update).await,
+                    Err(error) => Err(error),
+                },
+                None => Err(Error::AccountNotFound),
+            };
+
+            (derivation_path, result)
+        });
+
+        stream::iter(async_iter)
+            .buffered(PARALLEL_ACCOUNT_SYNCS)
+            .collect()
+            .await
+    }
+
     pub async fn discover_accounts<F>(
         &self,
         proton_api_client: ProtonWalletApiClient,
@@ -168,6 +356,8 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
         Ok(discovered_accounts)
     }
 
+    /// Returns the transactions of all the accounts of the wallet. Transfers
+    /// between accounts appear once, as [`TransactionKind::InternalTransfer`].
     pub async fn get_transactions(
         &self,
         pagination: Option<Pagination>,
@@ -193,7 +383,7 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
             .flatten()
             .collect::<Vec<_>>();
 
-        Ok(sort_and_paginate_txs(txs, pagination, sort))
+        Ok(sort_and_paginate_txs(merge_internal_transfers(txs), pagination, sort))
     }
 
     pub async fn get_transaction(
@@ -201,10 +391,12 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
         derivation_path: &DerivationPath,
         txid: String,
     ) -> Result<TransactionDetails, Error> {
+        parse_txid(&txid)?;
+
         self.accounts
             .get(derivation_path)
             .ok_or(Error::AccountNotFound)?
-            .get_transaction(txid)
+            .get_transaction(txid, None)
             .await
     }
 
@@ -212,11 +404,82 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
         self.network
     }
 
+    /// Returns the master fingerprint the given mnemonic and passphrase would
+    /// produce, without building a wallet. Any passphrase yields a valid but
+    /// different wallet, so this lets users confirm they typed the expected
+    /// one. The fingerprint doesn't depend on the network.
+    pub fn fingerprint_for_passphrase(
+        bip39_mnemonic: String,
+        bip38_passphrase: Option<String>,
+    ) -> Result<String, Error> {
+        let secp = Secp256k1::new();
+        let mprv = Self::master_key(NetworkKind::Main, bip39_mnemonic, bip38_passphrase)?;
+
+        Ok(mprv.fingerprint(&secp).to_string())
+    }
+
+    /// Returns the master fingerprint of the wallet, which depends on the
+    /// passphrase the wallet was created with.
     pub fn get_fingerprint(&self) -> String {
         let secp = Secp256k1::new();
         self.mprv.fingerprint(&secp).to_string()
     }
 
+    /// Derives a child mnemonic from the wallet master key as defined in BIP85,
+    /// at `m/83696968'/39'/0'/{words}'/{index}'`. The child mnemonic uses the
+    /// english word list and can be used as an independent wallet seed.
+    pub fn derive_bip85_mnemonic(&self, index: u32, word_count: WordCount) -> Result<Mnemonic, Error> {
+        let (words, entropy_len) = match word_count {
+            WordCount::Words12 => (12, 16),
+            WordCount::Words15 => (15, 20),
+            WordCount::Words18 => (18, 24),
+            WordCount::Words21 => (21, 28),
+            WordCount::Words24 => (24, 32),
+        };
+
+        let derivation_path = DerivationPath::from(vec![
+            ChildNumber::from_hardened_idx(BIP85_PURPOSE)?,
+            ChildNumber::from_hardened_idx(BIP85_BIP39_APPLICATION)?,
+            ChildNumber::from_hardened_idx(BIP85_ENGLISH_LANGUAGE)?,
+            ChildNumber::from_hardened_idx(words)?,
+            ChildNumber::from_hardened_idx(index)?,
+        ]);
+
+        let secp = Secp256k1::new();
+        let child_xprv = self.mprv.derive_priv(&secp, &derivation_path)?;
+
+        let mut engine = hmac::HmacEngine::<sha512::Hash>::new(BIP85_HMAC_KEY);
+        engine.input(&child_xprv.private_key.secret_bytes());
+        let entropy = hmac::Hmac::<sha512::Hash>::from_engine(engine).to_byte_array();
+
+        Mnemonic::new_with(&entropy[..entropy_len])
+    }
+
+    /// Returns the master fingerprint along with the fingerprint of the
+    /// account-level extended key at the given derivation path. Both are
+    /// derived locally from the master key.
+    pub fn get_account_fingerprint(&self, derivation_path: &DerivationPath) -> Result<AccountFingerprint, Error> {
+        let secp = Secp256k1::new();
+        let account_xprv = self.mprv.derive_priv(&secp, derivation_path)?;
+
+        Ok(AccountFingerprint {
+            master: self.mprv.fingerprint(&secp).to_string(),
+            account: account_xprv.fingerprint(&secp).to_string(),
+        })
+    }
+
+    /// Checks that the given fingerprint matches the account-level extended
+    /// key at the given derivation path, e.g. when reconstructing accounts
+    /// from server data.
+    pub fn matches_account_fingerprint(
+        &self,
+        derivation_path: &DerivationPath,
+        fingerprint: &str,
+    ) -> Result<bool, Error> {
+        let account_fingerprint = self.get_account_fingerprint(derivation_path)?;
+        Ok(account_fingerprint.account.eq_ignore_ascii_case(fingerprint))
+    }
+
     pub fn clear_store(&self) -> Result<(), Error> {
         for a in self.get_accounts().into_iter() {
             a.clear_store()?;
@@ -225,3 +488,387 @@ impl<C: WalletPersisterConnector<P>, P: WalletPersister> Wallet<C, P> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+    use andromeda_api::{address::scripthash_for_script, tests::utils::setup_test_connection, BASE_WALLET_API_V1};
+    use andromeda_common::{Network, ScriptType};
+    use bdk_wallet::{
+        bitcoin::{
+            bip32::{DerivationPath, Xpriv},
+            Amount, TxOut,
+        },
+        keys::bip39::WordCount,
+        serde_json, KeychainKind, Update,
+    };
+    use wiremock::{
+        matchers::{body_string_contains, method, path, path_regex},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::{AccountFingerprint, Wallet};
+    use crate::{
+        account::Account,
+        blockchain_client::BlockchainClient,
+        error::Error,
+        read_mock_file,
+        storage::{InMemoryPersister, InMemoryStorageFactory, MemoryPersisted},
+        tests::utils::receive_unconfirmed_tx,
+        transactions::TransactionKind,
+    };
+
+    const TEST_MNEMONIC: &str = "category law logic swear involve banner pink room diesel fragile sunset remove whale lounge captain code hobby lesson material current moment funny vast fade";
+
+    fn set_test_wallet() -> Wallet<MemoryPersisted, MemoryPersisted> {
+        Wallet::new(Network::Testnet, TEST_MNEMONIC.to_string(), None).unwrap()
+    }
+
+    async fn receive_unconfirmed(account: &Account<MemoryPersisted, MemoryPersisted>, value: u64) {
+        let address = account.peek_receive_address(0).await.unwrap().address;
+        receive_unconfirmed_tx(
+            account,
+            vec![TxOut {
+                value: Amount::from_sat(value),
+                script_pubkey: address.script_pubkey(),
+            }],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn should_add_account_with_in_memory_storage() {
+        let factory = InMemoryStorageFactory::new();
+        let derivation_path = DerivationPath::from_str("m/84'/1'/0'").unwrap();
+
+        let mut wallet: Wallet<InMemoryPersister, InMemoryPersister> =
+            Wallet::new(Network::Testnet, TEST_MNEMONIC.to_string(), None).unwrap();
+        let account = wallet
+            .add_account(ScriptType::NativeSegwit, derivation_path.clone(), factory.clone())
+            .unwrap();
+        let address = account.peek_receive_address(14).await.unwrap().address;
+        assert_eq!(address.to_string(), "tb1qre68v280t3t5mdy0hcu86fnx3h289h0arfe6lr");
+
+        // Reveals receive addresses 0 to 2, persisted along with the next update
+        for _ in 0..3 {
+            account.get_next_receive_address().await.unwrap();
+        }
+        account.apply_update(Update::default()).await.unwrap();
+
+        // Account is loaded back from the shared store
+        let mut reloaded_wallet: Wallet<InMemoryPersister, InMemoryPersister> =
+            Wallet::new(Network::Testnet, TEST_MNEMONIC.to_string(), None).unwrap();
+        let reloaded_account = reloaded_wallet
+            .add_account(ScriptType::NativeSegwit, derivation_path, factory)
+            .unwrap();
+        assert_eq!(
+            reloaded_account.peek_receive_address(14).await.unwrap().address,
+            address
+        );
+        assert_eq!(
+            reloaded_account
+                .get_wallet()
+                .await
+                .derivation_index(KeychainKind::External),
+            Some(2)
+        );
+    }
+
+    #[tokio::test]
+    async fn should_get_balance_per_account() {
+        let mut wallet = set_test_wallet();
+        let first_path = DerivationPath::from_str("m/84'/1'/0'").unwrap();
+        let second_path = DerivationPath::from_str("m/84'/1'/1'").unwrap();
+
+        let first_account = wallet
+            .add_account(ScriptType::NativeSegwit, first_path.clone(), MemoryPersisted)
+            .unwrap();
+        let second_account = wallet
+            .add_account(ScriptType::NativeSegwit, second_path.clone(), MemoryPersisted)
+            .unwrap();
+
+        receive_unconfirmed(&first_account, 10_000).await;
+        receive_unconfirmed(&second_account, 25_000).await;
+
+        let balance = wallet.get_balance().await.unwrap();
+        assert_eq!(balance.untrusted_pending.to_sat(), 35_000);
+        assert_eq!(balance.total().to_sat(), 35_000);
+
+        let balances = wallet.get_balance_per_account().await;
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[0].0, first_path);
+        assert_eq!(balances[0].1.untrusted_pending.to_sat(), 10_000);
+        assert_eq!(balances[1].0, second_path);
+        assert_eq!(balances[1].1.untrusted_pending.to_sat(), 25_000);
+    }
+
+    #[tokio::test]
+    async fn should_sync_all_accounts_independently() {
+        let mut wallet = Wallet::<MemoryPersisted, MemoryPersisted>::new(Network::Regtest, "category law logic swear involve banner pink room diesel fragile sunset remove whale lounge captain code hobby lesson material current moment funny vast fade".to_string(), None).unwrap();
+        let first_path = DerivationPath::from_str("m/84'/1'/0'").unwrap();
+        let second_path = DerivationPath::from_str("m/84'/1'/1'").unwrap();
+
+        let first_account = wallet
+            .add_account(ScriptType::NativeSegwit, first_path.clone(), MemoryPersisted)
+            .unwrap();
+        wallet
+            .add_account(ScriptType::NativeSegwit, second_path.clone(), MemoryPersisted)
+            .unwrap();
+
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path(format!("{}/blocks", BASE_WALLET_API_V1)))
+            .respond_with(ResponseTemplate::new(200).set_body_string(read_mock_file!("get_blocks_body")))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path_regex(".*/height/.*"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(read_mock_file!("get_block_hash_body")))
+            .mount(&mock_server)
+            .await;
+
+        // Scripthashes lookup fails for the first account only
+        let first_address = first_account.peek_receive_address(0).await.unwrap().address;
+        let req_path = format!("{}/addresses/scripthashes/transactions", BASE_WALLET_API_V1);
+        Mock::given(method("POST"))
+            .and(path(req_path.clone()))
+            .and(body_string_contains(scripthash_for_script(
+                &first_address.script_pubkey(),
+            )))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(req_path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Code": 1000,
+                "Transactions": {}
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = BlockchainClient::new(setup_test_connection(mock_server.uri()));
+        let results = wallet.sync_all(&client, None).await;
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, first_path);
+        assert!(results[0].1.is_err());
+        assert_eq!(results[1].0, second_path);
+        assert_eq!(results[1].1.as_ref().unwrap().tip_height, 3599);
+    }
+
+    #[tokio::test]
+    async fn should_merge_internal_transfers() {
+        let mut wallet = set_test_wallet();
+        let first_path = DerivationPath::from_str("m/84'/1'/0'").unwrap();
+        let second_path = DerivationPath::from_str("m/84'/1'/1'").unwrap();
+
+        let first_account = wallet
+            .add_account(ScriptType::NativeSegwit, first_path.clone(), MemoryPersisted)
+            .unwrap();
+        let second_account = wallet
+            .add_account(ScriptType::NativeSegwit, second_path.clone(), MemoryPersisted)
+            .unwrap();
+
+        receive_unconfirmed(&first_account, 10_000).await;
+
+        let recipient = second_account.peek_receive_address(0).await.unwrap().address;
+        let psbt = {
+            let mut wallet_lock = first_account.get_mutable_wallet().await;
+            let mut tx_builder = wallet_lock.build_tx();
+            tx_builder.add_recipient(recipient.script_pubkey(), Amount::from_sat(4_000));
+            tx_builder.finish().unwrap()
+        };
+        let fee = psbt.fee().unwrap().to_sat();
+
+        for account in [&first_account, &second_account] {
+            account
+                .get_mutable_wallet()
+                .await
+                .apply_unconfirmed_txs([(psbt.unsigned_tx.clone(), 1733468900)]);
+        }
+
+        let transactions = wallet.get_transactions(None, None).await.unwrap();
+        assert_eq!(transactions.len(), 2);
+
+        let internal_transfers = transactions
+            .iter()
+            .filter(|tx| tx.txid == psbt.unsigned_tx.compute_txid())
+            .collect::<Vec<_>>();
+        assert_eq!(internal_transfers.len(), 1);
+
+        let internal_transfer = internal_transfers[0];
+        assert_eq!(
+            internal_transfer.kind,
+            TransactionKind::InternalTransfer {
+                accounts: vec![second_path]
+            }
+        );
+        assert_eq!(internal_transfer.account_derivation_path, first_path);
+        assert_eq!(internal_transfer.value(), -(fee as i64));
+        assert!(internal_transfer.outputs.iter().all(|output| output.is_mine));
+    }
+
+    #[test]
+    fn should_not_add_account_twice() {
+        let mut wallet = set_test_wallet();
+        let derivation_path = DerivationPath::from_str("m/84'/1'/0'").unwrap();
+
+        let account = wallet
+            .add_account(ScriptType::NativeSegwit, derivation_path.clone(), MemoryPersisted)
+            .unwrap();
+        let duplicate = wallet
+            .add_account(ScriptType::NativeSegwit, derivation_path, MemoryPersisted)
+            .unwrap();
+
+        assert!(Arc::ptr_eq(&account, &duplicate));
+        assert_eq!(wallet.get_accounts().len(), 1);
+    }
+
+    #[test]
+    fn should_not_add_account_twice_with_another_script_type() {
+        let mut wallet = set_test_wallet();
+        let derivation_path = DerivationPath::from_str("m/84'/1'/0'").unwrap();
+
+        wallet
+            .add_account(ScriptType::NativeSegwit, derivation_path.clone(), MemoryPersisted)
+            .unwrap();
+
+        assert!(matches!(
+            wallet.add_account(ScriptType::Taproot, derivation_path, MemoryPersisted),
+            Err(Error::AccountScriptTypeMismatch {
+                existing: ScriptType::NativeSegwit,
+                requested: ScriptType::Taproot,
+            })
+        ));
+        assert_eq!(wallet.get_accounts().len(), 1);
+    }
+
+    #[test]
+    fn should_add_watch_only_account_once_per_key() {
+        let mut wallet = set_test_wallet();
+        let derivation_path = DerivationPath::from_str("m/84'/1'/0'").unwrap();
+
+        let account = wallet
+            .add_account(ScriptType::NativeSegwit, derivation_path.clone(), MemoryPersisted)
+            .unwrap();
+
+        // Same key at the same derivation path gives back the added account
+        let duplicate = wallet
+            .add_watch_only_account(ScriptType::NativeSegwit, account.get_account_xpub(), MemoryPersisted)
+            .unwrap();
+        assert!(Arc::ptr_eq(&account, &duplicate));
+
+        // Another key at the same derivation path is rejected
+        let mut other_wallet = Wallet::<MemoryPersisted, MemoryPersisted>::new(
+            Network::Testnet,
+            TEST_MNEMONIC.to_string(),
+            Some("passphrase".to_string()),
+        )
+        .unwrap();
+        let other_account = other_wallet
+            .add_account(ScriptType::NativeSegwit, derivation_path.clone(), MemoryPersisted)
+            .unwrap();
+
+        assert!(matches!(
+            wallet.add_watch_only_account(ScriptType::NativeSegwit, other_account.get_account_xpub(), MemoryPersisted),
+            Err(Error::AccountXpubMismatch(path)) if path == derivation_path
+        ));
+        assert_eq!(wallet.get_accounts().len(), 1);
+    }
+
+    #[test]
+    fn should_list_and_remove_accounts() {
+        let mut wallet = set_test_wallet();
+        let first_path = DerivationPath::from_str("m/84'/1'/0'").unwrap();
+        let second_path = DerivationPath::from_str("m/86'/1'/0'").unwrap();
+
+        wallet
+            .add_account(ScriptType::NativeSegwit, first_path.clone(), MemoryPersisted)
+            .unwrap();
+        wallet
+            .add_account(ScriptType::Taproot, second_path.clone(), MemoryPersisted)
+            .unwrap();
+        assert_eq!(wallet.list_accounts(), vec![first_path.clone(), second_path.clone()]);
+
+        wallet.remove_account(&first_path).unwrap();
+        assert_eq!(wallet.list_accounts(), vec![second_path]);
+
+        assert!(matches!(
+            wallet.remove_account(&first_path),
+            Err(Error::AccountNotFound)
+        ));
+    }
+
+    #[test]
+    fn should_get_account_fingerprint() {
+        let wallet = set_test_wallet();
+        let derivation_path = DerivationPath::from_str("m/84'/1'/0'").unwrap();
+
+        let fingerprint = wallet.get_account_fingerprint(&derivation_path).unwrap();
+        assert_eq!(
+            fingerprint,
+            AccountFingerprint {
+                master: "a7fd5114".to_string(),
+                account: "926a1a5c".to_string(),
+            }
+        );
+        assert_eq!(fingerprint.master, wallet.get_fingerprint());
+    }
+
+    #[test]
+    fn should_get_fingerprint_for_passphrase() {
+        let empty_passphrase_fingerprint =
+            Wallet::<MemoryPersisted, MemoryPersisted>::fingerprint_for_passphrase(TEST_MNEMONIC.to_string(), None)
+                .unwrap();
+        let abc_passphrase_fingerprint = Wallet::<MemoryPersisted, MemoryPersisted>::fingerprint_for_passphrase(
+            TEST_MNEMONIC.to_string(),
+            Some("abc".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(empty_passphrase_fingerprint, "a7fd5114");
+        assert_ne!(empty_passphrase_fingerprint, abc_passphrase_fingerprint);
+
+        let wallet: Wallet<MemoryPersisted, MemoryPersisted> =
+            Wallet::new(Network::Testnet, TEST_MNEMONIC.to_string(), Some("abc".to_string())).unwrap();
+        assert_eq!(wallet.get_fingerprint(), abc_passphrase_fingerprint);
+    }
+
+    #[test]
+    fn should_derive_bip85_mnemonic() {
+        // Test vector from BIP85
+        let wallet: Wallet<MemoryPersisted, MemoryPersisted> = Wallet {
+            mprv: Xpriv::from_str("xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb").unwrap(),
+            accounts: HashMap::new(),
+            network: Network::Bitcoin,
+        };
+
+        let mnemonic = wallet.derive_bip85_mnemonic(0, WordCount::Words12).unwrap();
+        assert_eq!(
+            mnemonic.as_string(),
+            "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose"
+        );
+
+        let mnemonic = wallet.derive_bip85_mnemonic(1, WordCount::Words12).unwrap();
+        assert_ne!(
+            mnemonic.as_string(),
+            "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose"
+        );
+    }
+
+    #[test]
+    fn should_match_account_fingerprint() {
+        let wallet = set_test_wallet();
+        let derivation_path = DerivationPath::from_str("m/84'/1'/0'").unwrap();
+
+        assert!(wallet
+            .matches_account_fingerprint(&derivation_path, "926a1a5c")
+            .unwrap());
+        assert!(!wallet
+            .matches_account_fingerprint(&derivation_path, "a7fd5114")
+            .unwrap());
+    }
+}
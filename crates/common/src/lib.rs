@@ -51,13 +51,14 @@ impl From<Network> for BdkNetwork {
 }
 
 impl From<BdkNetwork> for Network {
+    /// BDK's network enum is non-exhaustive, any test network it might add
+    /// is mapped to testnet
     fn from(network: BdkNetwork) -> Self {
         match network {
             BdkNetwork::Bitcoin => Network::Bitcoin,
-            BdkNetwork::Testnet => Network::Testnet,
             BdkNetwork::Signet => Network::Signet,
             BdkNetwork::Regtest => Network::Regtest,
-            _ => panic!("Network {} not supported", network),
+            _ => Network::Testnet,
         }
     }
 }
@@ -86,6 +87,7 @@ pub enum BitcoinUnit {
     /// 100,000 sats
     MBTC,
     /// 1 sat
+    #[serde(alias = "SAT")]
     SATS,
 }
 
@@ -131,6 +133,43 @@ impl FromParts for DerivationPath {
     }
 }
 
+/// Levels of a standard BIP44 account-level derivation path
+/// (`m/purpose'/coin_type'/account'`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DerivationPathParts {
+    pub purpose: u32,
+    pub coin_type: u32,
+    pub account: u32,
+    /// Whether purpose, coin type and account levels are respectively
+    /// hardened
+    pub hardened: [bool; 3],
+}
+
+pub trait ToParts {
+    fn to_parts(&self) -> Option<DerivationPathParts>;
+}
+
+impl ToParts for DerivationPath {
+    /// Splits a 3-level derivation path into its purpose, coin type and
+    /// account levels. Returns `None` for paths of any other depth.
+    fn to_parts(&self) -> Option<DerivationPathParts> {
+        let [purpose, coin_type, account] = self.as_ref() else {
+            return None;
+        };
+
+        let index = |child: &ChildNumber| match *child {
+            ChildNumber::Normal { index } | ChildNumber::Hardened { index } => index,
+        };
+
+        Some(DerivationPathParts {
+            purpose: index(purpose),
+            coin_type: index(coin_type),
+            account: index(account),
+            hardened: [purpose, coin_type, account].map(ChildNumber::is_hardened),
+        })
+    }
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
 pub enum ScriptType {
     /// Legacy scripts : https://bitcoinwiki.org/wiki/pay-to-pubkey-hash
@@ -189,3 +228,53 @@ impl From<ScriptType> for ChildNumber {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bitcoin::{bip32::DerivationPath, Network as BdkNetwork};
+
+    use super::{DerivationPathParts, Network, ToParts};
+
+    #[test]
+    fn should_convert_network_to_and_from_bdk() {
+        for (network, bdk_network) in [
+            (Network::Bitcoin, BdkNetwork::Bitcoin),
+            (Network::Testnet, BdkNetwork::Testnet),
+            (Network::Signet, BdkNetwork::Signet),
+            (Network::Regtest, BdkNetwork::Regtest),
+        ] {
+            assert_eq!(BdkNetwork::from(network), bdk_network);
+            assert_eq!(Network::from(bdk_network), network);
+        }
+    }
+
+    #[test]
+    fn should_split_standard_derivation_path_into_parts() {
+        let derivation_path = DerivationPath::from_str("m/84'/0'/0'").unwrap();
+
+        assert_eq!(
+            derivation_path.to_parts(),
+            Some(DerivationPathParts {
+                purpose: 84,
+                coin_type: 0,
+                account: 0,
+                hardened: [true, true, true],
+            })
+        );
+
+        let derivation_path = DerivationPath::from_str("m/86'/1'/5").unwrap();
+        assert_eq!(
+            derivation_path.to_parts().map(|parts| (parts.account, parts.hardened)),
+            Some((5, [true, true, false]))
+        );
+    }
+
+    #[test]
+    fn should_not_split_non_standard_derivation_path() {
+        assert_eq!(DerivationPath::from_str("m/84'/0'").unwrap().to_parts(), None);
+        assert_eq!(DerivationPath::from_str("m/84'/0'/0'/0/1").unwrap().to_parts(), None);
+        assert_eq!(DerivationPath::master().to_parts(), None);
+    }
+}
@@ -1,5 +1,7 @@
 use std::time::Duration;
 
+use crate::{BitcoinUnit, BITCOIN, MILLI_BITCOIN, SATOSHI};
+
 #[cfg(target_arch = "wasm32")]
 use instant;
 
@@ -14,3 +16,74 @@ pub fn now() -> Duration {
         .duration_since(std::time::SystemTime::UNIX_EPOCH)
         .unwrap();
 }
+
+/// Formats an amount of sats in the given unit: as an integer for sats, and
+/// with up to 5 (mBTC) or 8 (BTC) decimals otherwise, trailing zeros being
+/// trimmed. When `group_separator` is set, digits of the integer part are
+/// grouped by thousands using a comma.
+pub fn format_amount(sats: u64, unit: BitcoinUnit, group_separator: bool) -> String {
+    let (divisor, decimals) = match unit {
+        BitcoinUnit::BTC => (BITCOIN, 8),
+        BitcoinUnit::MBTC => (MILLI_BITCOIN, 5),
+        BitcoinUnit::SATS => (SATOSHI, 0),
+    };
+
+    let integer_part = (sats / divisor).to_string();
+    let integer_part = if group_separator {
+        group_thousands(&integer_part)
+    } else {
+        integer_part
+    };
+
+    let fractional_part = sats % divisor;
+    if fractional_part == 0 {
+        return integer_part;
+    }
+
+    let fractional_part = format!("{:0width$}", fractional_part, width = decimals);
+    format!("{}.{}", integer_part, fractional_part.trim_end_matches('0'))
+}
+
+fn group_thousands(digits: &str) -> String {
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_amount;
+    use crate::BitcoinUnit;
+
+    #[test]
+    fn should_format_sats_as_integer() {
+        assert_eq!(format_amount(89377, BitcoinUnit::SATS, false), "89377");
+    }
+
+    #[test]
+    fn should_format_one_sat_in_btc() {
+        assert_eq!(format_amount(1, BitcoinUnit::BTC, false), "0.00000001");
+    }
+
+    #[test]
+    fn should_trim_trailing_zeros() {
+        assert_eq!(format_amount(89_377_000, BitcoinUnit::BTC, false), "0.89377");
+        assert_eq!(format_amount(89_300, BitcoinUnit::MBTC, false), "0.893");
+        assert_eq!(format_amount(200_000_000, BitcoinUnit::BTC, false), "2");
+    }
+
+    #[test]
+    fn should_group_thousands() {
+        assert_eq!(format_amount(89377, BitcoinUnit::SATS, true), "89,377");
+        assert_eq!(format_amount(123_456_789_012, BitcoinUnit::BTC, true), "1,234.56789012");
+        assert_eq!(format_amount(123_456_789_012, BitcoinUnit::BTC, false), "1234.56789012");
+        assert_eq!(format_amount(999, BitcoinUnit::SATS, true), "999");
+    }
+}
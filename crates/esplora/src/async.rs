@@ -20,15 +20,17 @@ use std::{
 use crate::{BlockStatus, BlockSummary, Error, MerkleProof, OutputStatus, Tx, TxStatus};
 use andromeda_api::transaction::RecommendedFees;
 use andromeda_api::{
-    address::{AddressClient, ScriptHashTransactionsPayload},
+    address::{scripthash_for_script, AddressClient, ApiTx, ScriptHashTransactionsPayload},
     block::BlockClient,
-    transaction::{BroadcastMessage, ExchangeRateOrTransactionTime, MempoolInfo, TransactionClient},
+    transaction::{
+        BroadcastMessage, ExchangeRateOrTransactionTime, MempoolAcceptResult, MempoolInfo, TransactionClient,
+    },
     ProtonWalletApiClient,
 };
 use bitcoin::{
     block::Header as BlockHeader,
     consensus::{deserialize, serialize},
-    hashes::{hex::FromHex, sha256, Hash},
+    hashes::hex::FromHex,
     hex::DisplayHex,
     Block, BlockHash, MerkleBlock, ScriptBuf, Transaction, Txid,
 };
@@ -51,7 +53,7 @@ pub struct AsyncClient {
 const TRANSACTIONS_PER_PAGE: u32 = 25;
 
 fn hash_spk(spk: &ScriptBuf) -> String {
-    sha256::Hash::hash(spk.as_bytes()).to_string()
+    scripthash_for_script(spk)
 }
 
 impl AsyncClient {
@@ -115,6 +117,13 @@ impl AsyncClient {
         Ok(info.map(|i| i.into()))
     }
 
+    /// Get transaction info given it's [`Txid`], as returned by Proton Wallet
+    /// API. Unlike [`Self::get_tx_info`], inputs previous outputs are kept.
+    pub async fn get_api_tx_info(&self, txid: &Txid) -> Result<Option<ApiTx>, Error> {
+        let info = self.transaction.get_transaction_info(txid.to_string()).await?;
+        Ok(info)
+    }
+
     #[deprecated(
         since = "0.2.0",
         note = "Deprecated to improve alignment with Esplora API. Users should use `get_block_hash` and `get_header_by_hash` methods directly."
@@ -210,6 +219,15 @@ impl AsyncClient {
         Ok(())
     }
 
+    /// Test whether a [`Transaction`] would be accepted in the mempool,
+    /// without broadcasting it
+    pub async fn test_mempool_accept(&self, transaction: &Transaction) -> Result<MempoolAcceptResult, Error> {
+        Ok(self
+            .transaction
+            .test_mempool_accept(serialize(transaction).to_lower_hex_string())
+            .await?)
+    }
+
     /// Get the current height of the blockchain tip
     pub async fn get_height(&self) -> Result<u32, Error> {
         Ok(self.block.get_tip_height().await?)
@@ -290,6 +308,13 @@ impl AsyncClient {
         Ok(txs_by_spk_map)
     }
 
+    /// Get the whole transaction history of a given script pubkey, as
+    /// returned by Proton Wallet API
+    pub async fn scripthash_api_txs(&self, script: &ScriptBuf) -> Result<Vec<ApiTx>, Error> {
+        let txs = self.address.fetch_all_scripthash_transactions(hash_spk(script)).await?;
+        Ok(txs)
+    }
+
     /// Get an map where the key is the confirmation target (in number of
     /// blocks) and the value is the estimated feerate (in sat/vB).
     pub async fn get_fee_estimates(&self) -> Result<HashMap<String, f64>, Error> {
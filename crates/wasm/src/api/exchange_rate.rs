@@ -1,4 +1,4 @@
-use andromeda_api::exchange_rate::{ApiExchangeRate, ApiFiatCurrency, ExchangeRateClient};
+use andromeda_api::exchange_rate::{ApiExchangeRate, ApiFiatCurrency, ExchangeRateClient, HistoricalExchangeRate};
 use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
@@ -33,6 +33,37 @@ impl From<ApiExchangeRate> for WasmApiExchangeRate {
     }
 }
 
+impl From<WasmApiExchangeRate> for ApiExchangeRate {
+    fn from(value: WasmApiExchangeRate) -> Self {
+        Self {
+            ID: value.ID,
+            BitcoinUnit: value.BitcoinUnit.into(),
+            FiatCurrency: value.FiatCurrency.into(),
+            Sign: value.Sign,
+            ExchangeRateTime: value.ExchangeRateTime,
+            ExchangeRate: value.ExchangeRate,
+            Cents: value.Cents,
+        }
+    }
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[allow(non_snake_case)]
+pub struct WasmHistoricalExchangeRate {
+    pub ExchangeRate: WasmApiExchangeRate,
+    pub EffectiveTimestamp: u64,
+}
+
+impl From<HistoricalExchangeRate> for WasmHistoricalExchangeRate {
+    fn from(value: HistoricalExchangeRate) -> Self {
+        Self {
+            ExchangeRate: value.exchange_rate.into(),
+            EffectiveTimestamp: value.effective_timestamp,
+        }
+    }
+}
+
 #[derive(Tsify, Serialize, Deserialize, Clone)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
 #[allow(non_snake_case)]
@@ -63,6 +94,13 @@ pub struct WasmApiExchangeRateData {
     pub Data: WasmApiExchangeRate,
 }
 
+// We need this wrapper because, tsify doesn't support intoJs in async fns
+#[wasm_bindgen(getter_with_clone)]
+#[allow(non_snake_case)]
+pub struct WasmHistoricalExchangeRateData {
+    pub Data: WasmHistoricalExchangeRate,
+}
+
 // We need this wrapper because, tsify doesn't support intoJs in async fns
 #[wasm_bindgen(getter_with_clone)]
 #[allow(non_snake_case)]
@@ -99,6 +137,19 @@ impl WasmExchangeRateClient {
             .map_err(|e| e.to_js_error())
     }
 
+    #[wasm_bindgen(js_name = "getExchangeRateAt")]
+    pub async fn get_exchange_rate_at(
+        &self,
+        fiat: WasmFiatCurrencySymbol,
+        timestamp: u64,
+    ) -> Result<WasmHistoricalExchangeRateData, JsValue> {
+        self.0
+            .get_exchange_rate_at(fiat.into(), timestamp)
+            .await
+            .map(|n| WasmHistoricalExchangeRateData { Data: n.into() })
+            .map_err(|e| e.to_js_error())
+    }
+
     #[wasm_bindgen(js_name = "getAllFiatCurrencies")]
     pub async fn get_all_fiat_currencies(&self) -> Result<WasmApiFiatCurrencies, JsValue> {
         let currencies = self
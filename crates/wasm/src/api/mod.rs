@@ -1,4 +1,4 @@
-use andromeda_api::{self, ApiConfig, Auth, ProtonWalletApiClient};
+use andromeda_api::{self, ApiConfig, Auth, ChildSession, ProtonWalletApiClient};
 use bitcoin_address::WasmBitcoinAddressClient;
 use email_integration::WasmEmailIntegrationClient;
 use exchange_rate::WasmExchangeRateClient;
@@ -15,7 +15,7 @@ use crate::common::error::ErrorExt;
 mod bitcoin_address;
 mod email_integration;
 mod env;
-mod exchange_rate;
+pub mod exchange_rate;
 mod invite;
 mod network;
 mod payment_gateway;
@@ -31,6 +31,31 @@ pub struct WasmAuthData {
     pub scopes: Vec<String>,
 }
 
+impl From<WasmAuthData> for Auth {
+    fn from(value: WasmAuthData) -> Self {
+        ChildSession {
+            session_id: value.uid,
+            access_token: value.access,
+            refresh_token: value.refresh,
+            scopes: value.scopes,
+        }
+        .into()
+    }
+}
+
+#[wasm_bindgen]
+impl WasmAuthData {
+    #[wasm_bindgen(constructor)]
+    pub fn new(uid: String, access: String, refresh: String, scopes: Vec<String>) -> WasmAuthData {
+        WasmAuthData {
+            uid,
+            access,
+            refresh,
+            scopes,
+        }
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Clone)]
 pub struct WasmProtonWalletApiClient(ProtonWalletApiClient);
@@ -64,17 +89,26 @@ impl WasmProtonWalletApiClient {
         origin: Option<String>,
         url_prefix: Option<String>,
     ) -> Result<WasmProtonWalletApiClient, js_sys::Error> {
-        let config = ApiConfig {
-            spec: (app_version, user_agent),
-            auth: uid_str.map(|u| Auth::external(u)),
-            env: origin,
+        Self::from_auth(
+            app_version,
+            user_agent,
+            uid_str.map(|u| Auth::external(u)),
+            origin,
             url_prefix,
-            store: None,
-            proxy: None,
-        };
+        )
+    }
 
-        let client = ProtonWalletApiClient::from_config(config).map_err(|e| e.to_js_error())?;
-        Ok(WasmProtonWalletApiClient(client))
+    /// Builds a client resuming an authenticated session, e.g. after a reload,
+    /// from its uid, access and refresh tokens
+    #[wasm_bindgen(js_name = fromAuthData)]
+    pub fn from_auth_data(
+        app_version: String,
+        user_agent: String,
+        auth_data: WasmAuthData,
+        origin: Option<String>,
+        url_prefix: Option<String>,
+    ) -> Result<WasmProtonWalletApiClient, js_sys::Error> {
+        Self::from_auth(app_version, user_agent, Some(auth_data.into()), origin, url_prefix)
     }
 
     #[wasm_bindgen]
@@ -95,12 +129,36 @@ impl WasmProtonWalletApiClient {
     }
 }
 
+impl WasmProtonWalletApiClient {
+    fn from_auth(
+        app_version: String,
+        user_agent: String,
+        auth: Option<Auth>,
+        origin: Option<String>,
+        url_prefix: Option<String>,
+    ) -> Result<WasmProtonWalletApiClient, js_sys::Error> {
+        let config = ApiConfig {
+            spec: (app_version, user_agent),
+            auth,
+            env: origin,
+            url_prefix,
+            store: None,
+            proxy: None,
+            request_timeout: None,
+            on_unauthorized: None,
+        };
+
+        let client = ProtonWalletApiClient::from_config(config).map_err(|e| e.to_js_error())?;
+        Ok(WasmProtonWalletApiClient(client))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use andromeda_api::tests::utils::test_spec;
     use wasm_bindgen_test::wasm_bindgen_test;
 
-    use super::WasmProtonWalletApiClient;
+    use super::{WasmAuthData, WasmProtonWalletApiClient};
 
     #[wasm_bindgen_test]
     #[ignore]
@@ -109,4 +167,16 @@ mod tests {
         let client = WasmProtonWalletApiClient::new(test_spec().0, test_spec().1, None, None, None).unwrap();
         client.0.login("pro", "pro").await.unwrap();
     }
+
+    #[wasm_bindgen_test]
+    fn should_create_pw_api_client_from_auth_data() {
+        let auth_data = WasmAuthData::new(
+            "session_uid".to_string(),
+            "access_token".to_string(),
+            "refresh_token".to_string(),
+            vec!["wallet".to_string()],
+        );
+
+        assert!(WasmProtonWalletApiClient::from_auth_data(test_spec().0, test_spec().1, auth_data, None, None).is_ok());
+    }
 }
@@ -23,4 +23,18 @@ impl WasmNetworkClient {
             .map(|n| n.into())
             .map_err(|e| e.to_js_error())
     }
+
+    #[wasm_bindgen(js_name = "getTipHeight")]
+    pub async fn get_tip_height(&self) -> Result<u32, JsValue> {
+        self.0.get_tip_height().await.map_err(|e| e.to_js_error())
+    }
+
+    #[wasm_bindgen(js_name = "getTipHash")]
+    pub async fn get_tip_hash(&self) -> Result<String, JsValue> {
+        self.0
+            .get_tip_hash()
+            .await
+            .map(|hash| hash.to_string())
+            .map_err(|e| e.to_js_error())
+    }
 }
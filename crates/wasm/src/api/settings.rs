@@ -382,6 +382,9 @@ impl From<UserSettings> for WasmUserSettings {
 #[wasm_bindgen(getter_with_clone)]
 pub struct WasmUserSettingsData(pub WasmUserSettings);
 
+#[wasm_bindgen(getter_with_clone)]
+pub struct WasmBitcoinUnitData(pub WasmBitcoinUnit);
+
 #[wasm_bindgen]
 #[derive(Clone)]
 pub struct WasmSettingsClient(SettingsClient);
@@ -464,6 +467,37 @@ impl WasmSettingsClient {
             .map(|settings| WasmUserSettingsData(settings.into()))
     }
 
+    #[wasm_bindgen(js_name = "getPreferredUnit")]
+    pub async fn get_preferred_unit(&self) -> Result<WasmBitcoinUnitData, JsValue> {
+        self.0
+            .get_preferred_unit()
+            .await
+            .map(|unit| WasmBitcoinUnitData(unit.into()))
+            .map_err(|e| e.to_js_error())
+    }
+
+    #[wasm_bindgen(js_name = "getPreferredFiat")]
+    pub async fn get_preferred_fiat(&self) -> Result<String, JsValue> {
+        self.0.get_preferred_fiat().await.map_err(|e| e.to_js_error())
+    }
+
+    #[wasm_bindgen(js_name = "setPreferredUnit")]
+    pub async fn set_preferred_unit(&self, unit: WasmBitcoinUnit) -> Result<WasmBitcoinUnitData, JsValue> {
+        self.0
+            .set_preferred_unit(unit.into())
+            .await
+            .map(|unit| WasmBitcoinUnitData(unit.into()))
+            .map_err(|e| e.to_js_error())
+    }
+
+    #[wasm_bindgen(js_name = "setPreferredFiat")]
+    pub async fn set_preferred_fiat(&self, fiat: WasmFiatCurrencySymbol) -> Result<String, JsValue> {
+        self.0
+            .set_preferred_fiat(fiat.into())
+            .await
+            .map_err(|e| e.to_js_error())
+    }
+
     #[wasm_bindgen(js_name = "getUserWalletEligibility")]
     pub async fn get_user_wallet_eligibility(&self) -> Result<u8, JsValue> {
         self.0
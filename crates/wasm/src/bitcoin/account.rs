@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use andromeda_bitcoin::account::Account;
+use andromeda_bitcoin::{account::Account, blockchain_client::SyncScope, ScriptBuf};
 use wasm_bindgen::prelude::*;
 
 use super::{
@@ -12,9 +12,9 @@ use super::{
         address_info::WasmAddressInfo,
         balance::{WasmBalance, WasmBalanceWrapper},
         derivation_path::WasmDerivationPath,
-        pagination::{WasmPagination, WasmSortOrder},
-        transaction::{WasmTransactionDetailsArray, WasmTransactionDetailsData},
-        utxo::{WasmUtxo, WasmUtxoArray},
+        pagination::{WasmPagination, WasmSortOrder, WasmUtxoSort},
+        transaction::{WasmScript, WasmTransactionDetailsArray, WasmTransactionDetailsData, WasmTransactionsCursor},
+        utxo::{WasmUtxo, WasmUtxoArray, WasmUtxoPage},
     },
     wallet::WasmWallet,
 };
@@ -42,17 +42,32 @@ impl Into<WasmAccount> for Arc<Account<WalletWebConnector, WalletWebPersister>>
 
 #[wasm_bindgen]
 impl WasmAccount {
+    /// Creates an account for the given script type and derivation path.
+    ///
+    /// Derivation path's purpose must match the script type unless
+    /// `allow_mismatch` is set, e.g. when reloading persisted accounts created
+    /// by wallets not following BIP44.
     #[wasm_bindgen(constructor)]
     pub fn new(
         wallet: &WasmWallet,
         script_type: WasmScriptType,
         derivation_path: WasmDerivationPath,
+        gap_limit: Option<usize>,
+        allow_mismatch: Option<bool>,
     ) -> Result<WasmAccount, js_sys::Error> {
         let factory = WalletWebPersisterFactory;
 
         let (mprv, network) = wallet.get_inner().mprv();
-        let account = Account::new(mprv, network, script_type.into(), (&derivation_path).into(), factory)
-            .map_err(|e| e.to_js_error())?;
+        let (script_type, derivation_path) = (script_type.into(), (&derivation_path).into());
+        let mut account = if allow_mismatch.unwrap_or(false) {
+            Account::new_allowing_mismatch(mprv, network, script_type, derivation_path, factory)
+        } else {
+            Account::new(mprv, network, script_type, derivation_path, factory)
+        }
+        .map_err(|e| e.to_js_error())?;
+        if let Some(gap_limit) = gap_limit {
+            account = account.with_gap_limit(gap_limit);
+        }
 
         Ok(Arc::new(account).into())
     }
@@ -82,6 +97,13 @@ impl WasmAccount {
         Ok(address)
     }
 
+    /// Returns an address of the given keychain, at `index` if provided or
+    /// the next unused one otherwise, without marking it as used
+    #[wasm_bindgen(js_name = getAddressForKeychain)]
+    pub async fn get_address_for_keychain(&self, keychain: WasmKeychainKind, index: Option<u32>) -> WasmAddressInfo {
+        self.inner.get_address_for_keychain(keychain.into(), index).await.into()
+    }
+
     #[wasm_bindgen(js_name = peekReceiveAddress)]
     pub async fn peek_receive_address(&self, index: u32) -> Result<WasmAddressInfo, js_sys::Error> {
         let account_inner = self.get_inner();
@@ -102,6 +124,40 @@ impl WasmAccount {
         Ok(owns)
     }
 
+    #[wasm_bindgen(js_name = ownsScript)]
+    pub async fn owns_script(&self, script: &WasmScript) -> Result<bool, js_sys::Error> {
+        let script: ScriptBuf = script.into();
+        let owns = self.inner.owns_script(&script).await;
+
+        Ok(owns)
+    }
+
+    /// Signs a message with the key deriving the provided account's address,
+    /// returning a base64-encoded signature
+    #[wasm_bindgen(js_name = signBitcoinAddress)]
+    pub async fn sign_bitcoin_address(&self, address: String, message: String) -> Result<String, js_sys::Error> {
+        let signature = self
+            .inner
+            .sign_bitcoin_address(&address, &message)
+            .await
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(signature)
+    }
+
+    /// Signs a message with the key deriving the provided account's address,
+    /// following BIP-322 simple signature format
+    #[wasm_bindgen(js_name = signMessage)]
+    pub async fn sign_message(&self, message: String, address: String) -> Result<String, js_sys::Error> {
+        let signature = self
+            .inner
+            .sign_message(&message, &address)
+            .await
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(signature)
+    }
+
     #[wasm_bindgen(js_name = getBalance)]
     pub async fn get_balance(&self) -> Result<WasmBalanceWrapper, js_sys::Error> {
         let balance: WasmBalance = self.inner.get_balance().await.into();
@@ -109,6 +165,40 @@ impl WasmAccount {
         Ok(WasmBalanceWrapper { data: balance })
     }
 
+    /// Returns the account's balance, where coins with less than
+    /// `min_confirmations` confirmations are considered pending
+    #[wasm_bindgen(js_name = getBalanceWithConfirmations)]
+    pub async fn get_balance_with_confirmations(
+        &self,
+        min_confirmations: u32,
+        tip_height: Option<u32>,
+    ) -> Result<WasmBalanceWrapper, js_sys::Error> {
+        let balance: WasmBalance = self
+            .inner
+            .get_balance_with_confirmations(min_confirmations, tip_height)
+            .await
+            .into();
+
+        Ok(WasmBalanceWrapper { data: balance })
+    }
+
+    #[wasm_bindgen(js_name = getGapLimit)]
+    pub fn get_gap_limit(&self) -> usize {
+        self.inner.get_gap_limit()
+    }
+
+    #[wasm_bindgen(js_name = setGapLimit)]
+    pub fn set_gap_limit(&self, gap_limit: usize) {
+        self.inner.set_gap_limit(gap_limit)
+    }
+
+    /// Restricts the keychains scanned by full syncs, e.g. to skip change
+    /// scanning for receive-only accounts
+    #[wasm_bindgen(js_name = setSyncScope)]
+    pub async fn set_sync_scope(&self, external: bool, internal: bool) {
+        self.inner.set_sync_scope(SyncScope { external, internal }).await
+    }
+
     #[wasm_bindgen(js_name = getDerivationPath)]
     pub fn get_derivation_path(&self) -> Result<String, js_sys::Error> {
         let derivation_path = self.inner.get_derivation_path().to_string();
@@ -116,12 +206,45 @@ impl WasmAccount {
         Ok(derivation_path)
     }
 
+    #[wasm_bindgen(js_name = getAccountXpub)]
+    pub fn get_account_xpub(&self) -> String {
+        self.inner.export_account_xpub()
+    }
+
+    #[wasm_bindgen(js_name = getPublicDescriptor)]
+    pub fn get_public_descriptor(&self, keychain: WasmKeychainKind) -> Result<String, js_sys::Error> {
+        let descriptor = self
+            .inner
+            .public_descriptor(keychain.into())
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(descriptor)
+    }
+
     #[wasm_bindgen(js_name = getUtxos)]
-    pub async fn get_utxos(&self) -> Result<WasmUtxoArray, js_sys::Error> {
+    pub async fn get_utxos(
+        &self,
+        pagination: Option<WasmPagination>,
+        sort: Option<WasmUtxoSort>,
+    ) -> Result<WasmUtxoPage, js_sys::Error> {
+        let (utxos, total) = self
+            .inner
+            .get_utxos(pagination.map(|p| p.into()).unwrap_or_default(), sort.map(|s| s.into()))
+            .await;
+
+        Ok(WasmUtxoPage {
+            utxos: utxos.into_iter().map(|utxo| utxo.into()).collect::<Vec<WasmUtxo>>(),
+            total,
+        })
+    }
+
+    #[wasm_bindgen(js_name = getDustUtxos)]
+    pub async fn get_dust_utxos(&self, fee_rate: u64) -> Result<WasmUtxoArray, js_sys::Error> {
         let utxos = self
             .inner
-            .get_utxos()
+            .dust_utxos(fee_rate)
             .await
+            .map_err(|e| e.to_js_error())?
             .into_iter()
             .map(|utxo| utxo.into())
             .collect::<Vec<WasmUtxo>>();
@@ -184,6 +307,18 @@ impl WasmAccount {
             .map_err(|e| e.to_js_error())?)
     }
 
+    /// Returns the index of the next unused address of the keychain
+    #[wasm_bindgen(js_name = getNextUnusedIndex)]
+    pub async fn get_next_unused_index(&self, keychain: WasmKeychainKind) -> u32 {
+        self.inner.next_unused_index(keychain.into()).await
+    }
+
+    /// Returns the number of revealed addresses of the keychain that are used
+    #[wasm_bindgen(js_name = getAddressesUsedCount)]
+    pub async fn get_addresses_used_count(&self, keychain: WasmKeychainKind) -> u32 {
+        self.inner.addresses_used_count(keychain.into()).await
+    }
+
     #[wasm_bindgen(js_name = getTransactions)]
     pub async fn get_transactions(
         &self,
@@ -202,9 +337,39 @@ impl WasmAccount {
         Ok(WasmTransactionDetailsArray(transactions))
     }
 
+    /// Returns a cursor to pull the account's transactions page by page,
+    /// without converting the whole history upfront
+    #[wasm_bindgen(js_name = getTransactionsCursor)]
+    pub async fn get_transactions_cursor(&self, sort: Option<WasmSortOrder>) -> WasmTransactionsCursor {
+        self.inner.transactions_iter(sort.map(|s| s.into())).await.into()
+    }
+
     #[wasm_bindgen(js_name = getTransaction)]
     pub async fn get_transaction(&self, txid: String) -> Result<WasmTransactionDetailsData, js_sys::Error> {
-        let transaction = self.inner.get_transaction(txid).await.map_err(|e| e.to_js_error())?;
+        let transaction = self
+            .inner
+            .get_transaction(txid, None)
+            .await
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(WasmTransactionDetailsData {
+            Data: transaction.into(),
+        })
+    }
+
+    /// Same as `getTransaction`, but fetches the transaction from the client
+    /// when it isn't synced yet
+    #[wasm_bindgen(js_name = getOrFetchTransaction)]
+    pub async fn get_or_fetch_transaction(
+        &self,
+        txid: String,
+        client: &WasmBlockchainClient,
+    ) -> Result<WasmTransactionDetailsData, js_sys::Error> {
+        let transaction = self
+            .inner
+            .get_transaction(txid, Some(client.into()))
+            .await
+            .map_err(|e| e.to_js_error())?;
 
         Ok(WasmTransactionDetailsData {
             Data: transaction.into(),
@@ -1,16 +1,50 @@
 use std::{collections::HashMap, sync::Arc};
 
-use super::{account::WasmAccount, psbt::WasmPsbt};
-use crate::{api::WasmProtonWalletApiClient, common::error::ErrorExt};
+use super::{account::WasmAccount, psbt::WasmPsbt, types::transaction::WasmTransactionDetailsData};
+use crate::{
+    api::WasmProtonWalletApiClient,
+    common::{error::ErrorExt, types::WasmNetwork},
+};
 use andromeda_api::transaction::{BroadcastMessage, ExchangeRateOrTransactionTime, RecommendedFees};
-use andromeda_bitcoin::blockchain_client::{self, BlockchainClient, MinimumFees};
+use andromeda_bitcoin::{
+    account::DEFAULT_GAP_LIMIT,
+    blockchain_client::{AcceptCheck, BlockchainClient, FeeBuckets, MinimumFees, SyncProgress, SyncReport},
+    Txid,
+};
+use futures::{
+    channel::mpsc,
+    future::{self, AbortHandle, AbortRegistration},
+    StreamExt,
+};
 use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
+use web_sys::AbortSignal;
 
 #[wasm_bindgen(js_name = getDefaultStopGap)]
 pub fn get_default_stop_gap() -> usize {
-    blockchain_client::DEFAULT_STOP_GAP
+    DEFAULT_GAP_LIMIT
+}
+
+/// Bridges a JS `AbortSignal` to the registration of an `AbortHandle`
+/// aborted once the signal fires.
+///
+/// The listener is only freed once called, i.e. it is leaked if the signal
+/// never fires.
+fn abort_registration(signal: Option<AbortSignal>) -> Result<Option<AbortRegistration>, JsValue> {
+    let Some(signal) = signal else {
+        return Ok(None);
+    };
+
+    let (abort_handle, abort_registration) = AbortHandle::new_pair();
+    if signal.aborted() {
+        abort_handle.abort();
+    } else {
+        let on_abort = Closure::once_into_js(move || abort_handle.abort());
+        signal.add_event_listener_with_callback("abort", on_abort.unchecked_ref())?;
+    }
+
+    Ok(Some(abort_registration))
 }
 
 #[wasm_bindgen(getter_with_clone)]
@@ -40,6 +74,23 @@ impl From<MinimumFees> for WasmMinimumFees {
     }
 }
 
+#[wasm_bindgen]
+pub struct WasmFeeBuckets {
+    pub fast: f32,
+    pub medium: f32,
+    pub slow: f32,
+}
+
+impl From<FeeBuckets> for WasmFeeBuckets {
+    fn from(value: FeeBuckets) -> Self {
+        WasmFeeBuckets {
+            fast: value.fast,
+            medium: value.medium,
+            slow: value.slow,
+        }
+    }
+}
+
 #[wasm_bindgen]
 #[allow(non_snake_case)]
 pub struct WasmRecommendedFees {
@@ -62,6 +113,44 @@ impl From<RecommendedFees> for WasmRecommendedFees {
     }
 }
 
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+pub struct WasmSyncReport {
+    pub new_txs: Vec<String>,
+    pub confirmed_txs: Vec<String>,
+    pub reorged_txs: Vec<String>,
+    pub tip_height: u32,
+}
+
+impl From<SyncReport> for WasmSyncReport {
+    fn from(value: SyncReport) -> Self {
+        let to_strings = |txids: Vec<Txid>| txids.iter().map(|txid| txid.to_string()).collect();
+
+        WasmSyncReport {
+            new_txs: to_strings(value.new_txs),
+            confirmed_txs: to_strings(value.confirmed_txs),
+            reorged_txs: to_strings(value.reorged_txs),
+            tip_height: value.tip_height,
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+pub struct WasmAcceptCheck {
+    pub allowed: bool,
+    pub reject_reason: Option<String>,
+}
+
+impl From<AcceptCheck> for WasmAcceptCheck {
+    fn from(value: AcceptCheck) -> Self {
+        WasmAcceptCheck {
+            allowed: value.allowed,
+            reject_reason: value.reject_reason,
+        }
+    }
+}
+
 impl Into<Arc<BlockchainClient>> for &WasmBlockchainClient {
     fn into(self) -> Arc<BlockchainClient> {
         self.inner.clone()
@@ -130,17 +219,33 @@ pub struct WasmEmailIntegrationData {
 
 #[wasm_bindgen]
 impl WasmBlockchainClient {
-    /// Generates a Mnemonic with a random entropy based on the given word
-    /// count.
+    /// Creates a blockchain client. `parallel_requests` caps the number of
+    /// esplora requests sent in parallel during syncs.
     #[wasm_bindgen(constructor)]
-    pub fn new(proton_api_client: &WasmProtonWalletApiClient) -> Result<WasmBlockchainClient, JsValue> {
-        let inner = BlockchainClient::new(proton_api_client.into());
+    pub fn new(
+        proton_api_client: &WasmProtonWalletApiClient,
+        parallel_requests: Option<usize>,
+    ) -> Result<WasmBlockchainClient, JsValue> {
+        let mut inner = BlockchainClient::new(proton_api_client.into());
+        if let Some(parallel_requests) = parallel_requests {
+            inner = inner.with_parallel_requests(parallel_requests);
+        }
+
         Ok(WasmBlockchainClient { inner: Arc::new(inner) })
     }
 
+    /// Returns fee estimations, served from cache when recently fetched
+    /// unless `force_refresh` is set
     #[wasm_bindgen(js_name = getFeesEstimation)]
-    pub async fn get_fees_estimation(&mut self) -> Result<FeeRateByBlockEstimation, JsValue> {
-        let fees_estimation = self.inner.get_fees_estimation().await.map_err(|e| e.to_js_error())?;
+    pub async fn get_fees_estimation(
+        &mut self,
+        force_refresh: Option<bool>,
+    ) -> Result<FeeRateByBlockEstimation, JsValue> {
+        let fees_estimation = self
+            .inner
+            .get_fees_estimation(force_refresh.unwrap_or(false))
+            .await
+            .map_err(|e| e.to_js_error())?;
 
         Ok(serde_wasm_bindgen::to_value(&fees_estimation).unwrap().into())
     }
@@ -152,6 +257,13 @@ impl WasmBlockchainClient {
         Ok(WasmMinimumFees::from(minimum_fees))
     }
 
+    #[wasm_bindgen(js_name = getFeeBuckets)]
+    pub async fn get_fee_buckets(&self) -> Result<WasmFeeBuckets, JsValue> {
+        let fee_buckets = self.inner.get_fee_buckets().await.map_err(|e| e.to_js_error())?;
+
+        Ok(WasmFeeBuckets::from(fee_buckets))
+    }
+
     #[wasm_bindgen(js_name = getRecommendedFees)]
     pub async fn get_recommended_fees(&self) -> Result<WasmRecommendedFees, JsValue> {
         let recommended_fees = self.inner.get_recommended_fees().await.map_err(|e| e.to_js_error())?;
@@ -159,35 +271,92 @@ impl WasmBlockchainClient {
         Ok(WasmRecommendedFees::from(recommended_fees))
     }
 
+    /// Fully syncs the account. The sync can be cancelled with the optional
+    /// `AbortSignal`, nothing being applied in that case.
     #[wasm_bindgen(js_name = fullSync)]
-    pub async fn full_sync(&self, account: &WasmAccount, stop_gap: Option<usize>) -> Result<(), JsValue> {
+    pub async fn full_sync(
+        &self,
+        account: &WasmAccount,
+        stop_gap: Option<usize>,
+        signal: Option<AbortSignal>,
+    ) -> Result<WasmSyncReport, JsValue> {
         let account_inner = account.get_inner();
 
         let update = self
             .inner
-            .full_sync(&account_inner, stop_gap)
+            .full_sync(&account_inner, stop_gap, abort_registration(signal)?)
             .await
             .map_err(|e| e.to_js_error())?;
 
-        account_inner.apply_update(update).await.map_err(|e| e.to_js_error())?;
+        let report = account_inner.apply_update(update).await.map_err(|e| e.to_js_error())?;
+
+        Ok(report.into())
+    }
 
-        Ok(())
+    /// Same as `fullSync`, calling `onProgress(scannedScripts, totalEstimate)`
+    /// as the account's scripts are queried
+    #[wasm_bindgen(js_name = fullSyncWithProgress)]
+    pub async fn full_sync_with_progress(
+        &self,
+        account: &WasmAccount,
+        stop_gap: Option<usize>,
+        on_progress: js_sys::Function,
+        signal: Option<AbortSignal>,
+    ) -> Result<WasmSyncReport, JsValue> {
+        let account_inner = account.get_inner();
+        let abort_registration = abort_registration(signal)?;
+
+        // JS functions can't be sent to the sync, progress is forwarded through a
+        // channel instead. It is closed once the sync drops the sender.
+        let (sender, receiver) = mpsc::unbounded::<SyncProgress>();
+
+        let sync = self.inner.full_sync_with_progress(
+            &account_inner,
+            stop_gap,
+            move |progress| {
+                let _ = sender.unbounded_send(progress);
+            },
+            abort_registration,
+        );
+
+        let notify = receiver.for_each(|progress| {
+            let _ = on_progress.call2(
+                &JsValue::NULL,
+                &JsValue::from(progress.scanned_scripts),
+                &JsValue::from(progress.total_estimate),
+            );
+            future::ready(())
+        });
+
+        let (update, _) = future::join(sync, notify).await;
+        let update = update.map_err(|e| e.to_js_error())?;
+
+        let report = account_inner.apply_update(update).await.map_err(|e| e.to_js_error())?;
+
+        Ok(report.into())
     }
 
+    /// Partially syncs the account. Like `fullSync`, it can be cancelled with
+    /// the optional `AbortSignal`.
     #[wasm_bindgen(js_name = partialSync)]
-    pub async fn partial_sync(&self, account: &WasmAccount) -> Result<(), JsValue> {
+    pub async fn partial_sync(
+        &self,
+        account: &WasmAccount,
+        signal: Option<AbortSignal>,
+    ) -> Result<WasmSyncReport, JsValue> {
         let account_inner = account.get_inner();
+        let abort_registration = abort_registration(signal)?;
 
         let wallet_lock = account_inner.get_wallet().await;
         let update = self
             .inner
-            .partial_sync(wallet_lock)
+            .partial_sync(wallet_lock, abort_registration)
             .await
             .map_err(|e| e.to_js_error())?;
 
-        account_inner.apply_update(update).await.map_err(|e| e.to_js_error())?;
+        let report = account_inner.apply_update(update).await.map_err(|e| e.to_js_error())?;
 
-        Ok(())
+        Ok(report.into())
     }
 
     #[wasm_bindgen(js_name = shouldSync)]
@@ -199,6 +368,34 @@ impl WasmBlockchainClient {
         self.inner.should_sync(wallet_lock).await.map_err(|e| e.to_js_error())
     }
 
+    #[wasm_bindgen(js_name = getTransaction)]
+    pub async fn get_transaction(
+        &self,
+        network: WasmNetwork,
+        txid: String,
+    ) -> Result<WasmTransactionDetailsData, JsValue> {
+        let transaction = self
+            .inner
+            .get_transaction(txid, network.into())
+            .await
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(WasmTransactionDetailsData {
+            Data: transaction.into(),
+        })
+    }
+
+    /// Checks whether the signed PSBT's transaction would be accepted in the
+    /// mempool, without broadcasting it
+    #[wasm_bindgen(js_name = testAcceptPsbt)]
+    pub async fn test_accept_psbt(&self, psbt: &WasmPsbt) -> Result<WasmAcceptCheck, JsValue> {
+        let tx = psbt.get_inner().extract_tx().map_err(|e| e.to_js_error())?;
+
+        let accept_check = self.inner.test_accept(&tx).await.map_err(|e| e.to_js_error())?;
+
+        Ok(accept_check.into())
+    }
+
     #[wasm_bindgen(js_name = broadcastPsbt)]
     pub async fn broadcast_psbt(
         &self,
@@ -0,0 +1,12 @@
+use wasm_bindgen::prelude::*;
+
+use crate::common::error::ErrorExt;
+
+/// Verifies a BIP-322 simple signature of a message against an address
+#[wasm_bindgen(js_name = verifyMessage)]
+pub fn verify_message(message: String, signature: String, address: String) -> Result<bool, js_sys::Error> {
+    let verified =
+        andromeda_bitcoin::message::verify_message(&message, &signature, &address).map_err(|e| e.to_js_error())?;
+
+    Ok(verified)
+}
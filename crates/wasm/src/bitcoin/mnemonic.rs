@@ -34,6 +34,12 @@ pub struct WasmMnemonic {
     inner: Mnemonic,
 }
 
+impl From<Mnemonic> for WasmMnemonic {
+    fn from(value: Mnemonic) -> Self {
+        WasmMnemonic { inner: value }
+    }
+}
+
 #[wasm_bindgen]
 impl WasmMnemonic {
     /// Generates a Mnemonic with a random entropy based on the given word
@@ -52,6 +58,14 @@ impl WasmMnemonic {
             .map_err(|e| e.to_js_error())
     }
 
+    /// Creates a Mnemonic from the given entropy. Only 16, 20, 24, 28 or 32
+    /// bytes are accepted.
+    #[wasm_bindgen(js_name = fromEntropy)]
+    pub fn from_entropy(entropy: &[u8]) -> Result<WasmMnemonic, JsValue> {
+        let mnemonic = Mnemonic::new_with(entropy).map_err(|e| e.to_js_error())?;
+        Ok(WasmMnemonic { inner: mnemonic })
+    }
+
     /// Returns the Mnemonic as a string.
     #[wasm_bindgen(js_name = asString)]
     pub fn as_string(&self) -> String {
@@ -1,5 +1,6 @@
 pub mod account;
 pub mod blockchain_client;
+pub mod message;
 pub mod mnemonic;
 pub mod payment_link;
 pub mod psbt;
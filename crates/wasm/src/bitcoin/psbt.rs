@@ -1,5 +1,10 @@
-use andromeda_bitcoin::{error::Error as BitcoinError, psbt::Psbt, Address, ConsensusParams, SignOptions};
+use andromeda_bitcoin::{
+    psbt::{Psbt, PsbtInput, PsbtOutput},
+    Address, ConsensusParams, SignOptions,
+};
 use andromeda_common::Network;
+use serde::{Deserialize, Serialize};
+use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
 use super::account::WasmAccount;
@@ -9,6 +14,100 @@ use crate::common::{error::ErrorExt, types::WasmNetwork};
 #[derive(Clone)]
 pub struct WasmPsbtRecipient(pub String, pub u64);
 
+/// Subset of BDK's sign options exposed to JS. Unset fields fallback to BDK's
+/// defaults.
+#[derive(Tsify, Serialize, Deserialize, Clone, Default)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct WasmSignOptions {
+    /// Whether the signer should trust the `witness_utxo` of inputs that lack a
+    /// `non_witness_utxo`, e.g. for watch-only witness UTXOs
+    pub trust_witness_utxo: Option<bool>,
+    /// Whether the signer should grind for low-R ECDSA signatures
+    pub allow_grinding: Option<bool>,
+    /// Whether the signer should try to finalize the PSBT after signing
+    pub try_finalize: Option<bool>,
+    /// Whether the signer should sign taproot inputs with the internal key
+    pub sign_with_tap_internal_key: Option<bool>,
+}
+
+impl Into<SignOptions> for WasmSignOptions {
+    fn into(self) -> SignOptions {
+        let default = SignOptions::default();
+
+        SignOptions {
+            trust_witness_utxo: self.trust_witness_utxo.unwrap_or(default.trust_witness_utxo),
+            allow_grinding: self.allow_grinding.unwrap_or(default.allow_grinding),
+            try_finalize: self.try_finalize.unwrap_or(default.try_finalize),
+            sign_with_tap_internal_key: self
+                .sign_with_tap_internal_key
+                .unwrap_or(default.sign_with_tap_internal_key),
+            ..default
+        }
+    }
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct WasmPsbtInput {
+    pub outpoint: String,
+    pub value: Option<u64>,
+    pub address: Option<String>,
+    pub is_mine: bool,
+    pub is_witness: bool,
+}
+
+impl From<PsbtInput> for WasmPsbtInput {
+    fn from(value: PsbtInput) -> Self {
+        Self {
+            outpoint: value.outpoint.to_string(),
+            value: value.value,
+            address: value.address.map(|a| a.to_string()),
+            is_mine: value.is_mine,
+            is_witness: value.is_witness,
+        }
+    }
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct WasmPsbtOutput {
+    pub address: Option<String>,
+    pub value: u64,
+    pub is_change: bool,
+}
+
+impl From<PsbtOutput> for WasmPsbtOutput {
+    fn from(value: PsbtOutput) -> Self {
+        Self {
+            address: value.address.map(|a| a.to_string()),
+            value: value.value,
+            is_change: value.is_change,
+        }
+    }
+}
+
+// We need this wrapper because, tsify doesn't support intoJs in async fns
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+#[allow(non_snake_case)]
+pub struct WasmPsbtInputData {
+    pub Data: WasmPsbtInput,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct WasmPsbtInputArray(pub Vec<WasmPsbtInputData>);
+
+// We need this wrapper because, tsify doesn't support intoJs in async fns
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+#[allow(non_snake_case)]
+pub struct WasmPsbtOutputData {
+    pub Data: WasmPsbtOutput,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct WasmPsbtOutputArray(pub Vec<WasmPsbtOutputData>);
+
 #[wasm_bindgen(getter_with_clone)]
 #[derive(Clone)]
 pub struct WasmPsbt {
@@ -52,20 +151,54 @@ impl Into<Psbt> for &WasmPsbt {
 
 #[wasm_bindgen]
 impl WasmPsbt {
-    pub async fn sign(&mut self, wasm_account: &WasmAccount, network: WasmNetwork) -> Result<WasmPsbt, JsValue> {
+    pub async fn sign(
+        &mut self,
+        wasm_account: &WasmAccount,
+        network: WasmNetwork,
+        sign_options: Option<WasmSignOptions>,
+    ) -> Result<WasmPsbt, JsValue> {
         let inner = wasm_account.get_inner();
 
         let mut mutable_psbt = self.inner.inner().clone();
 
         inner
-            .get_wallet()
+            .sign(&mut mutable_psbt, sign_options.map(|o| o.into()))
             .await
-            .sign(&mut mutable_psbt, SignOptions::default())
-            .map_err(|e| BitcoinError::from(e).to_js_error())?;
+            .map_err(|e| e.to_js_error())?;
 
         WasmPsbt::from_psbt(&mutable_psbt.into(), network.into())
     }
 
+    /// Returns the PSBT inputs, flagged against the given account's wallet
+    pub async fn inputs(&self, wasm_account: &WasmAccount) -> WasmPsbtInputArray {
+        let account = wasm_account.get_inner();
+        let wallet_lock = account.get_wallet().await;
+
+        let inputs = self
+            .inner
+            .inputs(&wallet_lock)
+            .into_iter()
+            .map(|input| WasmPsbtInputData { Data: input.into() })
+            .collect::<Vec<_>>();
+
+        WasmPsbtInputArray(inputs)
+    }
+
+    /// Returns the PSBT outputs, flagging the given account's change outputs
+    pub async fn outputs(&self, wasm_account: &WasmAccount) -> WasmPsbtOutputArray {
+        let account = wasm_account.get_inner();
+        let wallet_lock = account.get_wallet().await;
+
+        let outputs = self
+            .inner
+            .outputs(&wallet_lock)
+            .into_iter()
+            .map(|output| WasmPsbtOutputData { Data: output.into() })
+            .collect::<Vec<_>>();
+
+        WasmPsbtOutputArray(outputs)
+    }
+
     #[wasm_bindgen(js_name = computeTxVbytes)]
     pub fn compute_tx_vbytes(&self) -> Result<u64, JsValue> {
         self.inner.compute_tx_vbytes().map_err(|e| e.to_js_error())
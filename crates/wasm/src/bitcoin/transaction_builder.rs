@@ -1,7 +1,9 @@
 use andromeda_bitcoin::{
-    transaction_builder::{CoinSelection, TmpRecipient, TxBuilder},
+    transaction_builder::{estimate_tx_vsize, CoinSelection, TmpRecipient, TxBuilder},
+    utils::parse_sat_amount,
     ChangeSpendPolicy, OutPoint,
 };
+use andromeda_common::ScriptType;
 use wasm_bindgen::prelude::*;
 
 use super::{
@@ -10,7 +12,10 @@ use super::{
     storage::{WalletWebConnector, WalletWebPersister},
     types::{locktime::WasmLockTime, transaction::WasmOutPoint},
 };
-use crate::common::{error::ErrorExt, types::WasmNetwork};
+use crate::common::{
+    error::ErrorExt,
+    types::{WasmNetwork, WasmScriptType},
+};
 
 #[wasm_bindgen]
 #[derive(Clone)]
@@ -116,6 +121,22 @@ impl WasmTxBuilder {
         WasmTxBuilder { inner }
     }
 
+    /// Same as `addRecipient`, with the amount of sats as a string of digits
+    /// so that large values aren't rounded by JS numbers
+    #[wasm_bindgen(js_name = addRecipientStr)]
+    pub fn add_recipient_str(
+        &self,
+        address_str: Option<String>,
+        amount: Option<String>,
+    ) -> Result<WasmTxBuilder, js_sys::Error> {
+        let amount = amount
+            .map(|amount| parse_sat_amount(&amount))
+            .transpose()
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(self.add_recipient(address_str, amount))
+    }
+
     #[wasm_bindgen(js_name = removeRecipient)]
     pub fn remove_recipient(&self, index: usize) -> WasmTxBuilder {
         let inner = self.inner.remove_recipient(index);
@@ -129,11 +150,51 @@ impl WasmTxBuilder {
         address_str: Option<String>,
         amount: Option<u64>,
     ) -> Result<WasmTxBuilder, js_sys::Error> {
-        let inner = self.inner.update_recipient(index, (address_str, amount));
+        let inner = self
+            .inner
+            .update_recipient(index, (address_str, amount))
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(WasmTxBuilder { inner })
+    }
+
+    #[wasm_bindgen(js_name = updateRecipientByUuid)]
+    pub fn update_recipient_by_uuid(
+        &self,
+        uuid: String,
+        address_str: Option<String>,
+        amount: Option<u64>,
+    ) -> Result<WasmTxBuilder, js_sys::Error> {
+        let inner = self
+            .inner
+            .update_recipient_by_uuid(&uuid, (address_str, amount))
+            .map_err(|e| e.to_js_error())?;
 
         Ok(WasmTxBuilder { inner })
     }
 
+    /// Updates a recipient's amount, provided as a string of digits so that
+    /// large values aren't rounded by JS numbers
+    #[wasm_bindgen(js_name = updateRecipientAmountStr)]
+    pub fn update_recipient_amount_str(&self, index: usize, amount: String) -> Result<WasmTxBuilder, js_sys::Error> {
+        let amount = parse_sat_amount(&amount).map_err(|e| e.to_js_error())?;
+
+        self.update_recipient(index, None, Some(amount))
+    }
+
+    /// Same as `updateRecipientAmountStr`, identifying the recipient by its
+    /// uuid
+    #[wasm_bindgen(js_name = updateRecipientAmountByUuidStr)]
+    pub fn update_recipient_amount_by_uuid_str(
+        &self,
+        uuid: String,
+        amount: String,
+    ) -> Result<WasmTxBuilder, js_sys::Error> {
+        let amount = parse_sat_amount(&amount).map_err(|e| e.to_js_error())?;
+
+        self.update_recipient_by_uuid(uuid, None, Some(amount))
+    }
+
     #[wasm_bindgen(js_name = updateRecipientAmountToMax)]
     pub async fn update_recipient_amount_to_max(&self, index: usize) -> Result<WasmTxBuilder, js_sys::Error> {
         let inner = self.inner.update_recipient_amount_to_max(index).await;
@@ -247,14 +308,35 @@ impl WasmTxBuilder {
         self.inner.change_policy.into()
     }
 
+    #[wasm_bindgen(js_name = setChangeAddress)]
+    pub fn set_change_address(&self, address_str: Option<String>) -> Result<WasmTxBuilder, js_sys::Error> {
+        let inner = self
+            .inner
+            .set_change_address(address_str)
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(WasmTxBuilder { inner })
+    }
+
+    #[wasm_bindgen(js_name = getChangeAddress)]
+    pub fn get_change_address(&self) -> Option<String> {
+        self.inner.change_address.clone()
+    }
+
     /**
      * Fees
      */
 
     #[wasm_bindgen(js_name = setFeeRate)]
-    pub fn set_fee_rate(&self, sat_per_vb: u64) -> WasmTxBuilder {
-        let inner = self.inner.set_fee_rate(sat_per_vb);
-        WasmTxBuilder { inner }
+    pub fn set_fee_rate(&self, sat_per_vb: f32) -> Result<WasmTxBuilder, js_sys::Error> {
+        let inner = self.inner.set_fee_rate(sat_per_vb).map_err(|e| e.to_js_error())?;
+
+        Ok(WasmTxBuilder { inner })
+    }
+
+    #[wasm_bindgen(js_name = hasHighFeeRate)]
+    pub fn has_high_fee_rate(&self) -> bool {
+        self.inner.has_high_fee_rate()
     }
 
     #[wasm_bindgen(js_name = getFeeRate)]
@@ -313,3 +395,40 @@ impl WasmTxBuilder {
         WasmPsbt::from_psbt(&psbt, network.into())
     }
 }
+
+/// Estimates the virtual size of a transaction spending inputs of the
+/// provided script types, without building a PSBT. Outputs, including
+/// change, are assumed to be of the same script type as the first input.
+#[wasm_bindgen(js_name = estimateTxVsize)]
+pub fn estimate_tx_vsize_wasm(inputs: Vec<WasmScriptType>, outputs: u32, has_change: bool) -> u32 {
+    let inputs = inputs.into_iter().map(Into::into).collect::<Vec<ScriptType>>();
+
+    estimate_tx_vsize(&inputs, outputs as usize, has_change) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::WasmTxBuilder;
+
+    #[wasm_bindgen_test]
+    fn should_preserve_large_string_amounts() {
+        // 2^53 + 1, which JS numbers can't represent
+        let tx_builder = WasmTxBuilder::new()
+            .add_recipient(None, None)
+            .update_recipient_amount_str(0, "9007199254740993".to_string())
+            .unwrap();
+        assert_eq!(tx_builder.inner.recipients[0].2.to_sat(), 9_007_199_254_740_993);
+
+        let tx_builder = tx_builder
+            .add_recipient_str(None, Some("9007199254740993".to_string()))
+            .unwrap();
+        assert_eq!(tx_builder.inner.recipients[1].2.to_sat(), 9_007_199_254_740_993);
+
+        assert!(tx_builder.update_recipient_amount_str(0, "9e15".to_string()).is_err());
+        assert!(tx_builder
+            .update_recipient_amount_str(0, "18446744073709551616".to_string())
+            .is_err());
+    }
+}
@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
+use super::derivation_path::WasmDerivationPath;
+
 #[derive(Tsify, Serialize, Deserialize, Clone)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
 #[allow(non_snake_case)]
@@ -33,3 +35,13 @@ impl Into<WasmBalance> for Balance {
 pub struct WasmBalanceWrapper {
     pub data: WasmBalance,
 }
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+pub struct WasmAccountBalance {
+    pub account_key: WasmDerivationPath,
+    pub data: WasmBalance,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct WasmAccountBalances(pub Vec<WasmAccountBalance>);
@@ -1,8 +1,9 @@
 use std::str::FromStr;
 
 use andromeda_bitcoin::{error::Error as BitcoinError, DerivationPath};
-use andromeda_common::FromParts;
+use andromeda_common::{DerivationPathParts, FromParts, ToParts};
 use serde::{Deserialize, Serialize};
+use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
 use crate::common::{
@@ -10,6 +11,26 @@ use crate::common::{
     types::{WasmNetwork, WasmScriptType},
 };
 
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct WasmDerivationPathParts {
+    pub purpose: u32,
+    pub coin_type: u32,
+    pub account: u32,
+    pub hardened: [bool; 3],
+}
+
+impl From<DerivationPathParts> for WasmDerivationPathParts {
+    fn from(value: DerivationPathParts) -> Self {
+        Self {
+            purpose: value.purpose,
+            coin_type: value.coin_type,
+            account: value.account,
+            hardened: value.hardened,
+        }
+    }
+}
+
 #[wasm_bindgen]
 #[derive(Clone, Deserialize, Serialize)]
 pub struct WasmDerivationPath {
@@ -43,6 +64,13 @@ impl WasmDerivationPath {
     pub fn to_str(&self) -> String {
         self.inner.to_string()
     }
+
+    /// Returns purpose, coin type and account levels of a standard BIP44
+    /// path, or undefined for non-standard ones
+    #[wasm_bindgen(js_name = toParts)]
+    pub fn to_parts(&self) -> Option<WasmDerivationPathParts> {
+        self.inner.to_parts().map(|parts| parts.into())
+    }
 }
 
 impl WasmDerivationPath {
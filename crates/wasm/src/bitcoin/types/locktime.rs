@@ -1,6 +1,8 @@
-use andromeda_bitcoin::{Height, LockTime, Time};
+use andromeda_bitcoin::{error::Error as BitcoinError, LockTime};
 use wasm_bindgen::prelude::*;
 
+use crate::common::error::ErrorExt;
+
 #[wasm_bindgen]
 #[derive(Clone)]
 pub struct WasmLockTime {
@@ -21,35 +23,71 @@ impl From<WasmLockTime> for LockTime {
 
 #[wasm_bindgen]
 impl WasmLockTime {
+    /// Builds a block height based locktime. Height must be lower than
+    /// 500_000_000 (BIP65)
     #[wasm_bindgen(js_name = fromHeight)]
-    pub fn from_height(height: u32) -> WasmLockTime {
-        WasmLockTime {
-            lock_time: LockTime::Blocks(Height::from_consensus(height).unwrap()),
-        }
+    pub fn from_height(height: u32) -> Result<WasmLockTime, js_sys::Error> {
+        let lock_time = LockTime::from_height(height).map_err(|e| BitcoinError::from(e).to_js_error())?;
+
+        Ok(WasmLockTime { lock_time })
+    }
+
+    /// Builds a unix timestamp based locktime. Timestamp must be greater or
+    /// equal to 500_000_000 (BIP65)
+    #[wasm_bindgen(js_name = fromTimestamp)]
+    pub fn from_timestamp(timestamp: u32) -> Result<WasmLockTime, js_sys::Error> {
+        let lock_time = LockTime::from_time(timestamp).map_err(|e| BitcoinError::from(e).to_js_error())?;
+
+        Ok(WasmLockTime { lock_time })
     }
 
     #[wasm_bindgen(js_name = fromSeconds)]
-    pub fn from_seconds(seconds: u32) -> WasmLockTime {
-        WasmLockTime {
-            lock_time: LockTime::Seconds(Time::from_consensus(seconds).unwrap()),
-        }
+    pub fn from_seconds(seconds: u32) -> Result<WasmLockTime, js_sys::Error> {
+        Self::from_timestamp(seconds)
     }
 
     #[wasm_bindgen(js_name = isBlockHeight)]
     pub fn is_block_height(&self) -> bool {
-        matches!(self.lock_time, LockTime::Blocks(_))
+        self.lock_time.is_block_height()
+    }
+
+    #[wasm_bindgen(js_name = isTimestamp)]
+    pub fn is_timestamp(&self) -> bool {
+        self.lock_time.is_block_time()
     }
 
     #[wasm_bindgen(js_name = isBlockTime)]
     pub fn is_block_time(&self) -> bool {
-        matches!(self.lock_time, LockTime::Seconds(_))
+        self.is_timestamp()
     }
 
     #[wasm_bindgen(js_name = toConsensusU32)]
     pub fn to_consensus_u32(&self) -> u32 {
-        match self.lock_time {
-            LockTime::Blocks(height) => height.to_consensus_u32(),
-            LockTime::Seconds(seconds) => seconds.to_consensus_u32(),
-        }
+        self.lock_time.to_consensus_u32()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::WasmLockTime;
+
+    #[wasm_bindgen_test]
+    fn should_build_block_height_locktime() {
+        let lock_time = WasmLockTime::from_height(800_000).unwrap();
+
+        assert!(lock_time.is_block_height());
+        assert!(!lock_time.is_timestamp());
+        assert_eq!(lock_time.to_consensus_u32(), 800_000);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_reject_height_as_timestamp() {
+        assert!(WasmLockTime::from_timestamp(800_000).is_err());
+        assert!(WasmLockTime::from_height(1_733_468_825).is_err());
+
+        let lock_time = WasmLockTime::from_timestamp(1_733_468_825).unwrap();
+        assert!(lock_time.is_timestamp());
     }
 }
@@ -1,4 +1,7 @@
-use andromeda_bitcoin::{transactions::Pagination, utils::SortOrder};
+use andromeda_bitcoin::{
+    transactions::Pagination,
+    utils::{SortOrder, UtxoSort},
+};
 use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
@@ -30,3 +33,20 @@ impl Into<SortOrder> for WasmSortOrder {
         }
     }
 }
+
+#[wasm_bindgen]
+pub enum WasmUtxoSort {
+    ValueAsc,
+    ValueDesc,
+    Oldest,
+}
+
+impl Into<UtxoSort> for WasmUtxoSort {
+    fn into(self) -> UtxoSort {
+        match self {
+            WasmUtxoSort::ValueAsc => UtxoSort::ValueAsc,
+            WasmUtxoSort::ValueDesc => UtxoSort::ValueDesc,
+            WasmUtxoSort::Oldest => UtxoSort::Oldest,
+        }
+    }
+}
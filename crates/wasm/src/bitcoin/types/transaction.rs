@@ -1,20 +1,27 @@
 use std::str::FromStr;
 
+use andromeda_api::exchange_rate::ApiExchangeRate;
 use andromeda_bitcoin::{
     error::Error as BitcoinError,
     psbt::Psbt,
-    transactions::{DetailledTxIn, DetailledTxOutput, TransactionDetails, TransactionTime},
-    Address, ConsensusParams, OutPoint, ScriptBuf, Sequence, Transaction,
+    transactions::{
+        DetailledTxIn, DetailledTxOutput, TransactionDetails, TransactionKind, TransactionTime, TransactionsCursor,
+    },
+    utils::convert_to_fiat,
+    Address, ConsensusParams, FromScriptError, OutPoint, ScriptBuf, Sequence, Transaction,
 };
 use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
 use super::{
-    super::{account::WasmAccount, psbt::WasmPsbt},
+    super::{account::WasmAccount, psbt::WasmPsbt, storage::WalletWebPersister},
     address::WasmAddress,
 };
-use crate::common::{error::ErrorExt, types::WasmNetwork};
+use crate::{
+    api::exchange_rate::WasmApiExchangeRate,
+    common::{error::ErrorExt, types::WasmNetwork},
+};
 
 #[wasm_bindgen(getter_with_clone)]
 #[derive(Clone, Serialize, Deserialize)]
@@ -61,13 +68,18 @@ impl WasmTransaction {
 
 #[wasm_bindgen]
 impl WasmScript {
+    /// Returns the address the script pays to, or `undefined` for scripts
+    /// without any, such as OP_RETURN or bare scripts. Malformed witness
+    /// programs are rejected with an error.
     #[wasm_bindgen(js_name = toAddress)]
-    pub fn to_address(&self, network: WasmNetwork) -> Result<WasmAddress, js_sys::Error> {
+    pub fn to_address(&self, network: WasmNetwork) -> Result<Option<WasmAddress>, js_sys::Error> {
         let script_buf: ScriptBuf = self.into();
-        let address = Address::from_script(script_buf.as_script(), ConsensusParams::new(network.into()))
-            .map_err(|e| BitcoinError::from(e).to_js_error())?;
 
-        Ok(address.into())
+        match Address::from_script(script_buf.as_script(), ConsensusParams::new(network.into())) {
+            Ok(address) => Ok(Some(address.into())),
+            Err(FromScriptError::UnrecognizedScript) => Ok(None),
+            Err(e) => Err(BitcoinError::from(e).to_js_error()),
+        }
     }
 }
 
@@ -108,6 +120,52 @@ impl Into<WasmSequence> for Sequence {
     }
 }
 
+impl From<WasmSequence> for Sequence {
+    fn from(value: WasmSequence) -> Self {
+        Sequence(value.0)
+    }
+}
+
+#[wasm_bindgen]
+impl WasmSequence {
+    /// Sequence signaling replaceability (BIP125), without any relative
+    /// timelock
+    pub fn rbf() -> WasmSequence {
+        Sequence::ENABLE_RBF_NO_LOCKTIME.into()
+    }
+
+    /// Final sequence: disables both RBF and nLockTime
+    #[wasm_bindgen(js_name = "final")]
+    pub fn final_sequence() -> WasmSequence {
+        Sequence::MAX.into()
+    }
+
+    /// Relative timelock of the given number of blocks (BIP68)
+    #[wasm_bindgen(js_name = fromRelativeBlocks)]
+    pub fn from_relative_blocks(blocks: u16) -> WasmSequence {
+        Sequence::from_height(blocks).into()
+    }
+
+    /// Relative timelock of at least the given number of seconds, rounded up
+    /// to the next 512 seconds interval (BIP68)
+    #[wasm_bindgen(js_name = fromRelativeSeconds)]
+    pub fn from_relative_seconds(seconds: u32) -> Result<WasmSequence, js_sys::Error> {
+        let sequence = Sequence::from_seconds_ceil(seconds).map_err(|e| BitcoinError::from(e).to_js_error())?;
+
+        Ok(sequence.into())
+    }
+
+    #[wasm_bindgen(js_name = isRbf)]
+    pub fn is_rbf(&self) -> bool {
+        Sequence(self.0).is_rbf()
+    }
+
+    #[wasm_bindgen(js_name = isFinal)]
+    pub fn is_final(&self) -> bool {
+        Sequence(self.0).is_final()
+    }
+}
+
 #[wasm_bindgen(getter_with_clone)]
 #[derive(Clone, Serialize, Deserialize)]
 pub struct WasmDetailledTxIn {
@@ -132,7 +190,11 @@ impl Into<WasmDetailledTxIn> for DetailledTxIn {
 pub struct WasmTxOut {
     pub value: u64,
     pub script_pubkey: WasmScript,
+    pub script_type: String,
+    pub script_asm: String,
     pub is_mine: bool,
+    pub is_change: bool,
+    pub derivation_index: Option<u32>,
     pub address: Option<String>,
 }
 
@@ -141,8 +203,30 @@ impl Into<WasmTxOut> for DetailledTxOutput {
         WasmTxOut {
             value: self.value,
             script_pubkey: self.script_pubkey.into(),
+            script_type: self.script_type,
+            script_asm: self.script_asm,
             address: self.address.map(|a| a.to_string()),
             is_mine: self.is_mine,
+            is_change: self.is_change,
+            derivation_index: self.derivation_index,
+        }
+    }
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum WasmTransactionKind {
+    External,
+    InternalTransfer { accounts: Vec<String> },
+}
+
+impl Into<WasmTransactionKind> for TransactionKind {
+    fn into(self) -> WasmTransactionKind {
+        match self {
+            TransactionKind::External => WasmTransactionKind::External,
+            TransactionKind::InternalTransfer { accounts } => WasmTransactionKind::InternalTransfer {
+                accounts: accounts.into_iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+            },
         }
     }
 }
@@ -154,12 +238,20 @@ pub struct WasmTransactionDetails {
     pub txid: String,
     pub received: u64,
     pub sent: u64,
+    /// Net value (sats) of the transaction for the account, negative for a
+    /// spend
+    pub value: i64,
     pub fee: Option<u64>,
     pub size: u64,
+    pub weight: u64,
     pub time: WasmTransactionTime,
+    pub confirmations: Option<u32>,
+    pub replaceable: bool,
     pub inputs: Vec<WasmDetailledTxIn>,
     pub outputs: Vec<WasmTxOut>,
     pub account_derivation_path: String,
+    pub kind: WasmTransactionKind,
+    pub raw_hex: String,
 }
 
 // We need this wrapper because unfortunately, tsify doesn't support
@@ -174,18 +266,58 @@ pub struct WasmTransactionDetailsData {
 #[wasm_bindgen(getter_with_clone)]
 pub struct WasmTransactionDetailsArray(pub Vec<WasmTransactionDetailsData>);
 
+/// Cursor pulling an account's transactions page by page
+#[wasm_bindgen]
+pub struct WasmTransactionsCursor(TransactionsCursor<WalletWebPersister>);
+
+impl From<TransactionsCursor<WalletWebPersister>> for WasmTransactionsCursor {
+    fn from(value: TransactionsCursor<WalletWebPersister>) -> Self {
+        Self(value)
+    }
+}
+
+#[wasm_bindgen]
+impl WasmTransactionsCursor {
+    /// Returns the next `take` transactions, empty once all of them were
+    /// returned
+    #[wasm_bindgen(js_name = nextPage)]
+    pub async fn next_page(&mut self, take: usize) -> Result<WasmTransactionDetailsArray, js_sys::Error> {
+        let transactions = self
+            .0
+            .next_page(take)
+            .await
+            .map_err(|e| e.to_js_error())?
+            .into_iter()
+            .map(|tx| WasmTransactionDetailsData { Data: tx.into() })
+            .collect::<Vec<_>>();
+
+        Ok(WasmTransactionDetailsArray(transactions))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn remaining(&self) -> usize {
+        self.0.remaining()
+    }
+}
+
 impl Into<WasmTransactionDetails> for TransactionDetails {
     fn into(self) -> WasmTransactionDetails {
         WasmTransactionDetails {
             txid: self.txid.to_string(),
             received: self.received,
             sent: self.sent,
+            value: self.value(),
             fee: self.fees,
             size: self.vbytes_size,
+            weight: self.weight,
             time: self.time.into(),
+            confirmations: self.confirmations,
+            replaceable: self.replaceable,
             inputs: self.inputs.into_iter().map(|input| input.into()).collect::<Vec<_>>(),
             outputs: self.outputs.into_iter().map(|output| output.into()).collect::<Vec<_>>(),
             account_derivation_path: self.account_derivation_path.to_string(),
+            kind: self.kind.into(),
+            raw_hex: self.raw_hex,
         }
     }
 }
@@ -204,6 +336,39 @@ pub async fn create_transaction_from_psbt(
     Ok(WasmTransactionDetailsData { Data: tx.into() })
 }
 
+#[wasm_bindgen(js_name = createTransactionFromHex)]
+pub async fn create_transaction_from_hex(
+    hex: String,
+    account: &WasmAccount,
+) -> Result<WasmTransactionDetailsData, js_sys::Error> {
+    let tx = TransactionDetails::from_hex(&hex, account.get_inner())
+        .await
+        .map_err(|e| e.to_js_error())?;
+
+    Ok(WasmTransactionDetailsData { Data: tx.into() })
+}
+
+#[derive(Tsify, Serialize, Deserialize, Clone)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct WasmTransactionFiatValue {
+    /// Net value of the transaction, negative for a spend
+    pub value: f64,
+    pub fee: Option<f64>,
+}
+
+#[wasm_bindgen(js_name = getTransactionFiatValue)]
+pub fn get_transaction_fiat_value(
+    transaction: WasmTransactionDetails,
+    exchange_rate: WasmApiExchangeRate,
+) -> WasmTransactionFiatValue {
+    let exchange_rate: ApiExchangeRate = exchange_rate.into();
+
+    WasmTransactionFiatValue {
+        value: convert_to_fiat(transaction.value, &exchange_rate),
+        fee: transaction.fee.map(|fee| convert_to_fiat(fee as i64, &exchange_rate)),
+    }
+}
+
 #[derive(Tsify, Serialize, Deserialize, Clone)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
 #[allow(non_snake_case)]
@@ -229,3 +394,63 @@ impl Into<WasmTransactionTime> for TransactionTime {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use andromeda_bitcoin::ScriptBuf;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::{WasmScript, WasmSequence};
+    use crate::common::types::WasmNetwork;
+
+    #[wasm_bindgen_test]
+    fn should_encode_relative_blocks() {
+        let sequence = WasmSequence::from_relative_blocks(144);
+
+        assert_eq!(sequence.0, 144);
+        assert!(!sequence.is_final());
+    }
+
+    #[wasm_bindgen_test]
+    fn should_signal_rbf() {
+        let sequence = WasmSequence::rbf();
+
+        assert_eq!(sequence.0, 0xfffffffd);
+        assert!(sequence.is_rbf());
+        assert!(!sequence.is_final());
+    }
+
+    #[wasm_bindgen_test]
+    fn should_be_final() {
+        let sequence = WasmSequence::final_sequence();
+
+        assert!(sequence.is_final());
+        assert!(!sequence.is_rbf());
+    }
+
+    #[wasm_bindgen_test]
+    fn should_round_relative_seconds_up() {
+        let sequence = WasmSequence::from_relative_seconds(1000).unwrap();
+
+        // 2 intervals of 512 seconds, with the type flag set
+        assert_eq!(sequence.0, (1 << 22) | 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_return_no_address_for_op_return_script() {
+        let script: WasmScript = ScriptBuf::new_op_return([0xde, 0xad, 0xbe, 0xef]).into();
+
+        assert!(script.to_address(WasmNetwork::Bitcoin).unwrap().is_none());
+    }
+
+    #[wasm_bindgen_test]
+    fn should_return_address_for_standard_script() {
+        let script = WasmScript(
+            ScriptBuf::from_hex("0014ef651e82d6717ee9f1c74e8acd5bd1bf639b94a1")
+                .unwrap()
+                .to_bytes(),
+        );
+
+        assert!(script.to_address(WasmNetwork::Bitcoin).unwrap().is_some());
+    }
+}
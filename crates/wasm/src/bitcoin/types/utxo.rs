@@ -29,3 +29,10 @@ impl Into<WasmUtxo> for LocalOutput {
 
 #[wasm_bindgen(getter_with_clone)]
 pub struct WasmUtxoArray(pub Vec<WasmUtxo>);
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct WasmUtxoPage {
+    pub utxos: Vec<WasmUtxo>,
+    /// Total number of unspent outputs of the account
+    pub total: usize,
+}
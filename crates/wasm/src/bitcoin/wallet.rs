@@ -1,14 +1,20 @@
 use std::str::FromStr;
 
-use andromeda_bitcoin::{error::Error as BitcoinError, wallet::Wallet, DerivationPath};
+use andromeda_bitcoin::{
+    error::Error as BitcoinError,
+    wallet::{AccountFingerprint, Wallet},
+    DerivationPath, Xpub,
+};
 use andromeda_common::error::Error;
 use wasm_bindgen::prelude::*;
 
 use super::{
     account::WasmAccount,
+    mnemonic::WasmMnemonic,
     storage::{WalletWebConnector, WalletWebPersister, WalletWebPersisterFactory},
     types::{
-        balance::WasmBalanceWrapper,
+        balance::{WasmAccountBalance, WasmAccountBalances, WasmBalanceWrapper},
+        defined::WasmWordCount,
         derivation_path::WasmDerivationPath,
         pagination::{WasmPagination, WasmSortOrder},
         transaction::{WasmTransactionDetailsArray, WasmTransactionDetailsData},
@@ -48,6 +54,27 @@ pub struct WasmDiscoveredAccounts {
     pub data: Vec<WasmDiscoveredAccount>,
 }
 
+#[wasm_bindgen(getter_with_clone)]
+pub struct WasmDerivationPaths {
+    pub data: Vec<WasmDerivationPath>,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
+pub struct WasmAccountFingerprint {
+    pub master: String,
+    pub account: String,
+}
+
+impl From<AccountFingerprint> for WasmAccountFingerprint {
+    fn from(value: AccountFingerprint) -> Self {
+        Self {
+            master: value.master,
+            account: value.account,
+        }
+    }
+}
+
 #[wasm_bindgen]
 impl WasmWallet {
     #[wasm_bindgen(constructor)]
@@ -61,6 +88,8 @@ impl WasmWallet {
         Ok(Self { inner: wallet })
     }
 
+    /// Adds an account at the given derivation path. If one is already
+    /// registered at this path, the existing account is returned.
     #[wasm_bindgen(js_name = addAccount)]
     pub fn add_account(&mut self, script_type: u8, derivation_path: String) -> Result<WasmAccount, js_sys::Error> {
         let factory = WalletWebPersisterFactory;
@@ -80,6 +109,23 @@ impl WasmWallet {
         Ok(account_arc.into())
     }
 
+    /// Adds a watch-only account from an account-level extended public key.
+    /// Its derivation path is inferred from the script type and the key.
+    #[wasm_bindgen(js_name = addWatchOnlyAccount)]
+    pub fn add_watch_only_account(&mut self, xpub: String, script_type: u8) -> Result<WasmAccount, js_sys::Error> {
+        let factory = WalletWebPersisterFactory;
+
+        let account_xpub = Xpub::from_str(&xpub).map_err(|e| BitcoinError::from(e).to_js_error())?;
+        let script_type = script_type.try_into().map_err(|e: Error| e.to_js_error())?;
+
+        let account_arc = self
+            .inner
+            .add_watch_only_account(script_type, account_xpub, factory)
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(account_arc.into())
+    }
+
     #[wasm_bindgen(js_name = discoverAccounts)]
     pub async fn discover_accounts(
         &self,
@@ -116,6 +162,30 @@ impl WasmWallet {
             .map(|account| account.into())
     }
 
+    #[wasm_bindgen(js_name = listAccounts)]
+    pub fn list_accounts(&self) -> WasmDerivationPaths {
+        let derivation_paths = self
+            .inner
+            .list_accounts()
+            .into_iter()
+            .map(|derivation_path| derivation_path.into())
+            .collect::<Vec<_>>();
+
+        WasmDerivationPaths { data: derivation_paths }
+    }
+
+    #[wasm_bindgen(js_name = removeAccount)]
+    pub fn remove_account(&mut self, derivation_path: String) -> Result<(), js_sys::Error> {
+        let derivation_path =
+            DerivationPath::from_str(&derivation_path).map_err(|e| BitcoinError::from(e).to_js_error())?;
+
+        self.inner
+            .remove_account(&derivation_path)
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(())
+    }
+
     #[wasm_bindgen(js_name = getBalance)]
     pub async fn get_balance(&self) -> Result<WasmBalanceWrapper, js_sys::Error> {
         let balance = self.inner.get_balance().await.map_err(|e| e.to_js_error())?;
@@ -123,6 +193,22 @@ impl WasmWallet {
         Ok(WasmBalanceWrapper { data: balance.into() })
     }
 
+    #[wasm_bindgen(js_name = getBalancePerAccount)]
+    pub async fn get_balance_per_account(&self) -> WasmAccountBalances {
+        let balances = self
+            .inner
+            .get_balance_per_account()
+            .await
+            .into_iter()
+            .map(|(derivation_path, balance)| WasmAccountBalance {
+                account_key: derivation_path.into(),
+                data: balance.into(),
+            })
+            .collect::<Vec<_>>();
+
+        WasmAccountBalances(balances)
+    }
+
     #[wasm_bindgen(js_name = getTransactions)]
     pub async fn get_transactions(
         &self,
@@ -160,11 +246,67 @@ impl WasmWallet {
         })
     }
 
+    /// Returns the master fingerprint the given mnemonic and passphrase would
+    /// produce, so that users can confirm they typed the expected passphrase.
+    #[wasm_bindgen(js_name = fingerprintForPassphrase)]
+    pub fn fingerprint_for_passphrase(
+        bip39_mnemonic: String,
+        bip38_passphrase: Option<String>,
+    ) -> Result<String, js_sys::Error> {
+        let fingerprint = Wallet::<WalletWebConnector, WalletWebPersister>::fingerprint_for_passphrase(
+            bip39_mnemonic,
+            bip38_passphrase,
+        )
+        .map_err(|e| e.to_js_error())?;
+        Ok(fingerprint)
+    }
+
+    /// Derives a child mnemonic from the wallet master key as defined in
+    /// BIP85.
+    #[wasm_bindgen(js_name = deriveBip85Mnemonic)]
+    pub fn derive_bip85_mnemonic(&self, index: u32, word_count: WasmWordCount) -> Result<WasmMnemonic, js_sys::Error> {
+        let mnemonic = self
+            .inner
+            .derive_bip85_mnemonic(index, word_count.into())
+            .map_err(|e| e.to_js_error())?;
+        Ok(mnemonic.into())
+    }
+
     #[wasm_bindgen(js_name = getFingerprint)]
     pub fn get_fingerprint(&self) -> String {
         self.inner.get_fingerprint()
     }
 
+    #[wasm_bindgen(js_name = getAccountFingerprint)]
+    pub fn get_account_fingerprint(&self, derivation_path: String) -> Result<WasmAccountFingerprint, js_sys::Error> {
+        let derivation_path =
+            DerivationPath::from_str(&derivation_path).map_err(|e| BitcoinError::from(e).to_js_error())?;
+
+        let fingerprint = self
+            .inner
+            .get_account_fingerprint(&derivation_path)
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(fingerprint.into())
+    }
+
+    #[wasm_bindgen(js_name = matchesAccountFingerprint)]
+    pub fn matches_account_fingerprint(
+        &self,
+        derivation_path: String,
+        fingerprint: String,
+    ) -> Result<bool, js_sys::Error> {
+        let derivation_path =
+            DerivationPath::from_str(&derivation_path).map_err(|e| BitcoinError::from(e).to_js_error())?;
+
+        let matches = self
+            .inner
+            .matches_account_fingerprint(&derivation_path, &fingerprint)
+            .map_err(|e| e.to_js_error())?;
+
+        Ok(matches)
+    }
+
     #[wasm_bindgen(js_name = clearStore)]
     pub fn clear_store(&self) -> Result<(), js_sys::Error> {
         self.inner.clear_store().map_err(|e| e.to_js_error())?;
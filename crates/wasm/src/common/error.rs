@@ -1,7 +1,7 @@
 use std::error::Error;
 
 use andromeda_api::error::Error as ApiError;
-use andromeda_bitcoin::error::{Bip39Error, CreateTxError, Error as BitcoinError, InsufficientFundsError};
+use andromeda_bitcoin::error::{Bip39Error, CreateTxError, Error as BitcoinError};
 use andromeda_common::error::Error as CommonError;
 use andromeda_esplora::error::Error as EsploraError;
 use serde::Serialize;
@@ -19,111 +19,339 @@ pub fn json_to_jsvalue(json: Value) -> JsValue {
     json.serialize(&serializer).unwrap()
 }
 
-impl ErrorExt for ApiError {
-    fn to_js_error(self) -> JsValue {
-        match self {
-            ApiError::AuthSession(kind) => JsValue::from(&format!(
-                "AuthSession: A muon {kind} error was caused by a non-existent auth session",
-            )),
-            ApiError::AuthRefresh(kind) => JsValue::from(&format!(
-                "AuthRefresh: A muon {kind} error was caused by a failed auth refresh"
-            )),
-            ApiError::ForkAuthSession => JsValue::from(
-                &"ForkAuthSession: A muon error was caused by a failed auth via forked session".to_string(),
-            ),
-            ApiError::ForkSession => {
-                JsValue::from(&"ForkSession: A muon error was caused by a failed fork session".to_string())
-            }
-            ApiError::LoginError => JsValue::from(&"LoginError: A muon error was caused by a failed login".to_string()),
-            ApiError::UnsupportedTwoFactor => {
-                JsValue::from(&"UnsupportedTwoFactor: A muon error was caused by unsupported TwoFactor".to_string())
-            }
-            ApiError::MuonError(me) => JsValue::from(&format!(
-                "MuonError: {me} (caused by: {source:?})",
-                source = me.source(),
-            )),
-            ApiError::BitcoinDeserialize(bde) => {
-                JsValue::from(&format!("BitcoinDeserializeError occurred: {:?}", bde.source()))
-            }
-            ApiError::HexToArrayDecoding(hde) => {
-                JsValue::from(&format!("HexToArrayDecoding error occurred: {:?}", hde.source()))
-            }
-            ApiError::HexToBytesErrorDecoding(hde) => {
-                JsValue::from(&format!("HexToBytesErrorDecoding error occurred: {:?}", hde.source()))
-            }
-            ApiError::Http => JsValue::from("HTTP error occurred"),
-            ApiError::ErrorCode(status, error) => json_to_jsvalue(json!({
-                "kind": "HTTPError",
-                "status": status.as_u16(),
-                "code": error.Code,
-                "error": error.Error,
-                "details": error.Details
-            })),
-            ApiError::Deserialize(err) => JsValue::from(&err),
-            ApiError::MuonAppVersion(err) => JsValue::from(&format!("MuonAppVersion occurred: {:?}", err.source())),
-            ApiError::MuonStatus(err) => JsValue::from(&format!("MuonStatusError occurred: {:?}", err.source())),
-            ApiError::Utf8Error(err) => JsValue::from(&format!("Utf8Error occurred: {:?}", err.source())),
+fn api_error_kind(error: &ApiError) -> &'static str {
+    match error {
+        ApiError::AuthSession(_) => "AuthSession",
+        ApiError::AuthRefresh(_) => "AuthRefresh",
+        ApiError::ForkAuthSession => "ForkAuthSession",
+        ApiError::ForkSession => "ForkSession",
+        ApiError::LoginError => "LoginError",
+        ApiError::UnsupportedTwoFactor => "UnsupportedTwoFactor",
+        ApiError::MuonAppVersion(_) => "MuonAppVersion",
+        ApiError::MuonStatus(_) => "MuonStatus",
+        ApiError::MuonError(_) => "MuonError",
+        ApiError::BitcoinDeserialize(_) => "BitcoinDeserialize",
+        ApiError::HexToArrayDecoding(_) => "HexToArrayDecoding",
+        ApiError::HexToBytesErrorDecoding(_) => "HexToBytesErrorDecoding",
+        ApiError::BitcoinAddressParse(_) => "BitcoinAddressParse",
+        ApiError::Http => "Http",
+        ApiError::RequestTimeout => "RequestTimeout",
+        // Kind the frontend already branches on
+        ApiError::ErrorCode(..) => "HTTPError",
+        ApiError::Deserialize(_) => "Deserialize",
+        ApiError::Utf8Error(_) => "Utf8Error",
+    }
+}
+
+fn create_tx_error_kind(error: &CreateTxError) -> &'static str {
+    match error {
+        CreateTxError::SpendingPolicyRequired(_) => "SpendingPolicyRequired",
+        CreateTxError::LockTime { .. } => "LockTime",
+        CreateTxError::FeeTooLow { .. } => "FeeTooLow",
+        CreateTxError::FeeRateTooLow { .. } => "FeeRateTooLow",
+        CreateTxError::NoUtxosSelected => "NoUtxosSelected",
+        CreateTxError::OutputBelowDustLimit(_) => "OutputBelowDustLimit",
+        CreateTxError::NoRecipients => "NoRecipients",
+        CreateTxError::UnknownUtxo => "UnknownUtxo",
+        CreateTxError::MissingNonWitnessUtxo(_) => "MissingNonWitnessUtxo",
+        _ => "CreateTx",
+    }
+}
+
+fn bitcoin_error_kind(error: &BitcoinError) -> &'static str {
+    match error {
+        BitcoinError::AccountNotFound => "AccountNotFound",
+        BitcoinError::NoAccountBound => "NoAccountBound",
+        BitcoinError::CreateWithPersistError => "CreateWithPersistError",
+        BitcoinError::LoadWithPersistError => "LoadWithPersistError",
+        BitcoinError::PersistError => "PersistError",
+        BitcoinError::MiniscriptPsbt(_) => "MiniscriptPsbt",
+        BitcoinError::CreateTx(error) => create_tx_error_kind(error),
+        BitcoinError::InsufficientFunds { .. } => "InsufficientFunds",
+        BitcoinError::BuildFeeBump(_) => "BuildFeeBump",
+        BitcoinError::AddUtxo(_) => "AddUtxo",
+        BitcoinError::AddForeignUtxo(_) => "AddForeignUtxo",
+        BitcoinError::Signer(_) => "Signer",
+        BitcoinError::CannotConnect(_) => "CannotConnect",
+        BitcoinError::Bip32(_) => "Bip32",
+        BitcoinError::Bip39(_) => "Bip39",
+        BitcoinError::EsploraClient(_) => "EsploraClient",
+        BitcoinError::Api(_) => "Api",
+        BitcoinError::HexToArray(_) => "HexToArray",
+        BitcoinError::HexToBytes(_) => "HexToBytes",
+        BitcoinError::BitcoinAddressParse(_) => "BitcoinAddressParse",
+        BitcoinError::FromScript(_) => "FromScript",
+        BitcoinError::Descriptor(_) => "Descriptor",
+        BitcoinError::ExtractTx(_) => "ExtractTx",
+        BitcoinError::Psbt(_) => "Psbt",
+        BitcoinError::InvalidAddress(_) => "InvalidAddress",
+        BitcoinError::InvalidTxId(_) => "InvalidTxId",
+        BitcoinError::InvalidTransactionHex(_) => "InvalidTransactionHex",
+        BitcoinError::InvalidAmount(_) => "InvalidAmount",
+        BitcoinError::AmountOverflow(_) => "AmountOverflow",
+        BitcoinError::InvalidFeeRate(_) => "InvalidFeeRate",
+        BitcoinError::InvalidEntropyLength { .. } => "InvalidEntropyLength",
+        BitcoinError::AmountAboveMaxMoney(_) => "AmountAboveMaxMoney",
+        BitcoinError::AddressNotOwned(_) => "AddressNotOwned",
+        BitcoinError::AddressIndexOutOfRange => "AddressIndexOutOfRange",
+        BitcoinError::ScriptTypePathMismatch { .. } => "ScriptTypePathMismatch",
+        BitcoinError::AccountScriptTypeMismatch { .. } => "AccountScriptTypeMismatch",
+        BitcoinError::AddressNetworkMismatch { .. } => "AddressNetworkMismatch",
+        BitcoinError::InvalidDescriptor(_) => "InvalidDescriptor",
+        BitcoinError::InvalidAccountXpub(_) => "InvalidAccountXpub",
+        BitcoinError::AccountXpubMismatch(_) => "AccountXpubMismatch",
+        BitcoinError::WatchOnlyCannotSign => "WatchOnlyCannotSign",
+        BitcoinError::MessageSigningNotSupported => "MessageSigningNotSupported",
+        BitcoinError::InvalidData(_) => "InvalidData",
+        BitcoinError::TransactionNotFound => "TransactionNotFound",
+        BitcoinError::RecipientNotFound(_) => "RecipientNotFound",
+        BitcoinError::UtxoNotFound(_) => "UtxoNotFound",
+        BitcoinError::LockTimeConversion(_) => "LockTimeConversion",
+        BitcoinError::RelativeLockTimeOverflow(_) => "RelativeLockTimeOverflow",
+        BitcoinError::LocktimeNotSatisfied { .. } => "LocktimeNotSatisfied",
+        BitcoinError::SyncCancelled => "SyncCancelled",
+        BitcoinError::InvalidProofOfReserves(_) => "InvalidProofOfReserves",
+        BitcoinError::InvalidSnapshot(_) => "InvalidSnapshot",
+        BitcoinError::Other(_) => "Other",
+    }
+}
+
+/// Builds the detailled error object surfaced to JS:
+/// - `id`: stable identifier made of the error domain and kind
+/// - `code`: same as `id`, except for API HTTP errors which keep the numeric
+///   code returned by the API
+/// - `kind`: machine-readable kind the frontend can branch on
+/// - `message`: human-readable description of the error
+///
+/// Kind-specific `fields` are merged into the object, they can't override
+/// the ones above.
+fn detailled_error(domain: &str, kind: &str, message: impl ToString, fields: Value) -> Value {
+    let mut error = json!({
+        "id": format!("{domain}.{kind}"),
+        "code": format!("{domain}.{kind}"),
+        "kind": kind,
+        "message": message.to_string(),
+    });
+
+    if let (Some(error), Value::Object(fields)) = (error.as_object_mut(), fields) {
+        for (key, value) in fields {
+            error.entry(key).or_insert(value);
         }
     }
+
+    error
 }
 
-impl ErrorExt for BitcoinError {
-    fn to_js_error(self) -> JsValue {
-        let common_error = JsValue::from(
-            &format!("Wasm error occurred in Bitcoin: {}", self),
-            // json!(self), TODO: fix this to have more detailed errors
-        );
+fn api_error_details(error: ApiError) -> Value {
+    let kind = api_error_kind(&error);
+
+    let message = match &error {
+        ApiError::AuthSession(kind) => {
+            format!("AuthSession: A muon {kind} error was caused by a non-existent auth session")
+        }
+        ApiError::AuthRefresh(kind) => format!("AuthRefresh: A muon {kind} error was caused by a failed auth refresh"),
+        ApiError::ForkAuthSession => {
+            "ForkAuthSession: A muon error was caused by a failed auth via forked session".to_string()
+        }
+        ApiError::ForkSession => "ForkSession: A muon error was caused by a failed fork session".to_string(),
+        ApiError::LoginError => "LoginError: A muon error was caused by a failed login".to_string(),
+        ApiError::UnsupportedTwoFactor => {
+            "UnsupportedTwoFactor: A muon error was caused by unsupported TwoFactor".to_string()
+        }
+        ApiError::MuonError(me) => format!("MuonError: {me} (caused by: {source:?})", source = me.source()),
+        ApiError::BitcoinDeserialize(bde) => format!("BitcoinDeserializeError occurred: {:?}", bde.source()),
+        ApiError::HexToArrayDecoding(hde) => format!("HexToArrayDecoding error occurred: {:?}", hde.source()),
+        ApiError::HexToBytesErrorDecoding(hde) => format!("HexToBytesErrorDecoding error occurred: {:?}", hde.source()),
+        ApiError::BitcoinAddressParse(err) => format!("BitcoinAddressParse error occurred: {:?}", err.source()),
+        ApiError::Http => "HTTP error occurred".to_string(),
+        ApiError::RequestTimeout => "RequestTimeout: The request took too long to complete".to_string(),
+        ApiError::ErrorCode(status, error) => {
+            let mut details = detailled_error(
+                "api",
+                kind,
+                &error.Error,
+                json!({
+                    "status": status.as_u16(),
+                    "error": error.Error,
+                    "details": error.Details
+                }),
+            );
+            details["code"] = json!(error.Code);
+
+            return details;
+        }
+        ApiError::Deserialize(err) => err.clone(),
+        ApiError::MuonAppVersion(err) => format!("MuonAppVersion occurred: {:?}", err.source()),
+        ApiError::MuonStatus(err) => format!("MuonStatusError occurred: {:?}", err.source()),
+        ApiError::Utf8Error(err) => format!("Utf8Error occurred: {:?}", err.source()),
+    };
+
+    detailled_error("api", kind, message, json!({}))
+}
+
+fn bitcoin_error_details(error: BitcoinError) -> Value {
+    let message = format!("Wasm error occurred in Bitcoin: {}", error);
 
-        match self {
-            BitcoinError::CreateTx(error) => match error {
-                CreateTxError::CoinSelection(InsufficientFundsError { needed, available }) => json_to_jsvalue(json!({
-                    "kind": "InsufficientFunds",
-                    "needed": needed,
-                    "available": available,
-                })),
-                CreateTxError::OutputBelowDustLimit(limit) => json_to_jsvalue(json!({
-                    "kind": "OutputBelowDustLimit",
+    match error {
+        BitcoinError::InsufficientFunds { needed, available } => detailled_error(
+            "bitcoin",
+            "InsufficientFunds",
+            message,
+            json!({
+                "needed": needed,
+                "available": available,
+            }),
+        ),
+        BitcoinError::AddressNetworkMismatch { expected, got } => detailled_error(
+            "bitcoin",
+            "AddressNetworkMismatch",
+            message,
+            json!({
+                "expected": expected.to_string(),
+                "got": got.to_string(),
+            }),
+        ),
+        BitcoinError::InvalidEntropyLength { got_bits } => detailled_error(
+            "bitcoin",
+            "InvalidEntropyLength",
+            message,
+            json!({
+                "gotBits": got_bits,
+            }),
+        ),
+        BitcoinError::CreateTx(error) => match error {
+            CreateTxError::OutputBelowDustLimit(limit) => detailled_error(
+                "bitcoin",
+                "OutputBelowDustLimit",
+                message,
+                json!({
                     "limit": limit,
-                })),
-                _ => common_error,
-            },
-            BitcoinError::Bip39(error) => match error {
-                Bip39Error::BadWordCount(count) => json_to_jsvalue(json!({
-                    "kind": "BadWordCount",
+                }),
+            ),
+            error => detailled_error("bitcoin", create_tx_error_kind(&error), message, json!({})),
+        },
+        BitcoinError::Bip39(error) => match error {
+            Bip39Error::BadWordCount(count) => detailled_error(
+                "bitcoin",
+                "BadWordCount",
+                message,
+                json!({
                     "count": count,
-                })),
-                Bip39Error::UnknownWord(index) => json_to_jsvalue(json!({
-                    "kind": "UnknownWord",
+                }),
+            ),
+            Bip39Error::UnknownWord(index) => detailled_error(
+                "bitcoin",
+                "UnknownWord",
+                message,
+                json!({
                     "index": index,
-                })),
-                Bip39Error::BadEntropyBitCount(bit) => json_to_jsvalue(json!({
-                    "kind": "BadEntropyBitCount",
+                }),
+            ),
+            Bip39Error::BadEntropyBitCount(bit) => detailled_error(
+                "bitcoin",
+                "BadEntropyBitCount",
+                message,
+                json!({
                     "bit": bit,
-                })),
-                Bip39Error::InvalidChecksum => json_to_jsvalue(json!({
-                    "kind": "InvalidChecksum",
-                })),
-                Bip39Error::AmbiguousLanguages(_) => json_to_jsvalue(json!({
-                    "kind": "AmbiguousLanguages",
-                })),
-            },
-            BitcoinError::EsploraClient(EsploraError::ApiError(error)) => error.to_js_error(),
-            _ => common_error,
-        }
+                }),
+            ),
+            Bip39Error::InvalidChecksum => detailled_error("bitcoin", "InvalidChecksum", message, json!({})),
+            Bip39Error::AmbiguousLanguages(_) => detailled_error("bitcoin", "AmbiguousLanguages", message, json!({})),
+        },
+        BitcoinError::EsploraClient(EsploraError::ApiError(error)) => api_error_details(error),
+        BitcoinError::Api(error) => api_error_details(error),
+        error => detailled_error("bitcoin", bitcoin_error_kind(&error), message, json!({})),
     }
 }
 
-impl ErrorExt for CommonError {
-    fn to_js_error(self) -> JsValue {
-        match self {
-            CommonError::InvalidNetwork(network) => json_to_jsvalue(json!({
-                "kind": "InvalidNetwork",
+fn common_error_details(error: CommonError) -> Value {
+    let message = error.to_string();
+
+    match error {
+        CommonError::InvalidNetwork(network) => detailled_error(
+            "common",
+            "InvalidNetwork",
+            message,
+            json!({
                 "network": network,
-            })),
-            CommonError::InvalidScriptType(script_type) => json_to_jsvalue(json!({
-                "kind":"InvalidScriptType",
+            }),
+        ),
+        CommonError::InvalidScriptType(script_type) => detailled_error(
+            "common",
+            "InvalidScriptType",
+            message,
+            json!({
                 "scriptType": script_type,
-            })),
-        }
+            }),
+        ),
+    }
+}
+
+impl ErrorExt for ApiError {
+    fn to_js_error(self) -> JsValue {
+        json_to_jsvalue(api_error_details(self))
+    }
+}
+
+impl ErrorExt for BitcoinError {
+    fn to_js_error(self) -> JsValue {
+        json_to_jsvalue(bitcoin_error_details(self))
+    }
+}
+
+impl ErrorExt for CommonError {
+    fn to_js_error(self) -> JsValue {
+        json_to_jsvalue(common_error_details(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use andromeda_api::error::Error as ApiError;
+    use andromeda_bitcoin::error::{CreateTxError, Error as BitcoinError};
+    use andromeda_common::error::Error as CommonError;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::{api_error_details, bitcoin_error_details, common_error_details};
+
+    #[wasm_bindgen_test]
+    fn should_attach_kind_and_code_to_insufficient_funds() {
+        let error = BitcoinError::InsufficientFunds {
+            needed: 10_000,
+            available: 4_000,
+        };
+
+        let details = bitcoin_error_details(error);
+
+        assert_eq!(details["kind"], "InsufficientFunds");
+        assert_eq!(details["code"], "bitcoin.InsufficientFunds");
+        assert_eq!(details["needed"], 10_000);
+        assert_eq!(details["available"], 4_000);
+        assert_eq!(
+            details["message"],
+            "Wasm error occurred in Bitcoin: Insufficient funds: 4000 sat available of 10000 sat needed"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn should_derive_kind_from_variant() {
+        let details = bitcoin_error_details(BitcoinError::AccountNotFound);
+        assert_eq!(details["kind"], "AccountNotFound");
+        assert_eq!(details["id"], "bitcoin.AccountNotFound");
+        assert_eq!(details["code"], "bitcoin.AccountNotFound");
+
+        let details = bitcoin_error_details(BitcoinError::CreateTx(CreateTxError::NoRecipients));
+        assert_eq!(details["kind"], "NoRecipients");
+        assert_eq!(details["code"], "bitcoin.NoRecipients");
+
+        let details = api_error_details(ApiError::RequestTimeout);
+        assert_eq!(details["kind"], "RequestTimeout");
+        assert_eq!(details["id"], "api.RequestTimeout");
+        assert_eq!(details["code"], "api.RequestTimeout");
+
+        let details = common_error_details(CommonError::InvalidNetwork("foo".to_string()));
+        assert_eq!(details["kind"], "InvalidNetwork");
+        assert_eq!(details["network"], "foo");
+        assert_eq!(details["message"], "Invalid network: foo");
     }
 }
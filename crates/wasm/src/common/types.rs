@@ -1,5 +1,5 @@
 use andromeda_bitcoin::{BdkNetwork, KeychainKind};
-use andromeda_common::{BitcoinUnit, Network, ScriptType};
+use andromeda_common::{utils::format_amount, BitcoinUnit, Network, ScriptType};
 use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
@@ -32,8 +32,15 @@ impl Into<BitcoinUnit> for WasmBitcoinUnit {
     }
 }
 
+#[wasm_bindgen(js_name = formatAmount)]
+pub fn format_amount_in_unit(sats: u64, unit: WasmBitcoinUnit, group_separator: bool) -> String {
+    format_amount(sats, unit.into(), group_separator)
+}
+
+/// JS representation of [`Network`]. Conversions from and to other network
+/// types go through it, so that mappings are only defined once.
 #[wasm_bindgen]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WasmNetwork {
     /// Mainnet Bitcoin.
     Bitcoin,
@@ -61,31 +68,21 @@ impl From<Network> for WasmNetwork {
         match network {
             Network::Bitcoin => WasmNetwork::Bitcoin,
             Network::Testnet => WasmNetwork::Testnet,
-            Network::Regtest => WasmNetwork::Regtest,
             Network::Signet => WasmNetwork::Signet,
+            Network::Regtest => WasmNetwork::Regtest,
         }
     }
 }
 
 impl From<WasmNetwork> for BdkNetwork {
     fn from(network: WasmNetwork) -> Self {
-        match network {
-            WasmNetwork::Bitcoin => BdkNetwork::Bitcoin,
-            WasmNetwork::Testnet => BdkNetwork::Testnet,
-            WasmNetwork::Signet => BdkNetwork::Signet,
-            WasmNetwork::Regtest => BdkNetwork::Regtest,
-        }
+        Network::from(network).into()
     }
 }
 
 impl From<BdkNetwork> for WasmNetwork {
     fn from(network: BdkNetwork) -> Self {
-        match network {
-            BdkNetwork::Bitcoin => WasmNetwork::Bitcoin,
-            BdkNetwork::Regtest => WasmNetwork::Regtest,
-            BdkNetwork::Signet => WasmNetwork::Signet,
-            _ => WasmNetwork::Testnet, // default to testnet, might need to change that
-        }
+        Network::from(network).into()
     }
 }
 
@@ -166,3 +163,28 @@ impl FromBool for u8 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use andromeda_bitcoin::BdkNetwork;
+    use andromeda_common::Network;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::WasmNetwork;
+
+    #[wasm_bindgen_test]
+    fn should_convert_network_in_both_directions() {
+        for (wasm_network, network, bdk_network) in [
+            (WasmNetwork::Bitcoin, Network::Bitcoin, BdkNetwork::Bitcoin),
+            (WasmNetwork::Testnet, Network::Testnet, BdkNetwork::Testnet),
+            (WasmNetwork::Signet, Network::Signet, BdkNetwork::Signet),
+            (WasmNetwork::Regtest, Network::Regtest, BdkNetwork::Regtest),
+        ] {
+            assert_eq!(Network::from(wasm_network), network);
+            assert_eq!(WasmNetwork::from(network), wasm_network);
+
+            assert_eq!(BdkNetwork::from(wasm_network), bdk_network);
+            assert_eq!(WasmNetwork::from(bdk_network), wasm_network);
+        }
+    }
+}
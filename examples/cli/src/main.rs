@@ -172,6 +172,8 @@ async fn sync_account(
         url_prefix: None,
         store: None,
         proxy: None,
+        request_timeout: None,
+        on_unauthorized: None,
     };
 
     let proton_api_client = ProtonWalletApiClient::from_config(config).unwrap();
@@ -180,7 +182,7 @@ async fn sync_account(
 
     let chain = BlockchainClient::new(proton_api_client);
 
-    let update = chain.full_sync(&account, None).await.unwrap();
+    let update = chain.full_sync(&account, None, None).await.unwrap();
     account
         .apply_update(update)
         .await